@@ -0,0 +1,41 @@
+<?php
+
+// PHP Example Code
+
+namespace App\Models;
+
+interface Publishable
+{
+    const DRAFT = 'draft';
+
+    public function publish(): void;
+}
+
+trait HasSlug
+{
+    private static $separator = '-';
+
+    abstract protected function title(): string;
+
+    public static function slugify(string $value): string
+    {
+        return strtolower(str_replace(' ', self::$separator, $value));
+    }
+}
+
+enum Status: string
+{
+    case Draft = 'draft';
+    case Published = 'published';
+}
+
+final class Article implements Publishable
+{
+    use HasSlug;
+
+    public function publish(): void
+    {
+        $this->status = Status::Published;
+        Log::info(static::class);
+    }
+}
@@ -0,0 +1,23 @@
+// React(TypeScript) Example Code
+
+import React from 'react'
+import { Layout } from './Layout'
+import { UserCard } from './UserCard'
+
+interface ProfileProps {
+    user: User
+}
+
+export function Profile({ user }: ProfileProps) {
+    return (
+        <Layout.Page title={user.name}>
+            <UserCard user={user} />
+        </Layout.Page>
+    )
+}
+
+export const ProfileList = ({ users }: { users: User[] }) => (
+    <>
+        {users.map((user) => <Profile key={user.id} user={user} />)}
+    </>
+)
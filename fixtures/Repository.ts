@@ -0,0 +1,30 @@
+// TypeScript Example Code
+
+export interface Identifiable {
+    id: number
+    label(): string
+}
+
+export type Id = number | string
+
+export enum Status {
+    Draft = "draft",
+    Published = "published",
+}
+
+declare const VERSION: string
+declare function log(message: string): void
+
+export abstract class Repository<T extends Identifiable> {
+    protected items: T[] = []
+
+    abstract validate(item: T): boolean
+
+    find(id: Id): T | undefined
+    find(ids: Id[]): T[]
+    find(input: Id | Id[]) {
+        return Array.isArray(input)
+            ? this.items.filter((item) => input.includes(item.id))
+            : this.items.find((item) => item.id === input)
+    }
+}
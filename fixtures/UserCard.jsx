@@ -0,0 +1,23 @@
+// React(JavaScript) Example Code
+
+import React, { memo } from 'react'
+import Avatar from './Avatar'
+
+function UserCard({ user }) {
+    return (
+        <div className="user-card">
+            <Avatar src={user.avatarUrl} />
+            <UserName user={user} />
+        </div>
+    )
+}
+
+const UserName = ({ user }) => <span>{user.name}</span>
+
+export const UserList = memo(({ users }) => (
+    <ul>
+        {users.map((user) => <UserCard key={user.id} user={user} />)}
+    </ul>
+))
+
+export default UserCard
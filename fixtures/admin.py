@@ -0,0 +1,36 @@
+# Python Example Code
+
+from django.contrib import admin
+
+from .models import Choice, Question
+
+__all__ = ["QuestionAdmin", "register_all"]
+
+MAX_CHOICES = 3
+default_site = admin.site
+
+
+class ChoiceInline(admin.TabularInline):
+    model = Choice
+    extra = MAX_CHOICES
+
+
+@admin.register(Question)
+class QuestionAdmin(admin.ModelAdmin):
+    inlines = [ChoiceInline]
+    list_display = ("question_text", "pub_date", "was_published_recently")
+
+    @admin.display(boolean=True, description="Published recently?")
+    def was_published_recently(self, obj):
+        return obj.was_published_recently()
+
+    @staticmethod
+    def label(question):
+        return str(question)
+
+
+def register_all(site=default_site):
+    def register(model):
+        site.register(model)
+
+    register(Choice)
@@ -0,0 +1,16 @@
+<?php
+
+// PHP Example Code
+
+function retry(callable $callback, int $attempts = 3)
+{
+    $report = fn ($error) => $error->report();
+    for ($attempt = 1; $attempt <= $attempts; $attempt++) {
+        try {
+            return $callback($attempt);
+        } catch (Exception $error) {
+            $report($error);
+        }
+    }
+    return $fallback();
+}
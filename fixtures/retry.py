@@ -0,0 +1,15 @@
+# Python Example Code
+
+import logging
+
+
+def retry(func, attempts=3):
+    last_error = None
+    for attempt in range(attempts):
+        try:
+            return func(attempt)
+        except Exception as error:
+            last_error = error
+    report = logging.getLogger(__name__).error
+    report(last_error)
+    raise last_error
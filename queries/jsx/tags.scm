@@ -0,0 +1,41 @@
+; Function components
+
+(
+  (function_declaration
+    name: (identifier) @name) @definition.component
+  (#match? @name "^[A-Z]")
+)
+
+(
+  (variable_declarator
+    name: (identifier) @name
+    value: [
+      (arrow_function)
+      (function)
+      (call_expression
+        arguments: (arguments . [(arrow_function) (function)]))
+    ]) @definition.component
+  (#match? @name "^[A-Z]")
+)
+
+; Component usages
+
+(
+  [
+    (jsx_opening_element
+      name: (identifier) @name)
+    (jsx_self_closing_element
+      name: (identifier) @name)
+  ] @reference.component
+  (#match? @name "^[A-Z]")
+)
+
+(
+  [
+    (jsx_opening_element
+      name: (nested_identifier (identifier) @name .))
+    (jsx_self_closing_element
+      name: (nested_identifier (identifier) @name .))
+  ] @reference.component
+  (#match? @name "^[A-Z]")
+)
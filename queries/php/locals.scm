@@ -0,0 +1,28 @@
+; Scopes
+
+((function_definition) @local.scope
+ (#set! local.scope-inherits false))
+
+((method_declaration) @local.scope
+ (#set! local.scope-inherits false))
+
+((anonymous_function_creation_expression) @local.scope
+ (#set! local.scope-inherits false))
+
+(arrow_function) @local.scope
+
+; Parameters
+
+(simple_parameter name: (variable_name) @local.definition)
+(variadic_parameter name: (variable_name) @local.definition)
+(anonymous_function_use_clause (variable_name) @local.definition)
+(catch_clause (variable_name) @local.definition)
+
+; Assignments
+
+(assignment_expression left: (variable_name) @local.definition)
+(reference_assignment_expression left: (variable_name) @local.definition)
+(list_literal (variable_name) @local.definition)
+(foreach_statement (variable_name) @local.definition)
+(foreach_statement (pair (variable_name) @local.definition))
+(static_variable_declaration name: (variable_name) @local.definition)
@@ -0,0 +1,98 @@
+; Types other than classes
+
+(interface_declaration
+  name: (name) @name) @definition.interface
+
+(trait_declaration
+  name: (name) @name) @definition.trait
+
+(enum_declaration
+  name: (name) @name) @definition.enum
+
+(enum_case
+  name: (name) @name) @definition.enum_member
+
+; Class members
+
+(property_declaration
+  (property_element
+    (variable_name
+      (name) @name))) @definition.property
+
+(const_declaration
+  (const_element
+    (name) @name)) @definition.constant
+
+; Trait usage, like `use HasFactory, Notifiable;`
+
+(use_declaration
+  [
+    (name) @name
+    (qualified_name (name) @name)
+  ]) @reference.trait
+
+(class_interface_clause
+  [
+    (name) @name
+    (qualified_name (name) @name)
+  ]) @reference.implementation
+
+; Classes referred by static access, like `Post::class` or `User::where()`
+
+(class_constant_access_expression
+  .
+  [
+    (name) @name
+    (qualified_name (name) @name)
+  ]) @reference.class
+
+(scoped_call_expression
+  scope: [
+    (name) @name
+    (qualified_name (name) @name)
+  ]) @reference.class
+
+; Properties read through objects, like `$user->password` or the relation `$user->posts`
+
+(member_access_expression
+  name: (name) @name) @reference.property
+
+; Calls through local bindings
+
+(
+  (function_call_expression
+    function: (variable_name) @name) @reference.call
+  (#is-not? local)
+)
+
+(function_call_expression
+  function: (variable_name) @name) @reference.local
+
+; Classes, functions and calls, like the bundled query tags them
+
+(class_declaration
+  name: (name) @name) @definition.class
+
+(function_definition
+  name: (name) @name) @definition.function
+
+(method_declaration
+  name: (name) @name) @definition.function
+
+(object_creation_expression
+  [
+    (qualified_name (name) @name)
+    (variable_name (name) @name)
+  ]) @reference.class
+
+(function_call_expression
+  function: [
+    (qualified_name (name) @name)
+    (variable_name (name)) @name
+  ]) @reference.call
+
+(scoped_call_expression
+  name: (name) @name) @reference.call
+
+(member_call_expression
+  name: (name) @name) @reference.call
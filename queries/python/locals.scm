@@ -0,0 +1,26 @@
+; Scopes
+
+[
+  (function_definition)
+  (lambda)
+] @local.scope
+
+; Parameters
+
+(parameters (identifier) @local.definition)
+(lambda_parameters (identifier) @local.definition)
+(default_parameter name: (identifier) @local.definition)
+(typed_parameter (identifier) @local.definition)
+(typed_default_parameter name: (identifier) @local.definition)
+(list_splat_pattern (identifier) @local.definition)
+(dictionary_splat_pattern (identifier) @local.definition)
+
+; Assignments
+
+(assignment left: (identifier) @local.definition)
+(assignment left: (pattern_list (identifier) @local.definition))
+(augmented_assignment left: (identifier) @local.definition)
+(named_expression name: (identifier) @local.definition)
+(for_statement left: (identifier) @local.definition)
+(for_statement left: (pattern_list (identifier) @local.definition))
+(for_in_clause left: (identifier) @local.definition)
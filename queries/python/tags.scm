@@ -0,0 +1,46 @@
+; Methods, told apart from free functions
+
+(class_definition
+  body: (block
+    (function_definition
+      name: (identifier) @name) @definition.method))
+
+(class_definition
+  body: (block
+    (decorated_definition
+      definition: (function_definition
+        name: (identifier) @name) @definition.method)))
+
+; Class attributes, like model fields of Django
+
+(class_definition
+  body: (block
+    (expression_statement
+      (assignment
+        left: (identifier) @name) @definition.field)))
+
+; Module variables, whose upper case ones are constants by convention
+
+(
+  (module
+    (expression_statement
+      (assignment
+        left: (identifier) @name) @definition.constant))
+  (#match? @name "^[A-Z][A-Z0-9_]*$")
+)
+
+(module
+  (expression_statement
+    (assignment
+      left: (identifier) @name) @definition.variable))
+
+; Calls through local bindings
+
+(
+  (call
+    function: (identifier) @name) @reference.call
+  (#is-not? local)
+)
+
+(call
+  function: (identifier) @name) @reference.local
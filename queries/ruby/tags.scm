@@ -0,0 +1,117 @@
+; Method definitions
+
+(
+  (comment)* @doc
+  .
+  [
+    (method
+      name: (_) @name) @definition.method
+    (singleton_method
+      name: (_) @name) @definition.method
+  ]
+  (#strip! @doc "^#\\s*")
+  (#select-adjacent! @doc @definition.method)
+)
+
+(alias
+  name: (_) @name) @definition.method
+
+(setter
+  (identifier) @ignore)
+
+; Class definitions
+
+(
+  (comment)* @doc
+  .
+  [
+    (class
+      name: [
+        (constant) @name
+        (scope_resolution
+          name: (_) @name)
+      ]) @definition.class
+    (singleton_class
+      value: [
+        (constant) @name
+        (scope_resolution
+          name: (_) @name)
+      ]) @definition.class
+  ]
+  (#strip! @doc "^#\\s*")
+  (#select-adjacent! @doc @definition.class)
+)
+
+; Module definitions
+
+(
+  (module
+    name: [
+      (constant) @name
+      (scope_resolution
+        name: (_) @name)
+    ]) @definition.module
+)
+
+; Constant definitions
+
+(assignment
+  left: [
+    (constant) @name
+    (scope_resolution
+      name: (constant) @name)
+  ]) @definition.constant
+
+; Local variable definitions are not references
+
+(assignment left: (identifier) @ignore)
+(operator_assignment left: (identifier) @ignore)
+(left_assignment_list (identifier) @ignore)
+(rest_assignment (identifier) @ignore)
+(destructured_left_assignment (identifier) @ignore)
+
+(method_parameters (identifier) @ignore)
+(lambda_parameters (identifier) @ignore)
+(block_parameters (identifier) @ignore)
+(block_parameter (identifier) @ignore)
+(destructured_parameter (identifier) @ignore)
+(splat_parameter (identifier) @ignore)
+(hash_splat_parameter (identifier) @ignore)
+(keyword_parameter name: (identifier) @ignore)
+(optional_parameter name: (identifier) @ignore)
+
+; Attribute writers
+
+[
+  (assignment
+    left: (call
+      method: (identifier) @name))
+  (operator_assignment
+    left: (call
+      method: (identifier) @name))
+] @reference.attribute_writer
+
+; Calls
+
+(call method: (identifier) @name) @reference.call
+
+; Constant references
+
+((constant) @name @reference.constant)
+
+; Sections of method visibility, like `private`
+
+(
+  (identifier) @name @reference.visibility
+  (#match? @name "^(private|protected|public)$")
+)
+
+; Bare identifiers are receiver-less method calls unless they are bound as local variables.
+
+(
+  (identifier) @name @reference.call
+  (#is-not? local)
+  (#not-match? @name "^(lambda|load|require|require_relative|__FILE__|__LINE__)$")
+)
+
+((identifier) @name @reference.local)
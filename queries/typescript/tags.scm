@@ -0,0 +1,75 @@
+; Ambient declarations
+
+(ambient_declaration
+  [
+    (function_signature
+      name: (identifier) @name)
+    (class_declaration
+      name: (type_identifier) @name)
+    (lexical_declaration
+      (variable_declarator
+        name: (identifier) @name))
+    (variable_declaration
+      (variable_declarator
+        name: (identifier) @name))
+    (module
+      name: (string (string_fragment) @name))
+  ]) @definition.ambient
+
+(ambient_declaration
+  (module
+    body: (statement_block
+      [
+        (function_signature
+          name: (identifier) @name)
+        (export_statement
+          declaration: (function_signature
+            name: (identifier) @name))
+      ]))) @definition.ambient
+
+; Overload signatures
+
+(function_signature
+  name: (identifier) @name) @definition.overload
+
+(class_body
+  (method_signature
+    name: (property_identifier) @name) @definition.overload)
+
+(abstract_method_signature
+  name: (property_identifier) @name) @definition.abstract_method
+
+; Type declarations
+
+(type_alias_declaration
+  name: (type_identifier) @name) @definition.type
+
+(enum_declaration
+  name: (identifier) @name) @definition.enum
+
+(enum_body
+  [
+    (property_identifier) @name
+    (enum_assignment
+      (property_identifier) @name)
+  ] @definition.enum_member)
+
+; Properties
+
+(public_field_definition
+  name: [
+    (property_identifier)
+    (private_property_identifier)
+  ] @name) @definition.property
+
+; Members of interfaces and named object types, but not of inline ones like `(props: { id: number })`
+
+(interface_declaration
+  body: (object_type
+    (property_signature
+      name: (property_identifier) @name) @definition.property))
+
+(type_alias_declaration
+  value: (object_type
+    (property_signature
+      name: (property_identifier) @name) @definition.property))
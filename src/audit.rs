@@ -0,0 +1,258 @@
+use std::ops::Range;
+use indexmap::IndexMap;
+use tree_sitter::Node;
+use crate::codegen::json;
+use crate::line_number;
+use crate::models::{Model, Orm, Value};
+use crate::syntax::{children, parse, text, visit};
+
+// Attributes whose names look sensitive, like `password` or `remember_digest`, but which models serialize,
+// as they are not hidden by `$hidden`, `@Exclude()` or the like. Findings are reported as SARIF.
+
+const RULE_ID: &str = "sensitive-attribute-exposure";
+
+// Words of attribute names, like `digest` of `remember_digest` or `password` of `passwordHash`.
+const SENSITIVE_WORDS: [&str; 5] = ["password", "token", "digest", "secret", "ssn"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub model: String,
+    pub attribute: String,
+    pub file: String,
+    // Byte range of the attribute name where it is declared, or of the declaration naming it otherwise.
+    pub range: Range<usize>,
+}
+
+// Findings of models, whose sources are given by the names of their files.
+pub fn audit(models: &[Model], sources: &IndexMap<String, Vec<u8>>) -> Vec<Finding> {
+    let mut findings = vec![];
+    for model in models {
+        let source = sources.get(&model.file).map(Vec::as_slice).unwrap_or_default();
+        let mut attributes: Vec<(String, Range<usize>)> = model.fields.iter().map(|field| (field.name.clone(), field.range.clone())).collect();
+        if model.orm == Orm::ActiveRecord && !source.is_empty() {
+            for (name, range) in stored_attributes(model, source) {
+                if !attributes.iter().any(|(attribute, _)| *attribute == name) {
+                    attributes.push((name, range));
+                }
+            }
+        }
+        for (attribute, range) in attributes {
+            if !is_sensitive(&attribute) || model.hidden.contains(&attribute) {
+                continue;
+            }
+            let range = name_range(source, range, &attribute);
+            findings.push(Finding { model: model.name.clone(), attribute, file: model.file.clone(), range });
+        }
+    }
+    findings
+}
+
+// SARIF 2.1.0 log of findings, whose regions are located by the given sources.
+pub fn sarif(findings: &[Finding], sources: &IndexMap<String, Vec<u8>>) -> String {
+    let string = |string: &str| Value::String(String::from(string));
+    let object = |pairs: Vec<(&str, Value)>| Value::Object(pairs.into_iter().map(|(key, value)| (String::from(key), value)).collect());
+
+    let results = findings.iter()
+        .map(|finding| {
+            let source = sources.get(&finding.file).map(Vec::as_slice).unwrap_or_default();
+            let (start_line, start_column) = position(source, finding.range.start);
+            let (end_line, end_column) = position(source, finding.range.end);
+            let region = object(vec![
+                ("startLine", Value::Number(start_line as f64)),
+                ("startColumn", Value::Number(start_column as f64)),
+                ("endLine", Value::Number(end_line as f64)),
+                ("endColumn", Value::Number(end_column as f64)),
+            ]);
+            let message = format!("`{}.{}` looks sensitive, but it is not hidden from serialization", finding.model, finding.attribute);
+            object(vec![
+                ("ruleId", string(RULE_ID)),
+                ("level", string("warning")),
+                ("message", object(vec![("text", Value::String(message))])),
+                ("locations", Value::Array(vec![object(vec![
+                    ("physicalLocation", object(vec![
+                        ("artifactLocation", object(vec![("uri", string(&finding.file))])),
+                        ("region", region),
+                    ])),
+                ])])),
+            ])
+        })
+        .collect();
+    let rule = object(vec![
+        ("id", string(RULE_ID)),
+        ("shortDescription", object(vec![("text", string("Sensitive attribute exposed by serialization"))])),
+    ]);
+    let run = object(vec![
+        ("tool", object(vec![("driver", object(vec![
+            ("name", string(env!("CARGO_PKG_NAME"))),
+            ("rules", Value::Array(vec![rule])),
+        ]))])),
+        ("columnKind", string("unicodeCodePoints")),
+        ("results", Value::Array(results)),
+    ]);
+    let log = object(vec![
+        ("$schema", string("https://json.schemastore.org/sarif-2.1.0.json")),
+        ("version", string("2.1.0")),
+        ("runs", Value::Array(vec![run])),
+    ]);
+    json(&log, 0) + "\n"
+}
+
+fn is_sensitive(name: &str) -> bool {
+    words(name).iter().any(|word| SENSITIVE_WORDS.contains(&word.as_str()))
+}
+
+// Lowercase words of snake_case, kebab-case or camelCase name.
+fn words(name: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    for char in name.chars() {
+        if !char.is_alphanumeric() || (char.is_uppercase() && word.chars().last().is_some_and(char::is_lowercase)) {
+            words.push(std::mem::take(&mut word));
+        }
+        if char.is_alphanumeric() {
+            word.extend(char.to_lowercase());
+        }
+    }
+    words.push(word);
+    words.into_iter().filter(|word| !word.is_empty()).collect()
+}
+
+// Range of the name within the declaration, like `'password'` in `$fillable = ['name', 'password']`.
+fn name_range(source: &[u8], range: Range<usize>, name: &str) -> Range<usize> {
+    let declaration = match source.get(range.clone()) {
+        Some(declaration) => declaration,
+        None => return range,
+    };
+    let is_word = |byte: Option<&u8>| byte.is_some_and(|byte| byte.is_ascii_alphanumeric() || *byte == b'_');
+    (0..declaration.len())
+        .filter(|start| declaration[*start..].starts_with(name.as_bytes()))
+        .find(|start| !is_word(start.checked_sub(1).and_then(|before| declaration.get(before))) && !is_word(declaration.get(start + name.len())))
+        .map_or(range.clone(), |start| range.start + start..range.start + start + name.len())
+}
+
+// Attributes which ActiveRecord models store without declaring them, as Rails reads columns from databases:
+// `password_digest` of `has_secure_password`, and the attributes written by methods, like `update_attribute(:remember_digest, ...)`.
+// Virtual attributes of `attr_accessor` are not serialized.
+fn stored_attributes(model: &Model, source: &[u8]) -> Vec<(String, Range<usize>)> {
+    let tree = parse(tree_sitter_ruby::language(), source);
+    let mut class = None;
+    visit(tree.root_node(), &mut |node| {
+        if node.kind() == "class" && node.byte_range() == model.range {
+            class = Some(node);
+        }
+    });
+    let class = match class {
+        Some(class) => class,
+        None => return vec![],
+    };
+
+    let symbol = |node: Node| String::from(text(node, source).trim_start_matches(':').trim_end_matches(':'));
+    let mut virtual_attributes = vec![];
+    let mut attributes = vec![];
+    visit(class, &mut |node| {
+        match node.kind() {
+            "call" if node.child_by_field_name("receiver").is_none() => {
+                let method = node.child_by_field_name("method").map_or("", |method| text(method, source));
+                let arguments: Vec<Node> = node.child_by_field_name("arguments").map(children).unwrap_or_default()
+                    .into_iter()
+                    .filter(|argument| argument.is_named())
+                    .collect();
+                match method {
+                    "attr_accessor" | "attr_reader" | "attr_writer" => {
+                        virtual_attributes.extend(arguments.iter().filter(|argument| argument.kind() == "simple_symbol").map(|argument| symbol(*argument)));
+                    }
+                    "has_secure_password" => {
+                        let name = arguments.first().filter(|argument| argument.kind() == "simple_symbol").map_or_else(|| String::from("password"), |argument| symbol(*argument));
+                        attributes.push((format!("{}_digest", name), node.byte_range()));
+                    }
+                    "update_attribute" | "update_column" | "write_attribute" => {
+                        if let Some(name) = arguments.first().filter(|argument| argument.kind() == "simple_symbol") {
+                            attributes.push((symbol(*name), name.byte_range()));
+                        }
+                    }
+                    "update_columns" | "update" | "update!" | "assign_attributes" => {
+                        let keys = arguments.iter().filter(|argument| argument.kind() == "pair").filter_map(|pair| pair.child_by_field_name("key"));
+                        attributes.extend(keys.map(|key| (symbol(key), key.byte_range())));
+                    }
+                    _ => {}
+                }
+            }
+            // `has_secure_password` without arguments.
+            "identifier" if text(node, source) == "has_secure_password" && node.parent().is_some_and(|parent| parent.kind() != "call") => {
+                attributes.push((String::from("password_digest"), node.byte_range()));
+            }
+            // `self.activation_digest = User.digest(activation_token)`
+            "assignment" => {
+                let left = node.child_by_field_name("left").filter(|left| left.kind() == "call");
+                let receiver = left.and_then(|left| left.child_by_field_name("receiver")).map(|receiver| text(receiver, source));
+                if let (Some("self"), Some(name)) = (receiver, left.and_then(|left| left.child_by_field_name("method"))) {
+                    attributes.push((String::from(text(name, source)), name.byte_range()));
+                }
+            }
+            _ => {}
+        }
+    });
+    let mut stored: Vec<(String, Range<usize>)> = vec![];
+    for (name, range) in attributes {
+        if !virtual_attributes.contains(&name) && !stored.iter().any(|(attribute, _)| *attribute == name) {
+            stored.push((name, range));
+        }
+    }
+    stored.sort_by_key(|(_, range)| range.start);
+    stored
+}
+
+// One-based line and column of offset, counting columns by characters.
+fn position(source: &[u8], offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line_start = before.iter().rposition(|byte| *byte == b'\n').map_or(0, |newline| newline + 1);
+    (line_number(source, offset), String::from_utf8_lossy(&before[line_start..]).chars().count() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_models;
+    use crate::models::models;
+
+    #[test]
+    fn it_should_find_sensitive_attributes_of_activerecord_models() {
+        let (models, sources) = read_models(&[("user.rb", "app/models/user.rb")]);
+        let findings = audit(&models, &sources);
+
+        let attributes: Vec<_> = findings.iter().map(|finding| (finding.attribute.as_str(), position(&sources[0], finding.range.start))).collect();
+        assert_eq!(attributes, vec![
+            ("password_digest", (21, 3)),
+            ("remember_digest", (39, 23)),
+            ("reset_digest", (75, 23)),
+            ("activation_digest", (123, 10)),
+        ]);
+    }
+
+    #[test]
+    fn it_should_skip_hidden_attributes() {
+        let (models, sources) = read_models(&[("User.php", "app/Models/User.php")]);
+        assert_eq!(audit(&models, &sources), vec![]);
+    }
+
+    #[test]
+    fn it_should_match_words_of_names() {
+        let source = b"@Entity()\nclass Account {\n  @Column({ select: false })\n  password: string\n  @Column()\n  apiToken: string\n  @Column()\n  tokenizer: string\n}\n";
+        let models = crate::models::models("entity/Account.ts", source).unwrap();
+        let findings = audit(&models, &IndexMap::from([(String::from("entity/Account.ts"), source.to_vec())]));
+        assert_eq!(findings.iter().map(|finding| (finding.attribute.as_str(), finding.range.clone())).collect::<Vec<_>>(), vec![("apiToken", 88..96)]);
+    }
+
+    #[test]
+    fn it_should_report_sarif() {
+        let source = b"class Person(models.Model):\n    ssn = models.CharField(max_length=11)\n";
+        let models = models("people/models.py", source).unwrap();
+        let sources = IndexMap::from([(String::from("people/models.py"), source.to_vec())]);
+        let log = sarif(&audit(&models, &sources), &sources);
+
+        assert!(log.contains("\"version\": \"2.1.0\""));
+        assert!(log.contains("\"text\": \"`Person.ssn` looks sensitive, but it is not hidden from serialization\""));
+        assert!(log.contains("\"uri\": \"people/models.py\""));
+        assert!(log.contains("\"startLine\": 2,\n                  \"startColumn\": 5,\n                  \"endLine\": 2,\n                  \"endColumn\": 8"));
+    }
+}
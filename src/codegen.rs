@@ -0,0 +1,299 @@
+use indexmap::IndexMap;
+use crate::ddl::{implicit_key_type, is_optional};
+use crate::hierarchy::simple_name;
+use crate::models::{DataType, Model, Orm, RelationKind, Value};
+
+// TypeScript interfaces and JSON Schemas (draft 2020-12) of the objects which models are serialized to, for frontends consuming them.
+// Attributes hidden from serialization are left out, and relations are optional as they are serialized only when loaded.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    TypeScript,
+    JsonSchema,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Type<'a> {
+    Scalar { data_type: DataType, length: Option<u32>, default: Option<&'a Value> },
+    // Related model, which is missing unless it is among the supplied models.
+    Model(Option<&'a str>),
+    Nullable(Box<Type<'a>>),
+    Array(Box<Type<'a>>),
+}
+
+struct Property<'a> {
+    name: &'a str,
+    property_type: Type<'a>,
+    required: bool,
+}
+
+pub fn render(models: &[Model], format: Format) -> String {
+    match format {
+        Format::TypeScript => typescript(models),
+        Format::JsonSchema => json_schema(models),
+    }
+}
+
+fn typescript(models: &[Model]) -> String {
+    let interfaces: Vec<String> = models.iter()
+        .map(|model| {
+            let mut lines = vec![format!("export interface {} {{", simple_name(&model.name))];
+            for property in properties(model, models) {
+                let name = if is_identifier(property.name) { String::from(property.name) } else { json_string(property.name) };
+                let optional = if property.required { "" } else { "?" };
+                lines.push(format!("  {}{}: {};", name, optional, typescript_type(&property.property_type)));
+            }
+            lines.push(String::from("}"));
+            lines.join("\n")
+        })
+        .collect();
+    interfaces.join("\n\n") + "\n"
+}
+
+fn typescript_type(property_type: &Type) -> String {
+    match property_type {
+        Type::Scalar { data_type, .. } => String::from(match data_type {
+            DataType::String | DataType::Text | DataType::Uuid | DataType::Binary => "string",
+            DataType::Integer | DataType::BigInteger | DataType::Float | DataType::Decimal => "number",
+            DataType::Boolean => "boolean",
+            // Dates and times are serialized as ISO 8601 strings.
+            DataType::Date | DataType::DateTime | DataType::Time => "string",
+            DataType::Json | DataType::Unknown => "unknown",
+        }),
+        Type::Model(name) => String::from(name.unwrap_or("unknown")),
+        Type::Nullable(item) => format!("{} | null", typescript_type(item)),
+        Type::Array(item) => format!("{}[]", typescript_type(item)),
+    }
+}
+
+fn json_schema(models: &[Model]) -> String {
+    let definitions = models.iter()
+        .map(|model| {
+            let properties = properties(model, models);
+            let required = properties.iter().filter(|property| property.required).map(|property| Value::String(String::from(property.name))).collect();
+            let schema = IndexMap::from([
+                (String::from("type"), Value::String(String::from("object"))),
+                (String::from("properties"), Value::Object(properties.iter().map(|property| (String::from(property.name), schema(&property.property_type))).collect())),
+                (String::from("required"), Value::Array(required)),
+            ]);
+            (String::from(simple_name(&model.name)), Value::Object(schema))
+        })
+        .collect();
+    let document = IndexMap::from([
+        (String::from("$schema"), Value::String(String::from("https://json-schema.org/draft/2020-12/schema"))),
+        (String::from("$defs"), Value::Object(definitions)),
+    ]);
+    json(&Value::Object(document), 0) + "\n"
+}
+
+fn schema(property_type: &Type) -> Value {
+    let string = |string: &str| Value::String(String::from(string));
+    let mut schema = IndexMap::new();
+    match property_type {
+        Type::Scalar { data_type, length, default } => {
+            let (json_type, format) = match data_type {
+                DataType::String | DataType::Text | DataType::Binary => (Some("string"), None),
+                DataType::Integer | DataType::BigInteger => (Some("integer"), None),
+                DataType::Float | DataType::Decimal => (Some("number"), None),
+                DataType::Boolean => (Some("boolean"), None),
+                DataType::Date => (Some("string"), Some("date")),
+                DataType::DateTime => (Some("string"), Some("date-time")),
+                DataType::Time => (Some("string"), Some("time")),
+                DataType::Uuid => (Some("string"), Some("uuid")),
+                DataType::Json | DataType::Unknown => (None, None),
+            };
+            schema.extend(json_type.map(|json_type| (String::from("type"), string(json_type))));
+            schema.extend(format.map(|format| (String::from("format"), string(format))));
+            schema.extend(length.map(|length| (String::from("maxLength"), Value::Number(length as f64))));
+            // Defaults given by expressions, like `timezone.now`, are computed when saving.
+            if let Some(default @ (Value::String(_) | Value::Number(_) | Value::Boolean(_) | Value::Null)) = default {
+                schema.insert(String::from("default"), (*default).clone());
+            }
+        }
+        Type::Model(name) => {
+            schema.extend(name.map(|name| (String::from("$ref"), Value::String(format!("#/$defs/{}", name)))));
+        }
+        Type::Nullable(item) => {
+            let mut item = match self::schema(item) {
+                Value::Object(item) => item,
+                _ => IndexMap::new(),
+            };
+            match item.get("type").and_then(Value::as_str).map(String::from) {
+                Some(json_type) => {
+                    item.insert(String::from("type"), Value::Array(vec![string(&json_type), string("null")]));
+                    return Value::Object(item);
+                }
+                // Schemas accepting anything accept null.
+                None if item.is_empty() => return Value::Object(item),
+                None => {
+                    let null = Value::Object(IndexMap::from([(String::from("type"), string("null"))]));
+                    schema.insert(String::from("anyOf"), Value::Array(vec![Value::Object(item), null]));
+                }
+            }
+        }
+        Type::Array(item) => {
+            schema.insert(String::from("type"), string("array"));
+            schema.insert(String::from("items"), self::schema(item));
+        }
+    }
+    Value::Object(schema)
+}
+
+// Properties of the serialized model: its key, fields and the keys held by its relations, followed by the relations.
+fn properties<'a>(model: &'a Model, models: &'a [Model]) -> Vec<Property<'a>> {
+    let is_visible = |name: &str| !model.hidden.iter().any(|hidden| hidden == name);
+    let mut properties = vec![];
+
+    // ORMs but TypeORM name the primary key `id` unless models declare one.
+    if model.orm != Orm::TypeOrm && !model.fields.iter().any(|field| field.primary_key || field.name == "id") && is_visible("id") {
+        properties.push(Property { name: "id", property_type: Type::Scalar { data_type: implicit_key_type(model.orm), length: None, default: None }, required: true });
+    }
+    for field in model.fields.iter().filter(|field| is_visible(&field.name)) {
+        let scalar = Type::Scalar { data_type: field.data_type, length: field.length, default: field.default.as_ref() };
+        let property_type = if field.nullable && !field.primary_key { Type::Nullable(Box::new(scalar)) } else { scalar };
+        properties.push(Property { name: &field.name, property_type, required: true });
+    }
+    // Keys held by relations are attributes of records, but TypeORM entities have the declared properties only.
+    if model.orm != Orm::TypeOrm {
+        for relation in model.relations.iter().filter(|relation| relation.kind == RelationKind::BelongsTo) {
+            let key = match relation.foreign_key.as_deref() {
+                Some(key) if is_visible(key) && !properties.iter().any(|property| property.name == key) => key,
+                _ => continue,
+            };
+            let scalar = Type::Scalar { data_type: implicit_key_type(model.orm), length: None, default: None };
+            let property_type = if is_optional(model.orm, relation) { Type::Nullable(Box::new(scalar)) } else { scalar };
+            properties.push(Property { name: key, property_type, required: true });
+        }
+    }
+    for relation in model.relations.iter().filter(|relation| is_visible(&relation.name)) {
+        let target = relation.target.as_deref()
+            .map(simple_name)
+            .and_then(|target| models.iter().map(|model| simple_name(&model.name)).find(|name| *name == target));
+        let property_type = match relation.kind {
+            RelationKind::BelongsTo | RelationKind::HasOne => Type::Nullable(Box::new(Type::Model(target))),
+            RelationKind::HasMany | RelationKind::ManyToMany => Type::Array(Box::new(Type::Model(target))),
+        };
+        properties.push(Property { name: &relation.name, property_type, required: false });
+    }
+    properties
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|char| char.is_alphabetic() || char == '_' || char == '$') && chars.all(|char| char.is_alphanumeric() || char == '_' || char == '$')
+}
+
+// JSON indented by two spaces, with arrays of scalars kept on a line.
+pub(crate) fn json(value: &Value, indent: usize) -> String {
+    let padding = " ".repeat(indent + 2);
+    match value {
+        Value::Object(object) if object.is_empty() => String::from("{}"),
+        Value::Object(object) => {
+            let pairs: Vec<String> = object.iter().map(|(key, value)| format!("{}{}: {}", padding, json_string(key), json(value, indent + 2))).collect();
+            format!("{{\n{}\n{}}}", pairs.join(",\n"), " ".repeat(indent))
+        }
+        Value::Array(array) if array.iter().all(|item| !matches!(item, Value::Object(_) | Value::Array(_))) => {
+            let items: Vec<String> = array.iter().map(|item| json(item, indent)).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Array(array) => {
+            let items: Vec<String> = array.iter().map(|item| format!("{}{}", padding, json(item, indent + 2))).collect();
+            format!("[\n{}\n{}]", items.join(",\n"), " ".repeat(indent))
+        }
+        Value::String(string) => json_string(string),
+        Value::Number(number) => number.to_string(),
+        Value::Boolean(boolean) => boolean.to_string(),
+        Value::Null => String::from("null"),
+        Value::Regex { .. } | Value::Expression(_) => json_string(&value.to_string()),
+    }
+}
+
+fn json_string(string: &str) -> String {
+    let mut quoted = String::from("\"");
+    for char in string.chars() {
+        match char {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            char if char.is_control() => quoted.push_str(&format!("\\u{:04x}", char as u32)),
+            char => quoted.push(char),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_fixture;
+    use crate::models::models;
+
+    #[test]
+    fn it_should_render_typescript_interfaces() {
+        let models = models("polls/models.py", &read_fixture("models.py")).unwrap();
+        assert_eq!(render(&models, Format::TypeScript), "\
+export interface Question {
+  id: number;
+  question_text: string;
+  pub_date: string;
+}
+
+export interface Choice {
+  id: number;
+  choice_text: string;
+  votes: number;
+  question_id: number;
+  question?: Question | null;
+}
+");
+    }
+
+    #[test]
+    fn it_should_leave_hidden_attributes_out() {
+        let models = models("app/Models/User.php", &read_fixture("User.php")).unwrap();
+        assert_eq!(render(&models, Format::TypeScript), "\
+export interface User {
+  id: number;
+  email_verified_at: string | null;
+  posts?: unknown[];
+}
+");
+    }
+
+    #[test]
+    fn it_should_render_json_schemas() {
+        let source = b"@Entity()\nclass Account {\n  @PrimaryGeneratedColumn()\n  id: number\n  @Column({ length: 100, default: \"\" })\n  email: string\n  @Column({ select: false })\n  password: string\n  @Column({ type: \"timestamp\", nullable: true })\n  verifiedAt: Date\n  @OneToMany(() => Session, (session) => session.account)\n  sessions: Session[]\n}\n";
+        let models = models("entity/Account.ts", source).unwrap();
+        assert_eq!(render(&models, Format::JsonSchema), r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "$defs": {
+    "Account": {
+      "type": "object",
+      "properties": {
+        "id": {
+          "type": "integer"
+        },
+        "email": {
+          "type": "string",
+          "maxLength": 100,
+          "default": ""
+        },
+        "verifiedAt": {
+          "type": ["string", "null"],
+          "format": "date-time"
+        },
+        "sessions": {
+          "type": "array",
+          "items": {}
+        }
+      },
+      "required": ["id", "email", "verifiedAt"]
+    }
+  }
+}
+"##);
+    }
+}
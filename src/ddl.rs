@@ -0,0 +1,508 @@
+use indexmap::IndexMap;
+use crate::hierarchy::simple_name;
+use crate::inflector::{camelize, pluralize, singularize, underscore};
+use crate::models::tables::table_name;
+use crate::models::{DataType, Model, Orm, Relation, RelationKind, Value};
+
+// `CREATE TABLE` statements of the schema which models describe, to compare it with the one of databases.
+// Foreign keys refer to the tables of the supplied models only, as the others may be named otherwise.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    PostgreSql,
+    MySql,
+    Sqlite,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub name: String,
+    pub columns: Vec<Column>,
+    pub primary_key: Vec<String>,
+    pub foreign_keys: Vec<ForeignKey>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub name: String,
+    pub data_type: DataType,
+    pub length: Option<u32>,
+    pub nullable: bool,
+    pub default: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForeignKey {
+    pub column: String,
+    pub table: String,
+    pub references: String,
+}
+
+impl Table {
+    pub(crate) fn new(name: &str) -> Self {
+        Table { name: String::from(name), columns: vec![], primary_key: vec![], foreign_keys: vec![] }
+    }
+
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+
+    // Adds the columns and foreign keys which the table doesn't have yet, like the ones of join tables declared by both sides.
+    fn merge(&mut self, other: Table) {
+        for column in other.columns {
+            if self.column(&column.name).is_none() {
+                self.columns.push(column);
+            }
+        }
+        for foreign_key in other.foreign_keys {
+            if !self.foreign_keys.contains(&foreign_key) {
+                self.foreign_keys.push(foreign_key);
+            }
+        }
+        if self.primary_key.is_empty() {
+            self.primary_key = other.primary_key;
+        }
+    }
+
+    // Single integer primary keys are generated by databases.
+    fn is_auto_increment(&self, column: &Column) -> bool {
+        self.primary_key == [column.name.as_str()] && matches!(column.data_type, DataType::Integer | DataType::BigInteger)
+    }
+}
+
+impl Column {
+    pub(crate) fn new(name: &str, data_type: DataType, nullable: bool) -> Self {
+        Column { name: String::from(name), data_type, length: None, nullable, default: None }
+    }
+}
+
+pub fn render(models: &[Model], dialect: Dialect) -> String {
+    let statements: Vec<String> = tables(models).iter().map(|table| create_table(table, dialect)).collect();
+    statements.join("\n\n") + "\n"
+}
+
+// Tables of models followed by the join tables of their relations, ordered so that referred tables come first.
+pub fn tables(models: &[Model]) -> Vec<Table> {
+    let mut tables: IndexMap<String, Table> = IndexMap::new();
+    for model in models {
+        if let Some(table) = model_table(model, models) {
+            tables.insert(table.name.clone(), table);
+        }
+    }
+    for model in models {
+        for relation in &model.relations {
+            let join_table = match join_table(model, relation, models) {
+                Some(join_table) if !models.iter().any(|other| table_of(other).as_deref() == Some(join_table.name.as_str())) => join_table,
+                _ => continue,
+            };
+            match tables.get_mut(&join_table.name) {
+                Some(table) => table.merge(join_table),
+                None => {
+                    tables.insert(join_table.name.clone(), join_table);
+                }
+            }
+        }
+    }
+    sort(tables.into_values().collect())
+}
+
+fn table_of(model: &Model) -> Option<String> {
+    table_name(model).map(|table| table.name)
+}
+
+// Model whose name is the target of relation, and its table.
+fn target<'a>(relation: &Relation, models: &'a [Model]) -> Option<(&'a Model, String)> {
+    let target = simple_name(relation.target.as_deref()?);
+    models.iter()
+        .filter(|model| simple_name(&model.name) == target)
+        .find_map(|model| Some((model, table_of(model)?)))
+}
+
+fn model_table(model: &Model, models: &[Model]) -> Option<Table> {
+    let mut table = Table::new(&table_of(model)?);
+    // Fields and the keys held by relations, in the order of their declarations.
+    let mut columns: Vec<(usize, Column)> = model.fields.iter()
+        .map(|field| (field.range.start, Column {
+            name: field.name.clone(),
+            data_type: field.data_type,
+            length: field.length,
+            nullable: field.nullable && !field.primary_key,
+            default: field.default.clone(),
+        }))
+        .collect();
+    table.primary_key = model.fields.iter().filter(|field| field.primary_key).map(|field| field.name.clone()).collect();
+
+    for relation in model.relations.iter().filter(|relation| relation.kind == RelationKind::BelongsTo) {
+        let foreign_key = match &relation.foreign_key {
+            Some(foreign_key) => foreign_key,
+            None => continue,
+        };
+        let related = target(relation, models);
+        let references = related.as_ref().and_then(|(related, _)| primary_key(related));
+        if !columns.iter().any(|(_, column)| column.name == *foreign_key) {
+            let data_type = references.as_ref().map_or(implicit_key_type(model.orm), |(_, data_type)| *data_type);
+            columns.push((relation.range.start, Column::new(foreign_key, data_type, is_optional(model.orm, relation))));
+            // Polymorphic relations hold the type of the related model besides its key, like `imageable_type`.
+            if relation.target.is_none() {
+                if let Some(prefix) = foreign_key.strip_suffix("_id") {
+                    let mut column = Column::new(&format!("{}_type", prefix), DataType::String, is_optional(model.orm, relation));
+                    column.length = Some(255);
+                    columns.push((relation.range.start, column));
+                }
+            }
+        }
+        if let (Some((_, table_name)), Some((references, _))) = (related, references) {
+            table.foreign_keys.push(ForeignKey { column: foreign_key.clone(), table: table_name, references });
+        }
+    }
+    columns.sort_by_key(|(start, _)| *start);
+    table.columns = columns.into_iter().map(|(_, column)| column).collect();
+
+    // ORMs but TypeORM name the primary key `id` unless models declare one.
+    if table.primary_key.is_empty() && model.orm != Orm::TypeOrm {
+        if table.column("id").is_none() {
+            table.columns.insert(0, Column::new("id", implicit_key_type(model.orm), false));
+        }
+        table.primary_key = vec![String::from("id")];
+    }
+    Some(table)
+}
+
+// Name and type of the single primary key of model.
+fn primary_key(model: &Model) -> Option<(String, DataType)> {
+    let keys: Vec<_> = model.fields.iter().filter(|field| field.primary_key).collect();
+    match keys.as_slice() {
+        [] if model.orm != Orm::TypeOrm => Some((String::from("id"), implicit_key_type(model.orm))),
+        [key] => Some((key.name.clone(), key.data_type)),
+        _ => None,
+    }
+}
+
+pub(crate) fn implicit_key_type(orm: Orm) -> DataType {
+    match orm {
+        Orm::ActiveRecord | Orm::Django | Orm::Eloquent => DataType::BigInteger,
+        Orm::TypeOrm | Orm::Objection => DataType::Integer,
+    }
+}
+
+// Whether the key held by relation may be null, which Rails and Django don't allow by default.
+pub(crate) fn is_optional(orm: Orm, relation: &Relation) -> bool {
+    let option = |key: &str| relation.options.get(key);
+    match orm {
+        Orm::ActiveRecord => option("optional").is_some_and(Value::is_true),
+        Orm::Django => option("null").is_some_and(Value::is_true),
+        Orm::TypeOrm => option("nullable") != Some(&Value::Boolean(false)),
+        Orm::Eloquent | Orm::Objection => true,
+    }
+}
+
+// Join table of many-to-many relation, or the table of the model which a relation goes through, like `has_many :followers, through: :relationships`.
+pub(crate) fn join_table(model: &Model, relation: &Relation, models: &[Model]) -> Option<Table> {
+    let table = table_of(model)?;
+    let related = target(relation, models);
+    let target_name = simple_name(relation.target.as_deref()?);
+    let option = |key: &str| relation.options.get(key);
+
+    let (name, columns) = match (relation.kind, model.orm) {
+        (RelationKind::ManyToMany, Orm::ActiveRecord) => {
+            let target_table = related.as_ref().map_or_else(|| pluralize(&underscore(target_name)), |(_, table)| table.clone());
+            let mut tables = [table.clone(), target_table];
+            tables.sort();
+            let name = relation.through.clone().unwrap_or_else(|| tables.join("_"));
+            let target_key = option("association_foreign_key").and_then(Value::as_str).map(String::from);
+            (name, (relation.foreign_key.clone()?, target_key.unwrap_or_else(|| format!("{}_id", underscore(target_name)))))
+        }
+        (RelationKind::ManyToMany, Orm::Eloquent) => {
+            let mut names = [underscore(simple_name(&model.name)), underscore(target_name)];
+            names.sort();
+            let name = relation.through.clone().unwrap_or_else(|| names.join("_"));
+            (name, (relation.foreign_key.clone()?, format!("{}_id", underscore(target_name))))
+        }
+        // Intermediate models given by `through` have their own tables.
+        (RelationKind::ManyToMany, Orm::Django) if option("through").is_none() => {
+            let name = relation.through.clone().unwrap_or_else(|| format!("{}_{}", table, relation.name));
+            let (from, to) = (simple_name(&model.name).to_lowercase(), target_name.to_lowercase());
+            match from == to {
+                true => (name, (format!("from_{}_id", from), format!("to_{}_id", to))),
+                false => (name, (format!("{}_id", from), format!("{}_id", to))),
+            }
+        }
+        // The owning side declares the join table by `@JoinTable`, whose columns are named after the tables and their keys.
+        (RelationKind::ManyToMany, Orm::TypeOrm) => {
+            let join = option("joinTable")?;
+            let target_table = related.as_ref().map_or_else(|| underscore(target_name), |(_, table)| table.clone());
+            let name = relation.through.clone().unwrap_or_else(|| underscore(&format!("{}_{}_{}", table, relation.name, target_table)));
+            let key = |table: &str, model: Option<&Model>| {
+                let key = model.and_then(primary_key).map_or_else(|| String::from("id"), |(key, _)| key);
+                lower_camelize(&format!("{}_{}", table, key))
+            };
+            let column = |option: &str| join.get(option).and_then(|column| column.get("name")).and_then(Value::as_str).map(String::from);
+            let from = column("joinColumn").unwrap_or_else(|| key(&table, Some(model)));
+            let to = column("inverseJoinColumn").unwrap_or_else(|| key(&target_table, related.as_ref().map(|(target, _)| *target)));
+            (name, (from, to))
+        }
+        // Objection names the columns of join tables by `join.through`, like `{ from: 'persons_movies.personId', to: 'persons_movies.movieId' }`.
+        (RelationKind::ManyToMany, Orm::Objection) => {
+            let to = option("join").and_then(|join| join.get("through")).and_then(|through| through.get("to")).and_then(Value::as_str)?;
+            (relation.through.clone()?, (relation.foreign_key.clone()?, String::from(to.rsplit('.').next().unwrap_or(to))))
+        }
+        (RelationKind::HasOne | RelationKind::HasMany, Orm::ActiveRecord) => return through_table(model, relation, models),
+        _ => return None,
+    };
+
+    let mut join_table = Table::new(&name);
+    let (from, to) = columns;
+    for (column, references) in [(from, Some(model)), (to, related.as_ref().map(|(target, _)| *target))] {
+        let key = references.and_then(|references| Some((references, primary_key(references)?)));
+        let data_type = key.as_ref().map_or(implicit_key_type(model.orm), |(_, (_, data_type))| *data_type);
+        if let Some((references, (key, _))) = key {
+            join_table.foreign_keys.push(ForeignKey { column: column.clone(), table: table_of(references)?, references: key });
+        }
+        join_table.columns.push(Column::new(&column, data_type, false));
+        join_table.primary_key.push(column);
+    }
+    Some(join_table)
+}
+
+// Table of the model joining the models of `has_many :following, through: :active_relationships, source: :followed`,
+// which holds the keys of both of the models when the source is a `belongs_to` of the intermediate model.
+// Sources like `has_many :comments` of `Post` for `has_many :comments, through: :posts` hold their keys in other tables.
+fn through_table(model: &Model, relation: &Relation, models: &[Model]) -> Option<Table> {
+    let through = model.relations.iter().find(|other| Some(&other.name) == relation.through.as_ref())?;
+    let intermediate_name = simple_name(through.target.as_deref()?);
+    let intermediate = models.iter().find(|other| simple_name(&other.name) == intermediate_name)?;
+    let sources = match relation.options.get("source").and_then(Value::as_str) {
+        Some(source) => vec![String::from(source)],
+        None => vec![relation.name.clone(), singularize(&relation.name)],
+    };
+    let source = intermediate.relations.iter().find(|other| sources.contains(&other.name))?;
+    if source.kind != RelationKind::BelongsTo {
+        return None;
+    }
+    let mut table = Table::new(&table_of(intermediate)?);
+    table.columns.push(Column::new("id", implicit_key_type(model.orm), false));
+    table.primary_key.push(String::from("id"));
+
+    let keys = [
+        (through.foreign_key.clone()?, Some((model, table_of(model)?))),
+        (source.foreign_key.clone()?, target(source, models)),
+    ];
+    for (column, references) in keys {
+        table.columns.push(Column::new(&column, implicit_key_type(model.orm), false));
+        if let Some((references, table_name)) = references {
+            if let Some((key, _)) = primary_key(references) {
+                table.foreign_keys.push(ForeignKey { column, table: table_name, references: key });
+            }
+        }
+    }
+    Some(table)
+}
+
+// Tables ordered so that the tables referred by foreign keys are created before, unless they refer to each other.
+fn sort(mut tables: Vec<Table>) -> Vec<Table> {
+    let mut sorted: Vec<Table> = vec![];
+    while !tables.is_empty() {
+        let is_ready = |table: &Table| {
+            table.foreign_keys.iter().all(|foreign_key| {
+                foreign_key.table == table.name || !tables.iter().any(|other| other.name == foreign_key.table && other.name != table.name)
+            })
+        };
+        let index = tables.iter().position(is_ready).unwrap_or(0);
+        sorted.push(tables.remove(index));
+    }
+    sorted
+}
+
+fn create_table(table: &Table, dialect: Dialect) -> String {
+    let mut definitions: Vec<String> = table.columns.iter().map(|column| column_definition(table, column, dialect)).collect();
+    if table.primary_key.len() > 1 {
+        let columns: Vec<String> = table.primary_key.iter().map(|column| quote(column, dialect)).collect();
+        definitions.push(format!("PRIMARY KEY ({})", columns.join(", ")));
+    }
+    for foreign_key in &table.foreign_keys {
+        definitions.push(format!(
+            "FOREIGN KEY ({}) REFERENCES {} ({})",
+            quote(&foreign_key.column, dialect), quote(&foreign_key.table, dialect), quote(&foreign_key.references, dialect),
+        ));
+    }
+    let definitions: Vec<String> = definitions.into_iter().map(|definition| format!("    {}", definition)).collect();
+    format!("CREATE TABLE {} (\n{}\n);", quote(&table.name, dialect), definitions.join(",\n"))
+}
+
+fn column_definition(table: &Table, column: &Column, dialect: Dialect) -> String {
+    let name = quote(&column.name, dialect);
+    if table.is_auto_increment(column) {
+        return match (dialect, column.data_type) {
+            (Dialect::PostgreSql, DataType::BigInteger) => format!("{} BIGSERIAL PRIMARY KEY", name),
+            (Dialect::PostgreSql, _) => format!("{} SERIAL PRIMARY KEY", name),
+            (Dialect::MySql, _) => format!("{} {} NOT NULL AUTO_INCREMENT PRIMARY KEY", name, sql_type(column, dialect)),
+            // SQLite generates keys for the `INTEGER PRIMARY KEY` columns only.
+            (Dialect::Sqlite, _) => format!("{} INTEGER PRIMARY KEY AUTOINCREMENT", name),
+        };
+    }
+    let mut definition = format!("{} {}", name, sql_type(column, dialect));
+    if !column.nullable {
+        definition.push_str(" NOT NULL");
+    }
+    // MySQL doesn't allow defaults of text, binary and JSON columns.
+    let has_default = !(dialect == Dialect::MySql && matches!(column.data_type, DataType::Text | DataType::Binary | DataType::Json | DataType::Unknown));
+    if let Some(default) = column.default.as_ref().filter(|_| has_default).and_then(literal) {
+        definition.push_str(&format!(" DEFAULT {}", default));
+    }
+    if table.primary_key == [column.name.as_str()] {
+        definition.push_str(" PRIMARY KEY");
+    }
+    definition
+}
+
+fn sql_type(column: &Column, dialect: Dialect) -> String {
+    let sql_type = match (column.data_type, dialect) {
+        (DataType::String, _) => return format!("VARCHAR({})", column.length.unwrap_or(255)),
+        (DataType::Integer, Dialect::MySql) => "INT",
+        (DataType::Integer, _) => "INTEGER",
+        (DataType::BigInteger, _) => "BIGINT",
+        (DataType::Float, Dialect::PostgreSql) => "DOUBLE PRECISION",
+        (DataType::Float, Dialect::MySql) => "DOUBLE",
+        (DataType::Float, Dialect::Sqlite) => "REAL",
+        (DataType::Decimal, Dialect::Sqlite) => "NUMERIC",
+        (DataType::Decimal, _) => "DECIMAL",
+        (DataType::Boolean, _) => "BOOLEAN",
+        (DataType::Date, _) => "DATE",
+        (DataType::DateTime, Dialect::PostgreSql) => "TIMESTAMP",
+        (DataType::DateTime, _) => "DATETIME",
+        (DataType::Time, _) => "TIME",
+        (DataType::Json, Dialect::PostgreSql) => "JSONB",
+        (DataType::Json, Dialect::MySql) => "JSON",
+        (DataType::Binary, Dialect::PostgreSql) => "BYTEA",
+        (DataType::Binary, _) => "BLOB",
+        (DataType::Uuid, Dialect::PostgreSql) => "UUID",
+        (DataType::Uuid, Dialect::MySql) => "CHAR(36)",
+        (DataType::Json | DataType::Uuid | DataType::Text | DataType::Unknown, _) => "TEXT",
+    };
+    String::from(sql_type)
+}
+
+// SQL literal of default value, which is missing for expressions like `timezone.now`.
+fn literal(value: &Value) -> Option<String> {
+    match value {
+        Value::String(string) => Some(format!("'{}'", string.replace('\'', "''"))),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Boolean(boolean) => Some(String::from(if *boolean { "TRUE" } else { "FALSE" })),
+        Value::Null => Some(String::from("NULL")),
+        _ => None,
+    }
+}
+
+fn quote(identifier: &str, dialect: Dialect) -> String {
+    match dialect {
+        Dialect::MySql => format!("`{}`", identifier.replace('`', "``")),
+        _ => format!("\"{}\"", identifier.replace('"', "\"\"")),
+    }
+}
+
+// Camel case starting with lower case, like `sample10PostId` for `sample10_post_id`.
+fn lower_camelize(word: &str) -> String {
+    let camelized = camelize(word);
+    let mut chars = camelized.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => camelized,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_models;
+    use crate::models::models;
+
+    #[test]
+    fn it_should_render_postgresql() {
+        let ddl = render(&read_models(&[("models.py", "polls/models.py")]).0, Dialect::PostgreSql);
+        assert_eq!(ddl, "CREATE TABLE \"polls_question\" (
+    \"id\" BIGSERIAL PRIMARY KEY,
+    \"question_text\" VARCHAR(200) NOT NULL,
+    \"pub_date\" TIMESTAMP NOT NULL
+);
+
+CREATE TABLE \"polls_choice\" (
+    \"id\" BIGSERIAL PRIMARY KEY,
+    \"question_id\" BIGINT NOT NULL,
+    \"choice_text\" VARCHAR(200) NOT NULL,
+    \"votes\" INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (\"question_id\") REFERENCES \"polls_question\" (\"id\")
+);
+");
+    }
+
+    #[test]
+    fn it_should_render_mysql_join_tables() {
+        let mut models = read_models(&[("Post.ts", "entity/Post.ts")]).0;
+        models.extend(crate::models::models("entity/Category.ts", b"@Entity()\nexport class Category {\n  @PrimaryGeneratedColumn()\n  id: number\n  @Column({ length: 50, default: 'misc' })\n  name: string\n}\n").unwrap());
+        let ddl = render(&models, Dialect::MySql);
+        assert!(ddl.contains("CREATE TABLE `sample10_post` (
+    `id` INT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    `title` VARCHAR(255) NOT NULL,
+    `text` VARCHAR(255) NOT NULL,
+    `detailsId` INT,
+    `coverId` INT
+);"));
+        assert!(ddl.contains("`name` VARCHAR(50) NOT NULL DEFAULT 'misc'"));
+        assert!(ddl.ends_with("CREATE TABLE `sample10_post_categories_category` (
+    `sample10PostId` INT NOT NULL,
+    `categoryId` INT NOT NULL,
+    PRIMARY KEY (`sample10PostId`, `categoryId`),
+    FOREIGN KEY (`sample10PostId`) REFERENCES `sample10_post` (`id`),
+    FOREIGN KEY (`categoryId`) REFERENCES `category` (`id`)
+);
+"));
+    }
+
+    #[test]
+    fn it_should_join_tables_through_belongs_to_sources() {
+        let source = b"class Relationship < ApplicationRecord\n  belongs_to :follower, class_name: \"User\"\n  belongs_to :followed, class_name: \"User\"\nend\nclass User < ApplicationRecord\n  has_many :active_relationships, class_name: \"Relationship\", foreign_key: \"follower_id\"\n  has_many :following, through: :active_relationships, source: :followed\n  has_many :posts\n  has_many :comments, through: :posts\nend\nclass Post < ApplicationRecord\n  has_many :comments\nend\n";
+        let models = models("app/models/user.rb", source).unwrap();
+        let user = &models[1];
+
+        let following = join_table(user, &user.relations[1], &models).unwrap();
+        assert_eq!(following.name, "relationships");
+        assert_eq!(following.columns.iter().map(|column| column.name.as_str()).collect::<Vec<_>>(), vec!["id", "follower_id", "followed_id"]);
+        assert_eq!(following.foreign_keys.iter().map(|foreign_key| foreign_key.table.as_str()).collect::<Vec<_>>(), vec!["users", "users"]);
+        // `comments` of `Post` hold their keys in `comments`, not in `posts`.
+        assert_eq!(join_table(user, &user.relations[3], &models), None);
+
+        let source = b"class User < ApplicationRecord\n  has_many :posts\n  has_many :comments, through: :posts\nend\n";
+        let tables = tables(&crate::models::models("app/models/user.rb", source).unwrap());
+        assert_eq!(tables.iter().map(|table| table.name.as_str()).collect::<Vec<_>>(), vec!["users"]);
+    }
+
+    #[test]
+    fn it_should_create_tables_in_sqlite() {
+        let mut models = read_models(&[("models.py", "polls/models.py"), ("user.rb", "app/models/user.rb"), ("Post.ts", "entity/Post.ts"), ("Animal.js", "models/Animal.js")]).0;
+        let source = b"class Micropost < ApplicationRecord\n  belongs_to :user\n  belongs_to :attachable, polymorphic: true, optional: true\n  has_and_belongs_to_many :tags\nend\nclass Tag < ApplicationRecord\n  has_and_belongs_to_many :microposts\nend\n";
+        models.extend(crate::models::models("app/models/micropost.rb", source).unwrap());
+        let ddl = render(&models, Dialect::Sqlite);
+
+        let connection = rusqlite::Connection::open_in_memory().unwrap();
+        connection.execute_batch(&ddl).unwrap();
+
+        let query = |sql: &str| -> Vec<String> {
+            let mut statement = connection.prepare(sql).unwrap();
+            let rows = statement.query_map([], |row| row.get::<_, String>(0)).unwrap();
+            rows.map(|row| row.unwrap()).collect()
+        };
+        assert_eq!(query("SELECT name FROM sqlite_master WHERE type = 'table' AND name != 'sqlite_sequence' ORDER BY name"), vec![
+            "animals", "microposts", "microposts_tags", "polls_choice", "polls_question", "sample10_post",
+            "sample10_post_categories_category", "tags", "users",
+        ]);
+        assert_eq!(query("SELECT name FROM pragma_table_info('microposts') WHERE \"notnull\" = 0 AND pk = 0"), vec!["attachable_id", "attachable_type"]);
+        assert_eq!(query("SELECT \"table\" FROM pragma_foreign_key_list('microposts_tags') ORDER BY \"from\""), vec!["microposts", "tags"]);
+        assert_eq!(query("SELECT name FROM pragma_table_info('polls_choice') WHERE pk = 1"), vec!["id"]);
+
+        // Rows of the tables referring to each other can be inserted in order.
+        connection.execute_batch("PRAGMA foreign_keys = ON;
+            INSERT INTO polls_question (question_text, pub_date) VALUES ('What''s new?', '2023-01-01');
+            INSERT INTO polls_choice (question_id, choice_text) VALUES (1, 'Not much');").unwrap();
+        assert!(connection.execute("INSERT INTO polls_choice (question_id, choice_text) VALUES (2, 'Nothing')", []).is_err());
+    }
+}
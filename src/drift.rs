@@ -0,0 +1,201 @@
+use std::fmt;
+use std::ops::Range;
+use indexmap::IndexMap;
+use crate::ddl::{self, ForeignKey, Table};
+use crate::line_number;
+use crate::models::tables::table_name;
+use crate::models::{DataType, Model, Orm, RelationKind};
+
+// Drift between the schema which models describe and the actual one of a database, see `schema` for reading it.
+// Extra columns are reported for the ORMs declaring every column of models only, as the others read columns from databases.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Drift {
+    MissingTable { table: String },
+    MissingColumn { table: String, column: String },
+    ExtraColumn { table: String, column: String },
+    TypeMismatch { table: String, column: String, expected: DataType, actual: DataType },
+    MissingForeignKey { table: String, column: String, references: String },
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Drift::MissingTable { table } => write!(f, "missing table `{}`", table),
+            Drift::MissingColumn { table, column } => write!(f, "missing column `{}.{}`", table, column),
+            Drift::ExtraColumn { table, column } => write!(f, "extra column `{}.{}`", table, column),
+            Drift::TypeMismatch { table, column, expected, actual } => write!(f, "column `{}.{}` is {}, expected {}", table, column, actual, expected),
+            Drift::MissingForeignKey { table, column, references } => write!(f, "missing foreign key `{}.{}` -> `{}`", table, column, references),
+        }
+    }
+}
+
+// Drift located at the declaration causing it, like the field of a mismatching column or the relation of a missing foreign key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub drift: Drift,
+    pub file: String,
+    pub range: Range<usize>,
+}
+
+pub fn check(models: &[Model], schema: &[Table]) -> Vec<Finding> {
+    let mut findings = vec![];
+    for expected in ddl::tables(models) {
+        let mut push = |drift: Drift, column: Option<&str>| {
+            if let Some((file, range)) = locate(models, &expected.name, column) {
+                findings.push(Finding { drift, file, range });
+            }
+        };
+        let table = expected.name.clone();
+        let actual = match schema.iter().find(|actual| actual.name == expected.name) {
+            Some(actual) => actual,
+            None => {
+                push(Drift::MissingTable { table }, None);
+                continue;
+            }
+        };
+        let model = models.iter().find(|model| table_name(model).is_some_and(|name| name.name == expected.name));
+
+        for column in &expected.columns {
+            let name = column.name.clone();
+            match actual.column(&column.name) {
+                // Attributes of ActiveRecord models may be virtual.
+                None if model.is_some_and(|model| model.orm == Orm::ActiveRecord && model.fields.iter().any(|field| field.name == column.name)) => {}
+                None => push(Drift::MissingColumn { table: table.clone(), column: name }, Some(&column.name)),
+                Some(found) if !is_compatible(column.data_type, found.data_type) => {
+                    push(Drift::TypeMismatch { table: table.clone(), column: name, expected: column.data_type, actual: found.data_type }, Some(&column.name));
+                }
+                Some(_) => {}
+            }
+        }
+        if model.is_some_and(declares_columns) {
+            for column in actual.columns.iter().filter(|column| expected.column(&column.name).is_none()) {
+                push(Drift::ExtraColumn { table: table.clone(), column: column.name.clone() }, None);
+            }
+        }
+        for ForeignKey { column, table: referred, references } in &expected.foreign_keys {
+            let is_missing = !actual.foreign_keys.iter().any(|foreign_key| foreign_key.column == *column && foreign_key.table == *referred);
+            // Missing columns are reported already.
+            if is_missing && actual.column(column).is_some() {
+                let drift = Drift::MissingForeignKey { table: table.clone(), column: column.clone(), references: format!("{}.{}", referred, references) };
+                push(drift, Some(column));
+            }
+        }
+    }
+    findings
+}
+
+// Lines like `app/models/user.rb:3: missing foreign key `microposts.user_id` -> `users.id``, for the files whose sources are given.
+pub fn report(findings: &[Finding], sources: &IndexMap<String, Vec<u8>>) -> String {
+    findings.iter()
+        .map(|finding| match sources.get(&finding.file) {
+            Some(source) => format!("{}:{}: {}\n", finding.file, line_number(source, finding.range.start), finding.drift),
+            None => format!("{}: {}\n", finding.file, finding.drift),
+        })
+        .collect()
+}
+
+// Whether models declare all of their columns, unlike ActiveRecord and Eloquent ones.
+// Objection models declare them by optional `jsonSchema`.
+fn declares_columns(model: &Model) -> bool {
+    match model.orm {
+        Orm::Django | Orm::TypeOrm => true,
+        Orm::Objection => !model.fields.is_empty(),
+        Orm::ActiveRecord | Orm::Eloquent => false,
+    }
+}
+
+// Types which databases store alike, like keys of `integer` referring to `bigint` ones in SQLite, or JSON stored as text.
+fn is_compatible(expected: DataType, actual: DataType) -> bool {
+    expected == actual || matches!(
+        (expected, actual),
+        (DataType::Unknown, _) | (_, DataType::Unknown)
+            | (DataType::Integer, DataType::BigInteger) | (DataType::BigInteger, DataType::Integer)
+            | (DataType::String, DataType::Text) | (DataType::Text, DataType::String)
+            | (DataType::Json | DataType::Uuid, DataType::String | DataType::Text)
+            | (DataType::Boolean, DataType::Integer)
+    )
+}
+
+// File and range of the declaration of table or column: the field or relation declaring column, the model of table,
+// or the relation of join table.
+fn locate(models: &[Model], table: &str, column: Option<&str>) -> Option<(String, Range<usize>)> {
+    if let Some(model) = models.iter().find(|model| table_name(model).is_some_and(|name| name.name == table)) {
+        let range = column.and_then(|column| {
+            let field = model.fields.iter().find(|field| field.name == column).map(|field| field.range.clone());
+            field.or_else(|| {
+                model.relations.iter()
+                    .filter(|relation| relation.kind == RelationKind::BelongsTo)
+                    .find(|relation| relation.foreign_key.as_deref().is_some_and(|key| {
+                        key == column || key.strip_suffix("_id").is_some_and(|prefix| column == format!("{}_type", prefix))
+                    }))
+                    .map(|relation| relation.range.clone())
+            })
+        });
+        return Some((model.file.clone(), range.unwrap_or_else(|| model.range.clone())));
+    }
+    models.iter().find_map(|model| {
+        let relation = model.relations.iter().find(|relation| ddl::join_table(model, relation, models).is_some_and(|join_table| join_table.name == table))?;
+        Some((model.file.clone(), relation.range.clone()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_models;
+    use crate::schema;
+
+    #[test]
+    fn it_should_check_sqlite_databases() {
+        let (models, sources) = read_models(&[("models.py", "polls/models.py")]);
+        let connection = rusqlite::Connection::open_in_memory().unwrap();
+        connection.execute_batch("CREATE TABLE polls_question (id INTEGER PRIMARY KEY, question_text VARCHAR(200) NOT NULL, pub_date DATETIME NOT NULL, slug VARCHAR(50));
+            CREATE TABLE polls_choice (id INTEGER PRIMARY KEY, question_id BIGINT NOT NULL, choice_text VARCHAR(200) NOT NULL, votes DATETIME);").unwrap();
+        let findings = check(&models, &schema::sqlite(&connection).unwrap());
+
+        assert_eq!(report(&findings, &sources), "\
+polls/models.py:10: extra column `polls_question.slug`
+polls/models.py:29: column `polls_choice.votes` is datetime, expected integer
+polls/models.py:27: missing foreign key `polls_choice.question_id` -> `polls_question.id`
+");
+    }
+
+    #[test]
+    fn it_should_check_rails_schemas() {
+        let (mut models, mut sources) = read_models(&[("user.rb", "app/models/user.rb")]);
+        let relationship = b"class Relationship < ApplicationRecord\n  belongs_to :follower, class_name: \"User\"\n  belongs_to :followed, class_name: \"User\"\nend\n";
+        models.extend(crate::models::models("app/models/relationship.rb", relationship).unwrap());
+        sources.insert(String::from("app/models/relationship.rb"), relationship.to_vec());
+        let source = b"ActiveRecord::Schema[7.0].define(version: 2023_05_01_120000) do
+  create_table \"users\", force: :cascade do |t|
+    t.string \"name\"
+    t.string \"email\"
+  end
+  create_table \"relationships\", force: :cascade do |t|
+    t.integer \"follower_id\"
+  end
+end
+";
+        let findings = check(&models, &schema::ruby(source));
+
+        assert_eq!(findings.iter().map(|finding| &finding.drift).collect::<Vec<_>>(), vec![
+            &Drift::MissingColumn { table: String::from("relationships"), column: String::from("followed_id") },
+            &Drift::MissingForeignKey { table: String::from("relationships"), column: String::from("follower_id"), references: String::from("users.id") },
+        ]);
+        assert_eq!(report(&findings[..1], &sources), "app/models/relationship.rb:3: missing column `relationships.followed_id`\n");
+    }
+
+    #[test]
+    fn it_should_check_sql_dumps() {
+        let (models, _) = read_models(&[("Post.ts", "entity/Post.ts")]);
+        let tables = schema::sql("CREATE TABLE sample10_post (id SERIAL PRIMARY KEY, title VARCHAR(255) NOT NULL, \"text\" TEXT NOT NULL, \"detailsId\" INT);").unwrap();
+        let findings = check(&models, &tables);
+
+        assert_eq!(findings.iter().map(|finding| finding.drift.to_string()).collect::<Vec<_>>(), vec![
+            "missing column `sample10_post.coverId`",
+            "missing table `sample10_post_categories_category`",
+        ]);
+        assert_eq!(findings[1].file, "entity/Post.ts");
+    }
+}
@@ -0,0 +1,278 @@
+use std::path::Path;
+use indexmap::IndexMap;
+use crate::hierarchy::simple_name;
+use crate::models::{Model, Relation, RelationKind};
+
+// Entity-relationship diagrams of models, rendered as Mermaid, Graphviz DOT or PlantUML.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Mermaid,
+    Dot,
+    PlantUml,
+}
+
+// Grouping of entities, which Mermaid doesn't support and ignores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Clustering {
+    None,
+    // By the module or namespace enclosing models, leaving the others ungrouped.
+    Namespace,
+    // By the directory of the files declaring models.
+    Directory,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cardinality {
+    ZeroOrOne,
+    ExactlyOne,
+    ZeroOrMore,
+}
+
+// Relation between entities, drawn once even if both of the models declare it.
+struct Edge<'a> {
+    from: &'a str,
+    to: &'a str,
+    label: &'a str,
+    // Cardinalities at the ends of `from` and `to`.
+    cardinalities: (Cardinality, Cardinality),
+}
+
+pub fn render(models: &[Model], format: Format, clustering: Clustering) -> String {
+    match format {
+        Format::Mermaid => mermaid(models),
+        Format::Dot => dot(models, clustering),
+        Format::PlantUml => plantuml(models, clustering),
+    }
+}
+
+fn mermaid(models: &[Model]) -> String {
+    let mut lines = vec![String::from("erDiagram")];
+    for model in models {
+        lines.push(format!("    {} {{", identifier(&model.name)));
+        for field in &model.fields {
+            lines.push(format!("        {} {}", identifier(field.field_type.as_deref().unwrap_or("any")), identifier(&field.name)));
+        }
+        lines.push(String::from("    }"));
+    }
+    for edge in edges(models) {
+        lines.push(format!("    {} {} {} : {}", identifier(edge.from), crow_foot(edge.cardinalities), identifier(edge.to), quote(edge.label)));
+    }
+    lines.join("\n") + "\n"
+}
+
+fn dot(models: &[Model], clustering: Clustering) -> String {
+    let mut lines = vec![String::from("digraph erd {"), String::from("    node [shape=record];")];
+    for (cluster, models) in clusters(models, clustering) {
+        let indent = if cluster.is_some() { "        " } else { "    " };
+        if let Some(cluster) = &cluster {
+            // Backslashes start escape sequences of DOT labels, like the ones of PHP namespaces.
+            let cluster = cluster.replace('\\', "\\\\");
+            lines.push(format!("    subgraph {} {{", quote(&format!("cluster_{}", cluster))));
+            lines.push(format!("        label={};", quote(&cluster)));
+        }
+        for model in models {
+            let fields: String = model.fields.iter()
+                .map(|field| format!("{} : {}\\l", escape_record(&field.name), escape_record(field.field_type.as_deref().unwrap_or("any"))))
+                .collect();
+            lines.push(format!("{}{} [label={}];", indent, quote(&model.name), quote(&format!("{{{}|{}}}", escape_record(&model.name), fields))));
+        }
+        if cluster.is_some() {
+            lines.push(String::from("    }"));
+        }
+    }
+    for edge in edges(models) {
+        let (tail, head) = edge.cardinalities;
+        lines.push(format!(
+            "    {} -> {} [label={}, dir=both, arrowtail={}, arrowhead={}];",
+            quote(edge.from), quote(edge.to), quote(edge.label), arrow(tail), arrow(head),
+        ));
+    }
+    lines.push(String::from("}"));
+    lines.join("\n") + "\n"
+}
+
+fn plantuml(models: &[Model], clustering: Clustering) -> String {
+    let mut lines = vec![String::from("@startuml")];
+    for (cluster, models) in clusters(models, clustering) {
+        let indent = if cluster.is_some() { "  " } else { "" };
+        if let Some(cluster) = &cluster {
+            lines.push(format!("package {} {{", quote(cluster)));
+        }
+        for model in models {
+            lines.push(format!("{}entity {} {{", indent, identifier(&model.name)));
+            for field in &model.fields {
+                lines.push(format!("{}  {} : {}", indent, field.name, field.field_type.as_deref().unwrap_or("any")));
+            }
+            lines.push(format!("{}}}", indent));
+        }
+        if cluster.is_some() {
+            lines.push(String::from("}"));
+        }
+    }
+    for edge in edges(models) {
+        lines.push(format!("{} {} {} : {}", identifier(edge.from), crow_foot(edge.cardinalities), identifier(edge.to), edge.label));
+    }
+    lines.push(String::from("@enduml"));
+    lines.join("\n") + "\n"
+}
+
+// Relations between models with their cardinalities, skipping the ones declared again by the other side,
+// and the ones to targets which are not among the models, like unresolved targets of polymorphic relations.
+fn edges(models: &[Model]) -> Vec<Edge<'_>> {
+    let declares = |model: &str, target: &str, kinds: &[RelationKind]| {
+        models.iter()
+            .filter(|other| simple_name(&other.name) == model)
+            .flat_map(|other| &other.relations)
+            .any(|relation| kinds.contains(&relation.kind) && relation.target.as_deref().map(simple_name) == Some(target))
+    };
+
+    let mut edges = vec![];
+    for model in models {
+        let from = simple_name(&model.name);
+        for relation in &model.relations {
+            let to = match relation.target.as_deref() {
+                Some(target) if models.iter().any(|other| simple_name(&other.name) == simple_name(target)) => simple_name(target),
+                _ => continue,
+            };
+            let duplicated = match relation.kind {
+                RelationKind::BelongsTo => declares(to, from, &[RelationKind::HasOne, RelationKind::HasMany]),
+                RelationKind::ManyToMany => to < from && declares(to, from, &[RelationKind::ManyToMany]),
+                _ => false,
+            };
+            if !duplicated {
+                edges.push(Edge { from, to, label: &relation.name, cardinalities: cardinalities(relation) });
+            }
+        }
+    }
+    edges
+}
+
+fn cardinalities(relation: &Relation) -> (Cardinality, Cardinality) {
+    match relation.kind {
+        RelationKind::BelongsTo => (Cardinality::ZeroOrMore, Cardinality::ExactlyOne),
+        RelationKind::HasOne => (Cardinality::ExactlyOne, Cardinality::ZeroOrOne),
+        RelationKind::HasMany => (Cardinality::ExactlyOne, Cardinality::ZeroOrMore),
+        RelationKind::ManyToMany => (Cardinality::ZeroOrMore, Cardinality::ZeroOrMore),
+    }
+}
+
+// Crow's foot notation shared by Mermaid and PlantUML, like `||--o{`.
+fn crow_foot((from, to): (Cardinality, Cardinality)) -> String {
+    let left = match from {
+        Cardinality::ZeroOrOne => "|o",
+        Cardinality::ExactlyOne => "||",
+        Cardinality::ZeroOrMore => "}o",
+    };
+    let right = match to {
+        Cardinality::ZeroOrOne => "o|",
+        Cardinality::ExactlyOne => "||",
+        Cardinality::ZeroOrMore => "o{",
+    };
+    format!("{}--{}", left, right)
+}
+
+fn arrow(cardinality: Cardinality) -> &'static str {
+    match cardinality {
+        Cardinality::ZeroOrOne => "teeodot",
+        Cardinality::ExactlyOne => "teetee",
+        Cardinality::ZeroOrMore => "crowodot",
+    }
+}
+
+// Models grouped by their clusters, in the order of their first models.
+fn clusters(models: &[Model], clustering: Clustering) -> IndexMap<Option<String>, Vec<&Model>> {
+    let mut clusters: IndexMap<Option<String>, Vec<&Model>> = IndexMap::new();
+    for model in models {
+        let cluster = match clustering {
+            Clustering::None => None,
+            Clustering::Namespace => model.namespace.clone(),
+            Clustering::Directory => Path::new(&model.file).parent()
+                .and_then(|directory| directory.to_str())
+                .filter(|directory| !directory.is_empty())
+                .map(String::from),
+        };
+        clusters.entry(cluster).or_default().push(model);
+    }
+    clusters
+}
+
+// Name usable as identifier, like `App_Models_User` for `App\Models\User`.
+fn identifier(name: &str) -> String {
+    name.chars().map(|char| if char.is_alphanumeric() || char == '_' { char } else { '_' }).collect()
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\\\""))
+}
+
+fn escape_record(text: &str) -> String {
+    text.chars()
+        .flat_map(|char| match char {
+            '{' | '}' | '|' | '<' | '>' => vec!['\\', char],
+            _ => vec![char],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_models;
+
+    #[test]
+    fn it_should_render_mermaid() {
+        let diagram = render(&read_models(&[("models.py", "fixtures/models.py")]).0, Format::Mermaid, Clustering::None);
+        assert_eq!(diagram, "erDiagram
+    Question {
+        CharField question_text
+        DateTimeField pub_date
+    }
+    Choice {
+        CharField choice_text
+        IntegerField votes
+    }
+    Choice }o--|| Question : \"question\"
+");
+    }
+
+    #[test]
+    fn it_should_render_dot_by_directory() {
+        let mut models = read_models(&[("Animal.js", "fixtures/Animal.js")]).0;
+        let source = b"class Person extends Model {\n  static get tableName() {\n    return 'persons';\n  }\n}\n";
+        models.extend(crate::models::models("models/Person.js", source).unwrap());
+        let diagram = render(&models, Format::Dot, Clustering::Directory);
+        assert_eq!(diagram, "digraph erd {
+    node [shape=record];
+    subgraph \"cluster_fixtures\" {
+        label=\"fixtures\";
+        \"Animal\" [label=\"{Animal|id : integer\\lownerId : integer\\lname : string\\lspecies : string\\l}\"];
+    }
+    subgraph \"cluster_models\" {
+        label=\"models\";
+        \"Person\" [label=\"{Person|}\"];
+    }
+    \"Animal\" -> \"Person\" [label=\"owner\", dir=both, arrowtail=crowodot, arrowhead=teetee];
+}
+");
+    }
+
+    #[test]
+    fn it_should_render_plantuml_without_duplicated_relations() {
+        let mut models = read_models(&[("user.rb", "fixtures/user.rb")]).0;
+        let source = b"module Blog\n  class Micropost < ApplicationRecord\n    belongs_to :user\n  end\nend\n";
+        models.extend(crate::models::models("micropost.rb", source).unwrap());
+
+        let diagram = render(&models, Format::PlantUml, Clustering::Namespace);
+        assert_eq!(diagram, "@startuml
+entity User {
+}
+package \"Blog\" {
+  entity Micropost {
+  }
+}
+User ||--o{ Micropost : microposts
+@enduml
+");
+    }
+}
@@ -0,0 +1,341 @@
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::Path;
+use tree_sitter::Node;
+use crate::syntax::{children, parse, text, visit};
+
+// Inheritance and mixins of classes, built into a class hierarchy across files.
+
+// Class-like declaration, with the names of its ancestors as written in source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Class {
+    pub name: String,
+    // `class`, `module` (Ruby), `interface` or `trait` (PHP).
+    pub kind: &'static str,
+    pub superclass: Option<String>,
+    pub interfaces: Vec<String>,
+    // Included modules, used traits and Python mixins.
+    pub mixins: Vec<String>,
+    // Language of the file, which is `javascript` for TypeScript too as their classes inherit each other.
+    pub language: &'static str,
+    pub file: String,
+    pub range: Range<usize>,
+}
+
+impl Class {
+    fn new(name: &str, kind: &'static str, file: &str, range: Range<usize>) -> Self {
+        Class {
+            name: String::from(name),
+            kind,
+            superclass: None,
+            interfaces: vec![],
+            mixins: vec![],
+            language: "",
+            file: String::from(file),
+            range,
+        }
+    }
+}
+
+// Classes declared in supplied source, choosing the parser by the extension of file name.
+pub fn classes(file_name: &str, source: &[u8]) -> Option<Vec<Class>> {
+    let extension = Path::new(file_name).extension()?.to_str()?;
+    let (language, mut classes) = match extension {
+        "js" | "mjs" | "cjs" | "jsx" => ("javascript", javascript(tree_sitter_javascript::language(), file_name, source)),
+        "ts" | "mts" | "cts" => ("javascript", javascript(tree_sitter_typescript::language_typescript(), file_name, source)),
+        "tsx" => ("javascript", javascript(tree_sitter_typescript::language_tsx(), file_name, source)),
+        "rb" => ("ruby", ruby(file_name, source)),
+        "php" => ("php", php(file_name, source)),
+        "py" => ("python", python(file_name, source)),
+        _ => return None,
+    };
+    for class in &mut classes {
+        class.language = language;
+    }
+    Some(classes)
+}
+
+fn javascript(language: tree_sitter::Language, file_name: &str, source: &[u8]) -> Vec<Class> {
+    let tree = parse(language, source);
+
+    let mut classes = vec![];
+    visit(tree.root_node(), &mut |node| {
+        if !matches!(node.kind(), "class_declaration" | "abstract_class_declaration" | "class") {
+            return;
+        }
+        // Class expressions are named after the variables they are assigned to.
+        let name = match node.child_by_field_name("name") {
+            Some(name) => name,
+            None => match node.parent().and_then(|parent| parent.child_by_field_name("name")) {
+                Some(name) if node.parent().is_some_and(|parent| parent.kind() == "variable_declarator") => name,
+                _ => return,
+            },
+        };
+
+        let mut class = Class::new(text(name, source), "class", file_name, node.byte_range());
+        let heritage = children(node).into_iter().find(|child| child.kind() == "class_heritage");
+        for clause in heritage.map(children).unwrap_or_default().into_iter().filter(|child| child.is_named()) {
+            match clause.kind() {
+                "extends_clause" => class.superclass = clause.named_child(0).map(|value| type_name(value, source)),
+                "implements_clause" => class.interfaces = named_types(clause, source),
+                // JavaScript has the superclass right in the heritage.
+                _ => class.superclass = Some(type_name(clause, source)),
+            }
+        }
+        classes.push(class);
+    });
+    classes
+}
+
+fn ruby(file_name: &str, source: &[u8]) -> Vec<Class> {
+    let tree = parse(tree_sitter_ruby::language(), source);
+
+    let mut classes = vec![];
+    visit(tree.root_node(), &mut |node| {
+        if !matches!(node.kind(), "class" | "module") {
+            return;
+        }
+        let name = match node.child_by_field_name("name") {
+            Some(name) => name,
+            None => return,
+        };
+
+        let mut class = Class::new(text(name, source), node.kind(), file_name, node.byte_range());
+        class.superclass = node.child_by_field_name("superclass")
+            .and_then(|superclass| superclass.named_child(0))
+            .map(|superclass| String::from(text(superclass, source)));
+        for call in children(node).into_iter().filter(|child| child.kind() == "call") {
+            let method = call.child_by_field_name("method").map_or("", |method| text(method, source));
+            if call.child_by_field_name("receiver").is_some() || !matches!(method, "include" | "extend" | "prepend") {
+                continue;
+            }
+            let arguments = call.child_by_field_name("arguments").map(children).unwrap_or_default();
+            class.mixins.extend(arguments.into_iter()
+                .filter(|argument| matches!(argument.kind(), "constant" | "scope_resolution"))
+                .map(|argument| String::from(text(argument, source))));
+        }
+        classes.push(class);
+    });
+    classes
+}
+
+fn php(file_name: &str, source: &[u8]) -> Vec<Class> {
+    let tree = parse(tree_sitter_php::language(), source);
+
+    let mut classes = vec![];
+    visit(tree.root_node(), &mut |node| {
+        let kind = match node.kind() {
+            "class_declaration" | "enum_declaration" => "class",
+            "interface_declaration" => "interface",
+            "trait_declaration" => "trait",
+            _ => return,
+        };
+        let name = match node.child_by_field_name("name") {
+            Some(name) => name,
+            None => return,
+        };
+
+        let mut class = Class::new(text(name, source), kind, file_name, node.byte_range());
+        for child in children(node) {
+            match child.kind() {
+                // Interfaces may extend several interfaces.
+                "base_clause" if kind == "interface" => class.interfaces.extend(named_types(child, source)),
+                "base_clause" => class.superclass = named_types(child, source).into_iter().next(),
+                "class_interface_clause" => class.interfaces.extend(named_types(child, source)),
+                _ => {}
+            }
+        }
+        let body = node.child_by_field_name("body").map(children).unwrap_or_default();
+        for declaration in body.into_iter().filter(|child| child.kind() == "use_declaration") {
+            class.mixins.extend(named_types(declaration, source));
+        }
+        classes.push(class);
+    });
+    classes
+}
+
+// Python has no interfaces, and the last base class is taken as the superclass, following the convention placing mixins first.
+fn python(file_name: &str, source: &[u8]) -> Vec<Class> {
+    let tree = parse(tree_sitter_python::language(), source);
+
+    let mut classes = vec![];
+    visit(tree.root_node(), &mut |node| {
+        if node.kind() != "class_definition" {
+            return;
+        }
+        let name = match node.child_by_field_name("name") {
+            Some(name) => name,
+            None => return,
+        };
+
+        let mut class = Class::new(text(name, source), "class", file_name, node.byte_range());
+        let mut bases: Vec<String> = node.child_by_field_name("superclasses")
+            .map(children)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|base| matches!(base.kind(), "identifier" | "attribute"))
+            .map(|base| String::from(text(base, source)))
+            .collect();
+        class.superclass = bases.pop();
+        class.mixins = bases;
+        classes.push(class);
+    });
+    classes
+}
+
+// Names of types listed in the clause, like `implements I, J<T>`.
+fn named_types(clause: Node, source: &[u8]) -> Vec<String> {
+    children(clause).into_iter()
+        .filter(|child| child.is_named() && !matches!(child.kind(), "type_arguments" | "comment"))
+        .map(|child| type_name(child, source))
+        .collect()
+}
+
+// Name of the type without its type arguments, like `Base` for `Base<T>`.
+fn type_name(node: Node, source: &[u8]) -> String {
+    match node.kind() {
+        "generic_type" => node.named_child(0).map_or_else(String::new, |name| type_name(name, source)),
+        _ => String::from(text(node, source)),
+    }
+}
+
+// Last segment of qualified name, like `Base` for `ActiveRecord::Base`, `models.Model` or `\App\Models\User`.
+pub(crate) fn simple_name(name: &str) -> &str {
+    name.rsplit(['.', ':', '\\']).next().unwrap_or(name)
+}
+
+// Class hierarchy across files, whose classes are linked by their simple names within languages,
+// or by their qualified names across them.
+#[derive(Debug, Default)]
+pub struct Hierarchy {
+    classes: Vec<Class>,
+}
+
+impl Hierarchy {
+    pub fn new() -> Self {
+        Hierarchy::default()
+    }
+
+    // Add classes declared in the file, ignoring the files of unsupported languages.
+    pub fn add_file(&mut self, file_name: &str, source: &[u8]) {
+        self.classes.extend(classes(file_name, source).unwrap_or_default());
+    }
+
+    pub fn classes(&self) -> &[Class] {
+        &self.classes
+    }
+
+    // Classes inheriting the named class directly or indirectly.
+    pub fn subclasses_of(&self, name: &str) -> Vec<&Class> {
+        self.descendants(|class| class.superclass.as_deref().is_some_and(|superclass| is_named(superclass, name)))
+    }
+
+    // Classes implementing the named interface, including the subclasses of implementing classes.
+    pub fn implementations_of(&self, name: &str) -> Vec<&Class> {
+        self.descendants(|class| class.interfaces.iter().any(|interface| is_named(interface, name)))
+    }
+
+    // Classes using the named module or trait, including the subclasses of using classes.
+    pub fn classes_using(&self, name: &str) -> Vec<&Class> {
+        self.descendants(|class| class.mixins.iter().any(|mixin| is_named(mixin, name)))
+    }
+
+    // Classes matching the predicate and all of their subclasses, in the order they were added.
+    fn descendants(&self, predicate: impl Fn(&Class) -> bool) -> Vec<&Class> {
+        let mut found: HashSet<usize> = HashSet::new();
+        let mut pending: Vec<usize> = (0..self.classes.len()).filter(|&index| predicate(&self.classes[index])).collect();
+        while let Some(index) = pending.pop() {
+            if !found.insert(index) {
+                continue;
+            }
+            let parent = &self.classes[index];
+            pending.extend((0..self.classes.len()).filter(|&child| {
+                let child = &self.classes[child];
+                child.superclass.as_deref().is_some_and(|superclass| {
+                    match child.language == parent.language {
+                        true => simple_name(superclass) == simple_name(&parent.name),
+                        false => is_qualified(superclass) && is_named(superclass, &parent.name),
+                    }
+                })
+            }));
+        }
+        (0..self.classes.len()).filter(|index| found.contains(index)).map(|index| &self.classes[index]).collect()
+    }
+}
+
+// Whether the ancestor as written in source is the named one, comparing qualified names when both of them are qualified,
+// like `models.Model`, and requiring simple names to be given as such, unlike `Model` of other libraries.
+fn is_named(written: &str, name: &str) -> bool {
+    let trim = |name: &str| String::from(name.trim_start_matches(['\\', ':']));
+    match (is_qualified(written), is_qualified(name)) {
+        (_, false) => simple_name(written) == name,
+        (true, true) => trim(written) == trim(name),
+        (false, true) => false,
+    }
+}
+
+fn is_qualified(name: &str) -> bool {
+    simple_name(name) != name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_fixture;
+
+    fn names(classes: &[&Class]) -> Vec<String> {
+        classes.iter().map(|class| class.name.clone()).collect()
+    }
+
+    #[test]
+    fn it_should_read_ancestors() {
+        let source = b"class Post extends Base<Post> implements Publishable, Sluggable<Post> {}\n";
+        let classes = super::classes("Post.ts", source).unwrap();
+        assert_eq!(classes[0].superclass.as_deref(), Some("Base"));
+        assert_eq!(classes[0].interfaces, vec!["Publishable", "Sluggable"]);
+
+        let source = b"class User < ApplicationRecord\n  include Comparable, Concerns::Searchable\nend\n";
+        let classes = super::classes("user.rb", source).unwrap();
+        assert_eq!(classes[0].superclass.as_deref(), Some("ApplicationRecord"));
+        assert_eq!(classes[0].mixins, vec!["Comparable", "Concerns::Searchable"]);
+
+        let source = b"class QuestionView(LoginRequiredMixin, generic.DetailView, metaclass=Meta):\n    pass\n";
+        let classes = super::classes("views.py", source).unwrap();
+        assert_eq!(classes[0].superclass.as_deref(), Some("generic.DetailView"));
+        assert_eq!(classes[0].mixins, vec!["LoginRequiredMixin"]);
+
+        assert_eq!(super::classes("README.md", b""), None);
+    }
+
+    #[test]
+    fn it_should_build_class_hierarchy() {
+        let mut hierarchy = Hierarchy::new();
+        hierarchy.add_file("user.rb", &read_fixture("user.rb"));
+        hierarchy.add_file("models.py", &read_fixture("models.py"));
+        hierarchy.add_file("Animal.js", &read_fixture("Animal.js"));
+        hierarchy.add_file("admin.rb", b"class Admin < User\nend\nclass Guest\n  include Comparable\nend\n");
+        hierarchy.add_file("Moderator.ts", b"class Moderator extends User {}\n");
+
+        // `User` of TypeScript is not the one of Ruby, and `Model` of Objection is not `models.Model` of Django.
+        assert_eq!(names(&hierarchy.subclasses_of("ApplicationRecord")), vec!["User", "Admin"]);
+        assert_eq!(names(&hierarchy.subclasses_of("models.Model")), vec!["Question", "Choice"]);
+        assert_eq!(names(&hierarchy.subclasses_of("Model")), vec!["Question", "Choice", "Animal"]);
+        assert_eq!(names(&hierarchy.classes_using("Comparable")), vec!["Guest"]);
+        assert!(hierarchy.implementations_of("Comparable").is_empty());
+    }
+
+    #[test]
+    fn it_should_read_php_ancestors() {
+        let mut hierarchy = Hierarchy::new();
+        hierarchy.add_file("User.php", &read_fixture("User.php"));
+        hierarchy.add_file("Article.php", &read_fixture("Article.php"));
+
+        let user = &hierarchy.classes()[0];
+        assert_eq!(user.superclass.as_deref(), Some("Authenticatable"));
+        assert_eq!(user.mixins, vec!["HasFactory", "Notifiable"]);
+
+        assert_eq!(names(&hierarchy.classes_using("HasSlug")), vec!["Article"]);
+        assert_eq!(names(&hierarchy.implementations_of("Publishable")), vec!["Article"]);
+        assert_eq!(names(&hierarchy.subclasses_of("Authenticatable")), vec!["User"]);
+    }
+}
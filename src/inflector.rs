@@ -0,0 +1,159 @@
+// Minimal English inflections, following the rules ORMs use to derive names from each other.
+
+// Words whose plural is the same as their singular.
+const UNCOUNTABLES: [&str; 10] = [
+    "equipment", "information", "rice", "money", "species", "series", "fish", "sheep", "news", "police",
+];
+
+// Irregular (singular, plural) pairs, which also apply to compound words like `salesperson`.
+const IRREGULARS: [(&str, &str); 18] = [
+    ("person", "people"), ("child", "children"), ("movie", "movies"),
+    ("quiz", "quizzes"), ("matrix", "matrices"), ("vertex", "vertices"), ("index", "indices"),
+    ("analysis", "analyses"), ("crisis", "crises"), ("thesis", "theses"), ("datum", "data"), ("medium", "media"),
+    ("tomato", "tomatoes"), ("potato", "potatoes"), ("hero", "heroes"),
+    ("bus", "buses"), ("status", "statuses"), ("alias", "aliases"),
+];
+
+// Irregular (singular, plural) pairs, which only apply to whole words, so that `box` or `blouse` are not inflected by them.
+const IRREGULAR_WORDS: [(&str, &str); 16] = [
+    ("ox", "oxen"), ("mouse", "mice"), ("louse", "lice"), ("man", "men"), ("woman", "women"), ("foot", "feet"), ("tooth", "teeth"), ("goose", "geese"),
+    ("knife", "knives"), ("wife", "wives"), ("life", "lives"), ("leaf", "leaves"), ("half", "halves"), ("wolf", "wolves"),
+    ("shelf", "shelves"), ("thief", "thieves"),
+];
+
+// Returns the plural form of given (lower case) word.
+pub fn pluralize(word: &str) -> String {
+    if word.is_empty() || is_uncountable(word) {
+        return word.to_string();
+    }
+    for (singular, plural) in IRREGULARS {
+        if let Some(prefix) = word.strip_suffix(singular) {
+            return format!("{}{}", prefix, plural);
+        }
+    }
+    for (singular, plural) in IRREGULAR_WORDS {
+        if let Some(prefix) = strip_word(word, singular) {
+            return format!("{}{}", prefix, plural);
+        }
+    }
+
+    if let Some(stem) = word.strip_suffix('y') {
+        if !stem.ends_with(['a', 'e', 'i', 'o', 'u']) {
+            return format!("{}ies", stem);
+        }
+    }
+    if word.ends_with(['s', 'x', 'z']) || word.ends_with("ch") || word.ends_with("sh") {
+        return format!("{}es", word);
+    }
+    format!("{}s", word)
+}
+
+// Returns the singular form of given (lower case) word.
+pub fn singularize(word: &str) -> String {
+    if word.is_empty() || is_uncountable(word) {
+        return word.to_string();
+    }
+    for (singular, plural) in IRREGULARS {
+        if let Some(prefix) = word.strip_suffix(plural) {
+            return format!("{}{}", prefix, singular);
+        }
+    }
+    for (singular, plural) in IRREGULAR_WORDS {
+        if let Some(prefix) = strip_word(word, plural) {
+            return format!("{}{}", prefix, singular);
+        }
+    }
+
+    if let Some(stem) = word.strip_suffix("ies") {
+        return format!("{}y", stem);
+    }
+    for suffix in ["sses", "shes", "ches", "xes", "zes"] {
+        if word.ends_with(suffix) {
+            return word[..word.len() - 2].to_string();
+        }
+    }
+    if word.ends_with("ss") || word.ends_with("us") {
+        return word.to_string();
+    }
+    match word.strip_suffix('s') {
+        Some(stem) => stem.to_string(),
+        None => word.to_string(),
+    }
+}
+
+// Returns the class name for given snake case word, like `UserProfile` for `user_profile`.
+pub fn camelize(word: &str) -> String {
+    word.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+// Returns the snake case word for given class name, like `user_profile` for `UserProfile`.
+pub fn underscore(word: &str) -> String {
+    let mut underscored = String::new();
+    for (index, char) in word.chars().enumerate() {
+        if char.is_uppercase() {
+            if index > 0 && !underscored.ends_with('_') {
+                underscored.push('_');
+            }
+            underscored.extend(char.to_lowercase());
+        } else {
+            underscored.push(char);
+        }
+    }
+    underscored
+}
+
+// Strip the last word of snake case word, like `admin_` for `admin_man`.
+fn strip_word<'a>(word: &'a str, last: &str) -> Option<&'a str> {
+    word.strip_suffix(last).filter(|prefix| prefix.is_empty() || prefix.ends_with('_'))
+}
+
+// Whether the last word of snake case word is uncountable, like `tropical_fish` but not `selfish` or `price`.
+fn is_uncountable(word: &str) -> bool {
+    UNCOUNTABLES.iter().any(|uncountable| strip_word(word, uncountable).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_inflect_words() {
+        assert_eq!(pluralize("user"), "users");
+        assert_eq!(pluralize("category"), "categories");
+        assert_eq!(pluralize("address"), "addresses");
+        assert_eq!(pluralize("person"), "people");
+        assert_eq!(pluralize("species"), "species");
+
+        assert_eq!(singularize("microposts"), "micropost");
+        assert_eq!(singularize("active_relationships"), "active_relationship");
+        assert_eq!(singularize("following"), "following");
+        assert_eq!(singularize("categories"), "category");
+        assert_eq!(singularize("addresses"), "address");
+        assert_eq!(singularize("people"), "person");
+
+        assert_eq!(pluralize("salesperson"), "salespeople");
+        assert_eq!(pluralize("index"), "indices");
+        assert_eq!(pluralize("analysis"), "analyses");
+        assert_eq!(pluralize("delivery_man"), "delivery_men");
+        assert_eq!(pluralize("human"), "humans");
+        assert_eq!(pluralize("box"), "boxes");
+        assert_eq!(pluralize("knife"), "knives");
+        assert_eq!(singularize("statuses"), "status");
+        assert_eq!(singularize("movies"), "movie");
+        assert_eq!(singularize("data"), "datum");
+        assert_eq!(singularize("olives"), "olive");
+        assert_eq!(singularize("wolves"), "wolf");
+
+        assert_eq!(camelize("active_relationship"), "ActiveRelationship");
+        assert_eq!(underscore("PostDetails"), "post_details");
+        assert_eq!(underscore("ownerId"), "owner_id");
+    }
+}
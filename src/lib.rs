@@ -1,7 +1,42 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use indexmap::{IndexMap, indexmap};
 use tree_sitter_tags::{TagsConfiguration, TagsContext};
+use macros::{MacroExpander, Synthetic};
+use queries::Fragment;
+use signatures::Signature;
+
+pub mod audit;
+pub mod codegen;
+pub mod ddl;
+pub mod drift;
+pub mod erd;
+pub mod hierarchy;
+pub mod inflector;
+pub mod macros;
+pub mod migrations;
+pub mod models;
+pub mod modifiers;
+pub mod php;
+pub mod python;
+pub mod queries;
+pub mod schema;
+pub mod signatures;
+mod syntax;
+
+// Tagging queries of this crate.
+// JSX, TypeScript and Python ones are prepended to the bundled ones so that their patterns take precedence,
+// while Ruby one replaces the bundled query as it re-classifies its catch-all references,
+// and PHP one replaces the bundled query as some of its captures are not valid kinds of tags.
+const JSX_TAGGING_QUERY: &str = include_str!("../queries/jsx/tags.scm");
+const TS_TAGGING_QUERY: &str = include_str!("../queries/typescript/tags.scm");
+const RUBY_TAGGING_QUERY: &str = include_str!("../queries/ruby/tags.scm");
+const PHP_TAGGING_QUERY: &str = include_str!("../queries/php/tags.scm");
+const PY_TAGGING_QUERY: &str = include_str!("../queries/python/tags.scm");
+
+// Locals queries for the languages which don't bundle them.
+const PHP_LOCALS_QUERY: &str = include_str!("../queries/php/locals.scm");
+const PY_LOCALS_QUERY: &str = include_str!("../queries/python/locals.scm");
 
 // Open source file
 #[allow(dead_code)]
@@ -10,33 +45,139 @@ fn read_fixture(fixture_name: &str) -> Vec<u8> {
     fs::read(file_path.unwrap()).expect("Can't open fixture file")
 }
 
-// Parse tags from supplied source.
+// Models of fixtures read as the files named by the pairs, with their sources keyed by these file names.
+#[cfg(test)]
+fn read_models(fixtures: &[(&str, &str)]) -> (Vec<models::Model>, IndexMap<String, Vec<u8>>) {
+    let mut models = vec![];
+    let mut sources = IndexMap::new();
+    for (fixture_name, file_name) in fixtures {
+        let source = read_fixture(fixture_name);
+        models.extend(models::models(file_name, &source).expect("Unsupported fixture file"));
+        sources.insert(String::from(*file_name), source);
+    }
+    (models, sources)
+}
+
+// Rows of synthetic definitions, marked by the macro which creates them.
+fn synthetic_rows(source: &[u8], synthetics: Vec<Synthetic>) -> Vec<(usize, IndexMap<&str, String>)> {
+    synthetics.into_iter()
+        .map(|synthetic| (synthetic.range.start, indexmap! {
+                "name" => synthetic.name,
+                "kind" => String::from(synthetic.kind),
+                "def_or_ref" => String::from("def"),
+                "first_line" => String::from(line_at(source, synthetic.range.start)),
+                "synthetic" => synthetic.macro_name,
+            }))
+        .collect()
+}
+
+// Rows of the tables and columns referred by raw SQL, and of the values interpolated into it which may inject SQL,
+// marked by the method receiving the SQL.
+fn sql_rows(source: &[u8], fragments: Vec<Fragment>) -> Vec<(usize, IndexMap<&str, String>)> {
+    let mut rows = vec![];
+    for fragment in fragments {
+        let start = fragment.range.start;
+        let references = fragment.tables.into_iter().map(|table| (table, "table")).chain(fragment.columns.into_iter().map(|column| (column, "column")));
+        for (name, kind) in references {
+            rows.push((start, indexmap! {
+                "name" => name,
+                "kind" => String::from(kind),
+                "def_or_ref" => String::from("ref"),
+                "first_line" => String::from(line_at(source, start)),
+                "sql" => fragment.method.clone(),
+            }));
+        }
+        for interpolation in fragment.interpolations {
+            rows.push((interpolation.start, indexmap! {
+                "name" => String::from_utf8_lossy(&source[interpolation.clone()]).into_owned(),
+                "kind" => String::from("sql_injection"),
+                "def_or_ref" => String::from("ref"),
+                "first_line" => String::from(line_at(source, interpolation.start)),
+                "sql" => fragment.method.clone(),
+            }));
+        }
+    }
+    rows
+}
+
+// Add a column to the tags whose names start at the keys of given values.
+fn add_column<'a>(columns: &mut IndexMap<usize, IndexMap<&'a str, String>>, column: &'a str, values: impl IntoIterator<Item = (usize, String)>) {
+    for (start, value) in values {
+        columns.entry(start).or_default().insert(column, value);
+    }
+}
+
+// Add `modifiers` column, listing modifiers separated by spaces.
+fn add_modifiers(columns: &mut IndexMap<usize, IndexMap<&str, String>>, modifiers: IndexMap<usize, Vec<String>>) {
+    add_column(columns, "modifiers", modifiers.into_iter().map(|(start, modifiers)| (start, modifiers.join(" "))));
+}
+
+// Add `signature` column, like `(id: number, limit?: number, *fields: string[]): User`, marking rest parameters as `*` across languages.
+fn add_signatures(columns: &mut IndexMap<usize, IndexMap<&str, String>>, signatures: IndexMap<usize, Signature>) {
+    add_column(columns, "signature", signatures.into_iter().map(|(start, signature)| (start, signature.to_string())));
+}
+
+// Extra columns of the tags in JavaScript family source.
+fn javascript_columns(language: tree_sitter::Language, source: &[u8]) -> IndexMap<usize, IndexMap<&str, String>> {
+    let mut columns = IndexMap::new();
+    add_modifiers(&mut columns, modifiers::javascript(language, source));
+    add_signatures(&mut columns, signatures::javascript(language, source));
+    columns
+}
+
+// Parse tags from supplied source, and merge extra rows into them by their position.
+// Extra columns are appended to the tags whose names start at their keys.
 #[allow(dead_code)]
-fn parse_tags(config: TagsConfiguration, source: &[u8]) -> Vec<IndexMap<&str, String>> {
+fn parse_tags_with<'a>(
+    config: TagsConfiguration,
+    source: &'a [u8],
+    rows: Vec<(usize, IndexMap<&'a str, String>)>,
+    columns: IndexMap<usize, IndexMap<&'a str, String>>,
+) -> Vec<IndexMap<&'a str, String>> {
     let mut context = TagsContext::new();
 
     let tags = context.generate_tags(&config, source, None).expect("Can't parse source code").0;
 
-    let mut parsed: Vec<IndexMap<&str, String>> = vec![];
+    let mut parsed: Vec<(usize, IndexMap<&str, String>)> = vec![];
     for result in tags {
         let tag = result.unwrap();
 
         // SEE: https://github.com/tree-sitter/tree-sitter/blob/v0.20.4/cli/src/tags.rs#L64-L71
-        let name = std::str::from_utf8(&source[tag.name_range]).unwrap_or("");
+        let name = std::str::from_utf8(&source[tag.name_range.clone()]).unwrap_or("");
         let kind = config.syntax_type_name(tag.syntax_type_id);
         let def_or_ref = if tag.is_definition { "def" } else { "ref" };
         let first_line = std::str::from_utf8(&source[tag.line_range]).unwrap_or("");
 
-        parsed.push(indexmap! {
-                // Wrap all variables as String for preventing exposing local variable reference that Rust compiler would complain :(
-                "name" => String::from(name),
-                "kind" => String::from(kind),
-                "def_or_ref" => String::from(def_or_ref),
-                "first_line" => String::from(first_line),
-            });
+        let mut row = indexmap! {
+            // Wrap all variables as String for preventing exposing local variable reference that Rust compiler would complain :(
+            "name" => String::from(name),
+            "kind" => String::from(kind),
+            "def_or_ref" => String::from(def_or_ref),
+            "first_line" => String::from(first_line),
+        };
+        if let Some(extra) = columns.get(&tag.name_range.start) {
+            row.extend(extra.clone());
+        }
+        parsed.push((tag.name_range.start, row));
     }
 
-    parsed
+    parsed.extend(rows);
+    // Stable sort keeps extra rows after the tag at the same position.
+    parsed.sort_by_key(|(start, _)| *start);
+
+    parsed.into_iter().map(|(_, tag)| tag).collect()
+}
+
+// Returns the line containing the byte offset, without surrounding whitespaces.
+fn line_at(source: &[u8], offset: usize) -> &str {
+    let start = source[..offset].iter().rposition(|&byte| byte == b'\n').map_or(0, |position| position + 1);
+    let end = source[offset..].iter().position(|&byte| byte == b'\n').map_or(source.len(), |position| offset + position);
+    std::str::from_utf8(&source[start..end]).unwrap_or("").trim()
+}
+
+// Returns the one-based number of the line containing the byte offset.
+pub(crate) fn line_number(source: &[u8], offset: usize) -> usize {
+    source[..offset.min(source.len())].iter().filter(|&&byte| byte == b'\n').count() + 1
 }
 
 #[allow(dead_code)]
@@ -46,50 +187,124 @@ fn parse_js(source: &[u8]) -> Vec<IndexMap<&str, String>> {
         tree_sitter_javascript::TAGGING_QUERY,
         tree_sitter_javascript::LOCALS_QUERY,
     ).unwrap();
-    parse_tags(config, source)
+    parse_tags_with(config, source, sql_rows(source, queries::javascript(tree_sitter_javascript::language(), source)), javascript_columns(tree_sitter_javascript::language(), source))
+}
+
+#[allow(dead_code)]
+fn parse_jsx(source: &[u8]) -> Vec<IndexMap<&str, String>> {
+    let tags_query = JSX_TAGGING_QUERY.to_owned() + tree_sitter_javascript::TAGGING_QUERY;
+    let config = TagsConfiguration::new(
+        tree_sitter_javascript::language(),
+        &tags_query,
+        tree_sitter_javascript::LOCALS_QUERY,
+    ).unwrap();
+    parse_tags_with(config, source, sql_rows(source, queries::javascript(tree_sitter_javascript::language(), source)), javascript_columns(tree_sitter_javascript::language(), source))
 }
 
 #[allow(dead_code)]
 fn parse_ts(source: &[u8]) -> Vec<IndexMap<&str, String>> {
     // SEE: https://github.com/tree-sitter/tree-sitter-typescript/blob/v0.20.1/package.json#L45-L52
-    let tags_query = tree_sitter_typescript::TAGGING_QUERY.to_owned() + tree_sitter_javascript::TAGGING_QUERY;
+    let tags_query = TS_TAGGING_QUERY.to_owned() + tree_sitter_typescript::TAGGING_QUERY + tree_sitter_javascript::TAGGING_QUERY;
     let locals_query = tree_sitter_typescript::LOCALS_QUERY.to_owned() + tree_sitter_javascript::LOCALS_QUERY;
     let config = TagsConfiguration::new(
         tree_sitter_typescript::language_typescript(),
         &tags_query,
         &locals_query,
     ).unwrap();
-    parse_tags(config, source)
+    parse_tags_with(config, source, sql_rows(source, queries::javascript(tree_sitter_typescript::language_typescript(), source)), javascript_columns(tree_sitter_typescript::language_typescript(), source))
+}
+
+#[allow(dead_code)]
+fn parse_tsx(source: &[u8]) -> Vec<IndexMap<&str, String>> {
+    let tags_query = JSX_TAGGING_QUERY.to_owned() + TS_TAGGING_QUERY + tree_sitter_typescript::TAGGING_QUERY + tree_sitter_javascript::TAGGING_QUERY;
+    let locals_query = tree_sitter_typescript::LOCALS_QUERY.to_owned() + tree_sitter_javascript::LOCALS_QUERY;
+    let config = TagsConfiguration::new(
+        tree_sitter_typescript::language_tsx(),
+        &tags_query,
+        &locals_query,
+    ).unwrap();
+    parse_tags_with(config, source, sql_rows(source, queries::javascript(tree_sitter_typescript::language_tsx(), source)), javascript_columns(tree_sitter_typescript::language_tsx(), source))
 }
 
 #[allow(dead_code)]
 fn parse_rb(source: &[u8]) -> Vec<IndexMap<&str, String>> {
+    parse_rb_with_macros(source, &macros::ruby_expanders())
+}
+
+// Parse tags from supplied Ruby source, along with definitions synthesized from macro calls by given expanders.
+#[allow(dead_code)]
+fn parse_rb_with_macros<'a>(source: &'a [u8], expanders: &[Box<dyn MacroExpander>]) -> Vec<IndexMap<&'a str, String>> {
     let config = TagsConfiguration::new(
         tree_sitter_ruby::language(),
-        tree_sitter_ruby::TAGGING_QUERY,
+        RUBY_TAGGING_QUERY,
         tree_sitter_ruby::LOCALS_QUERY,
     ).unwrap();
-    parse_tags(config, source)
+    let mut rows = synthetic_rows(source, macros::expand_ruby(source, expanders));
+    rows.extend(sql_rows(source, queries::ruby(source)));
+    let mut columns = IndexMap::new();
+    add_modifiers(&mut columns, modifiers::ruby(source));
+    add_signatures(&mut columns, signatures::ruby(source));
+    parse_tags_with(config, source, rows, columns)
 }
 
 #[allow(dead_code)]
 fn parse_php(source: &[u8]) -> Vec<IndexMap<&str, String>> {
     let config = TagsConfiguration::new(
         tree_sitter_php::language(),
-        tree_sitter_php::TAGS_QUERY,
-        "",
+        PHP_TAGGING_QUERY,
+        PHP_LOCALS_QUERY,
     ).unwrap();
-    parse_tags(config, source)
+    // Attributes, scopes and relations of Eloquent models are resolved by magic methods.
+    let mut rows = synthetic_rows(source, php::magic_members(source));
+    rows.extend(sql_rows(source, queries::php(source)));
+    let mut columns = IndexMap::new();
+    add_modifiers(&mut columns, php::modifiers(source));
+    add_signatures(&mut columns, signatures::php(source));
+    parse_tags_with(config, source, rows, columns)
 }
 
 #[allow(dead_code)]
 fn parse_py(source: &[u8]) -> Vec<IndexMap<&str, String>> {
+    let tags_query = PY_TAGGING_QUERY.to_owned() + tree_sitter_python::TAGGING_QUERY;
     let config = TagsConfiguration::new(
         tree_sitter_python::language(),
-        tree_sitter_python::TAGGING_QUERY,
-        "",
+        &tags_query,
+        PY_LOCALS_QUERY,
     ).unwrap();
-    parse_tags(config, source)
+
+    // Names listed in `__all__` are references to the definitions exported by the module.
+    let mut rows: Vec<(usize, IndexMap<&str, String>)> = python::exports(source).into_iter()
+        .map(|export| (export.range.start, indexmap! {
+                "name" => export.name,
+                "kind" => String::from("export"),
+                "def_or_ref" => String::from("ref"),
+                "first_line" => String::from(line_at(source, export.range.start)),
+            }))
+        .collect();
+    rows.extend(sql_rows(source, queries::python(source)));
+    let mut columns = IndexMap::new();
+    add_modifiers(&mut columns, modifiers::python(source));
+    add_signatures(&mut columns, signatures::python(source));
+    let decorators = python::decorators(source).into_iter().map(|(start, decorators)| (start, decorators.join(", ")));
+    add_column(&mut columns, "decorators", decorators);
+    parse_tags_with(config, source, rows, columns)
+}
+
+// Parse tags from supplied source, choosing the parser by the extension of file name.
+#[allow(dead_code)]
+fn parse_file<'a>(file_name: &str, source: &'a [u8]) -> Option<Vec<IndexMap<&'a str, String>>> {
+    let extension = Path::new(file_name).extension()?.to_str()?;
+    let tags = match extension {
+        "js" | "mjs" | "cjs" => parse_js(source),
+        "jsx" => parse_jsx(source),
+        "ts" | "mts" | "cts" => parse_ts(source),
+        "tsx" => parse_tsx(source),
+        "rb" => parse_rb(source),
+        "php" => parse_php(source),
+        "py" => parse_py(source),
+        _ => return None,
+    };
+    Some(tags)
 }
 
 #[cfg(test)]
@@ -104,9 +319,9 @@ mod tests {
 
         let expected: Vec<IndexMap<&str, &str>> = vec![
             indexmap! {"name" => "Animal", "kind" => "class", "def_or_ref" => "def", "first_line" => "class Animal extends Model {"},
-            indexmap! {"name" => "tableName", "kind" => "method", "def_or_ref" => "def", "first_line" => "static get tableName() {"},
-            indexmap! {"name" => "jsonSchema", "kind" => "method", "def_or_ref" => "def", "first_line" => "static get jsonSchema() {"},
-            indexmap! {"name" => "relationMappings", "kind" => "method", "def_or_ref" => "def", "first_line" => "static get relationMappings() {"},
+            indexmap! {"name" => "tableName", "kind" => "method", "def_or_ref" => "def", "first_line" => "static get tableName() {", "modifiers" => "public static getter", "signature" => "()"},
+            indexmap! {"name" => "jsonSchema", "kind" => "method", "def_or_ref" => "def", "first_line" => "static get jsonSchema() {", "modifiers" => "public static getter", "signature" => "()"},
+            indexmap! {"name" => "relationMappings", "kind" => "method", "def_or_ref" => "def", "first_line" => "static get relationMappings() {", "modifiers" => "public static getter", "signature" => "()"}
         ];
 
         assert_eq!(tags, expected);
@@ -121,24 +336,126 @@ mod tests {
             indexmap! {"name" => "Entity", "kind" => "call", "def_or_ref" => "ref", "first_line" => "@Entity(\"sample10_post\")"},
             indexmap! {"name" => "Post", "kind" => "class", "def_or_ref" => "def", "first_line" => "export class Post {"},
             indexmap! {"name" => "PrimaryGeneratedColumn", "kind" => "call", "def_or_ref" => "ref", "first_line" => "@PrimaryGeneratedColumn()"},
+            indexmap! {"name" => "id", "kind" => "property", "def_or_ref" => "def", "first_line" => "id: number", "modifiers" => "public"},
             indexmap! {"name" => "Column", "kind" => "call", "def_or_ref" => "ref", "first_line" => "@Column({"},
+            indexmap! {"name" => "title", "kind" => "property", "def_or_ref" => "def", "first_line" => "title: string", "modifiers" => "public"},
             indexmap! {"name" => "Column", "kind" => "call", "def_or_ref" => "ref", "first_line" => "@Column({"},
+            indexmap! {"name" => "text", "kind" => "property", "def_or_ref" => "def", "first_line" => "text: string", "modifiers" => "public"},
             indexmap! {"name" => "OneToOne", "kind" => "call", "def_or_ref" => "ref", "first_line" => "@OneToOne((type) => PostDetails, (details) => details.post, {"},
             indexmap! {"name" => "JoinColumn", "kind" => "call", "def_or_ref" => "ref", "first_line" => "@JoinColumn()"},
+            indexmap! {"name" => "details", "kind" => "property", "def_or_ref" => "def", "first_line" => "details: PostDetails", "modifiers" => "public"},
             indexmap! {"name" => "PostDetails", "kind" => "type", "def_or_ref" => "ref", "first_line" => "details: PostDetails"},
             indexmap! {"name" => "OneToMany", "kind" => "call", "def_or_ref" => "ref", "first_line" => "@OneToMany((type) => Image, (image) => image.post, {"},
+            indexmap! {"name" => "images", "kind" => "property", "def_or_ref" => "def", "first_line" => "images: Image[] = []", "modifiers" => "public"},
             indexmap! {"name" => "OneToMany", "kind" => "call", "def_or_ref" => "ref", "first_line" => "@OneToMany((type) => Image, (image) => image.secondaryPost)"},
+            indexmap! {"name" => "secondaryImages", "kind" => "property", "def_or_ref" => "def", "first_line" => "secondaryImages: Image[]", "modifiers" => "public"},
             indexmap! {"name" => "ManyToOne", "kind" => "call", "def_or_ref" => "ref", "first_line" => "@ManyToOne((type) => Cover, (cover) => cover.posts, {"},
             indexmap! {"name" => "JoinColumn", "kind" => "call", "def_or_ref" => "ref", "first_line" => "@JoinColumn({ name: \"coverId\" })"},
+            indexmap! {"name" => "cover", "kind" => "property", "def_or_ref" => "def", "first_line" => "cover: Cover", "modifiers" => "public"},
             indexmap! {"name" => "Cover", "kind" => "type", "def_or_ref" => "ref", "first_line" => "cover: Cover"},
             indexmap! {"name" => "Column", "kind" => "call", "def_or_ref" => "ref", "first_line" => "@Column(\"int\", {"},
+            indexmap! {"name" => "coverId", "kind" => "property", "def_or_ref" => "def", "first_line" => "coverId: number", "modifiers" => "public"},
             indexmap! {"name" => "ManyToMany", "kind" => "call", "def_or_ref" => "ref", "first_line" => "@ManyToMany((type) => Category, (category) => category.posts, {"},
-            indexmap! {"name" => "JoinTable", "kind" => "call", "def_or_ref" => "ref", "first_line" => "@JoinTable()"}
+            indexmap! {"name" => "JoinTable", "kind" => "call", "def_or_ref" => "ref", "first_line" => "@JoinTable()"},
+            indexmap! {"name" => "categories", "kind" => "property", "def_or_ref" => "def", "first_line" => "categories: Category[]", "modifiers" => "public"}
+        ];
+
+        assert_eq!(tags, expected);
+    }
+
+    #[test]
+    fn it_should_allow_jsx() {
+        let source = read_fixture("UserCard.jsx");
+        let tags = parse_jsx(&source);
+
+        let expected: Vec<IndexMap<&str, &str>> = vec![
+            indexmap! {"name" => "UserCard", "kind" => "component", "def_or_ref" => "def", "first_line" => "function UserCard({ user }) {", "signature" => "({ user })"},
+            indexmap! {"name" => "Avatar", "kind" => "component", "def_or_ref" => "ref", "first_line" => "<Avatar src={user.avatarUrl} />"},
+            indexmap! {"name" => "UserName", "kind" => "component", "def_or_ref" => "ref", "first_line" => "<UserName user={user} />"},
+            indexmap! {"name" => "UserName", "kind" => "component", "def_or_ref" => "def", "first_line" => "const UserName = ({ user }) => <span>{user.name}</span>", "signature" => "({ user })"},
+            indexmap! {"name" => "UserList", "kind" => "component", "def_or_ref" => "def", "first_line" => "export const UserList = memo(({ users }) => ("},
+            indexmap! {"name" => "memo", "kind" => "call", "def_or_ref" => "ref", "first_line" => "export const UserList = memo(({ users }) => ("},
+            indexmap! {"name" => "map", "kind" => "call", "def_or_ref" => "ref", "first_line" => "{users.map((user) => <UserCard key={user.id} user={user} />)}"},
+            indexmap! {"name" => "UserCard", "kind" => "component", "def_or_ref" => "ref", "first_line" => "{users.map((user) => <UserCard key={user.id} user={user} />)}"}
+        ];
+
+        assert_eq!(tags, expected);
+    }
+
+    #[test]
+    fn it_should_refer_capitalized_nested_components_only() {
+        let source = b"const Fade = () => <motion.div><Layout.Page /></motion.div>\n";
+        let tags = parse_jsx(source);
+
+        let expected: Vec<IndexMap<&str, &str>> = vec![
+            indexmap! {"name" => "Fade", "kind" => "component", "def_or_ref" => "def", "first_line" => "const Fade = () => <motion.div><Layout.Page /></motion.div>", "signature" => "()"},
+            indexmap! {"name" => "Page", "kind" => "component", "def_or_ref" => "ref", "first_line" => "const Fade = () => <motion.div><Layout.Page /></motion.div>"}
+        ];
+
+        assert_eq!(tags, expected);
+    }
+
+    #[test]
+    fn it_should_allow_ts_declarations() {
+        let source = read_fixture("Repository.ts");
+        let tags = parse_ts(&source);
+
+        let expected: Vec<IndexMap<&str, &str>> = vec![
+            indexmap! {"name" => "Identifiable", "kind" => "interface", "def_or_ref" => "def", "first_line" => "export interface Identifiable {"},
+            indexmap! {"name" => "id", "kind" => "property", "def_or_ref" => "def", "first_line" => "id: number", "modifiers" => "public"},
+            indexmap! {"name" => "label", "kind" => "method", "def_or_ref" => "def", "first_line" => "label(): string", "modifiers" => "public", "signature" => "(): string"},
+            indexmap! {"name" => "Id", "kind" => "type", "def_or_ref" => "def", "first_line" => "export type Id = number | string"},
+            indexmap! {"name" => "Status", "kind" => "enum", "def_or_ref" => "def", "first_line" => "export enum Status {"},
+            indexmap! {"name" => "Draft", "kind" => "enum_member", "def_or_ref" => "def", "first_line" => "Draft = \"draft\","},
+            indexmap! {"name" => "Published", "kind" => "enum_member", "def_or_ref" => "def", "first_line" => "Published = \"published\","},
+            indexmap! {"name" => "VERSION", "kind" => "ambient", "def_or_ref" => "def", "first_line" => "declare const VERSION: string"},
+            indexmap! {"name" => "log", "kind" => "ambient", "def_or_ref" => "def", "first_line" => "declare function log(message: string): void", "signature" => "(message: string): void"},
+            indexmap! {"name" => "Repository", "kind" => "class", "def_or_ref" => "def", "first_line" => "export abstract class Repository<T extends Identifiable> {", "modifiers" => "abstract"},
+            indexmap! {"name" => "items", "kind" => "property", "def_or_ref" => "def", "first_line" => "protected items: T[] = []", "modifiers" => "protected"},
+            indexmap! {"name" => "validate", "kind" => "abstract_method", "def_or_ref" => "def", "first_line" => "abstract validate(item: T): boolean", "modifiers" => "public abstract", "signature" => "(item: T): boolean"},
+            indexmap! {"name" => "T", "kind" => "type", "def_or_ref" => "ref", "first_line" => "abstract validate(item: T): boolean"},
+            indexmap! {"name" => "find", "kind" => "overload", "def_or_ref" => "def", "first_line" => "find(id: Id): T | undefined", "modifiers" => "public", "signature" => "(id: Id): T | undefined"},
+            indexmap! {"name" => "Id", "kind" => "type", "def_or_ref" => "ref", "first_line" => "find(id: Id): T | undefined"},
+            indexmap! {"name" => "find", "kind" => "overload", "def_or_ref" => "def", "first_line" => "find(ids: Id[]): T[]", "modifiers" => "public", "signature" => "(ids: Id[]): T[]"},
+            indexmap! {"name" => "find", "kind" => "method", "def_or_ref" => "def", "first_line" => "find(input: Id | Id[]) {", "modifiers" => "public", "signature" => "(input: Id | Id[])"},
+            indexmap! {"name" => "isArray", "kind" => "call", "def_or_ref" => "ref", "first_line" => "return Array.isArray(input)"},
+            indexmap! {"name" => "filter", "kind" => "call", "def_or_ref" => "ref", "first_line" => "? this.items.filter((item) => input.includes(item.id))"},
+            indexmap! {"name" => "includes", "kind" => "call", "def_or_ref" => "ref", "first_line" => "? this.items.filter((item) => input.includes(item.id))"},
+            indexmap! {"name" => "find", "kind" => "call", "def_or_ref" => "ref", "first_line" => ": this.items.find((item) => item.id === input)"}
+        ];
+
+        assert_eq!(tags, expected);
+    }
+
+    #[test]
+    fn it_should_allow_tsx() {
+        let source = read_fixture("Profile.tsx");
+        let tags = parse_tsx(&source);
+
+        let expected: Vec<IndexMap<&str, &str>> = vec![
+            indexmap! {"name" => "ProfileProps", "kind" => "interface", "def_or_ref" => "def", "first_line" => "interface ProfileProps {"},
+            indexmap! {"name" => "user", "kind" => "property", "def_or_ref" => "def", "first_line" => "user: User", "modifiers" => "public"},
+            indexmap! {"name" => "User", "kind" => "type", "def_or_ref" => "ref", "first_line" => "user: User"},
+            indexmap! {"name" => "Profile", "kind" => "component", "def_or_ref" => "def", "first_line" => "export function Profile({ user }: ProfileProps) {", "signature" => "({ user }: ProfileProps)"},
+            indexmap! {"name" => "ProfileProps", "kind" => "type", "def_or_ref" => "ref", "first_line" => "export function Profile({ user }: ProfileProps) {"},
+            indexmap! {"name" => "Page", "kind" => "component", "def_or_ref" => "ref", "first_line" => "<Layout.Page title={user.name}>"},
+            indexmap! {"name" => "UserCard", "kind" => "component", "def_or_ref" => "ref", "first_line" => "<UserCard user={user} />"},
+            indexmap! {"name" => "ProfileList", "kind" => "component", "def_or_ref" => "def", "first_line" => "export const ProfileList = ({ users }: { users: User[] }) => (", "signature" => "({ users }: { users: User[] })"},
+            indexmap! {"name" => "map", "kind" => "call", "def_or_ref" => "ref", "first_line" => "{users.map((user) => <Profile key={user.id} user={user} />)}"},
+            indexmap! {"name" => "Profile", "kind" => "component", "def_or_ref" => "ref", "first_line" => "{users.map((user) => <Profile key={user.id} user={user} />)}"}
         ];
 
         assert_eq!(tags, expected);
     }
 
+    #[test]
+    fn it_should_pick_parser_by_extension() {
+        let source = read_fixture("Profile.tsx");
+
+        assert_eq!(parse_file("Profile.tsx", &source), Some(parse_tsx(&source)));
+        assert_eq!(parse_file("Profile.txt", &source), None);
+    }
+
     #[test]
     fn it_should_allow_rb() {
         let source = read_fixture("user.rb");
@@ -146,114 +463,153 @@ mod tests {
 
         let expected: Vec<IndexMap<&str, &str>> = vec![
             indexmap! {"name" => "User", "kind" => "class", "def_or_ref" => "def", "first_line" => "class User < ApplicationRecord"},
-            indexmap! {"name" => "ApplicationRecord", "kind" => "call", "def_or_ref" => "ref", "first_line" => "class User < ApplicationRecord"},
+            indexmap! {"name" => "ApplicationRecord", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "class User < ApplicationRecord"},
             indexmap! {"name" => "has_many", "kind" => "call", "def_or_ref" => "ref", "first_line" => "has_many :microposts, dependent: :destroy"},
+            indexmap! {"name" => "microposts", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :microposts, dependent: :destroy", "synthetic" => "has_many"},
+            indexmap! {"name" => "microposts=", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :microposts, dependent: :destroy", "synthetic" => "has_many"},
+            indexmap! {"name" => "micropost_ids", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :microposts, dependent: :destroy", "synthetic" => "has_many"},
+            indexmap! {"name" => "micropost_ids=", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :microposts, dependent: :destroy", "synthetic" => "has_many"},
             indexmap! {"name" => "has_many", "kind" => "call", "def_or_ref" => "ref", "first_line" => "has_many :active_relationships, class_name:  \"Relationship\","},
+            indexmap! {"name" => "active_relationships", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :active_relationships, class_name:  \"Relationship\",", "synthetic" => "has_many"},
+            indexmap! {"name" => "active_relationships=", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :active_relationships, class_name:  \"Relationship\",", "synthetic" => "has_many"},
+            indexmap! {"name" => "active_relationship_ids", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :active_relationships, class_name:  \"Relationship\",", "synthetic" => "has_many"},
+            indexmap! {"name" => "active_relationship_ids=", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :active_relationships, class_name:  \"Relationship\",", "synthetic" => "has_many"},
             indexmap! {"name" => "has_many", "kind" => "call", "def_or_ref" => "ref", "first_line" => "has_many :passive_relationships, class_name:  \"Relationship\","},
+            indexmap! {"name" => "passive_relationships", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :passive_relationships, class_name:  \"Relationship\",", "synthetic" => "has_many"},
+            indexmap! {"name" => "passive_relationships=", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :passive_relationships, class_name:  \"Relationship\",", "synthetic" => "has_many"},
+            indexmap! {"name" => "passive_relationship_ids", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :passive_relationships, class_name:  \"Relationship\",", "synthetic" => "has_many"},
+            indexmap! {"name" => "passive_relationship_ids=", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :passive_relationships, class_name:  \"Relationship\",", "synthetic" => "has_many"},
             indexmap! {"name" => "has_many", "kind" => "call", "def_or_ref" => "ref", "first_line" => "has_many :following, through: :active_relationships,  source: :followed"},
+            indexmap! {"name" => "following", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :following, through: :active_relationships,  source: :followed", "synthetic" => "has_many"},
+            indexmap! {"name" => "following=", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :following, through: :active_relationships,  source: :followed", "synthetic" => "has_many"},
+            indexmap! {"name" => "following_ids", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :following, through: :active_relationships,  source: :followed", "synthetic" => "has_many"},
+            indexmap! {"name" => "following_ids=", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :following, through: :active_relationships,  source: :followed", "synthetic" => "has_many"},
             indexmap! {"name" => "has_many", "kind" => "call", "def_or_ref" => "ref", "first_line" => "has_many :followers, through: :passive_relationships, source: :follower"},
+            indexmap! {"name" => "followers", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :followers, through: :passive_relationships, source: :follower", "synthetic" => "has_many"},
+            indexmap! {"name" => "followers=", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :followers, through: :passive_relationships, source: :follower", "synthetic" => "has_many"},
+            indexmap! {"name" => "follower_ids", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :followers, through: :passive_relationships, source: :follower", "synthetic" => "has_many"},
+            indexmap! {"name" => "follower_ids=", "kind" => "method", "def_or_ref" => "def", "first_line" => "has_many :followers, through: :passive_relationships, source: :follower", "synthetic" => "has_many"},
             indexmap! {"name" => "attr_accessor", "kind" => "call", "def_or_ref" => "ref", "first_line" => "attr_accessor :remember_token, :activation_token, :reset_token"},
+            indexmap! {"name" => "remember_token", "kind" => "method", "def_or_ref" => "def", "first_line" => "attr_accessor :remember_token, :activation_token, :reset_token", "synthetic" => "attr_accessor"},
+            indexmap! {"name" => "remember_token=", "kind" => "method", "def_or_ref" => "def", "first_line" => "attr_accessor :remember_token, :activation_token, :reset_token", "synthetic" => "attr_accessor"},
+            indexmap! {"name" => "activation_token", "kind" => "method", "def_or_ref" => "def", "first_line" => "attr_accessor :remember_token, :activation_token, :reset_token", "synthetic" => "attr_accessor"},
+            indexmap! {"name" => "activation_token=", "kind" => "method", "def_or_ref" => "def", "first_line" => "attr_accessor :remember_token, :activation_token, :reset_token", "synthetic" => "attr_accessor"},
+            indexmap! {"name" => "reset_token", "kind" => "method", "def_or_ref" => "def", "first_line" => "attr_accessor :remember_token, :activation_token, :reset_token", "synthetic" => "attr_accessor"},
+            indexmap! {"name" => "reset_token=", "kind" => "method", "def_or_ref" => "def", "first_line" => "attr_accessor :remember_token, :activation_token, :reset_token", "synthetic" => "attr_accessor"},
             indexmap! {"name" => "before_save", "kind" => "call", "def_or_ref" => "ref", "first_line" => "before_save   :downcase_email"},
             indexmap! {"name" => "before_create", "kind" => "call", "def_or_ref" => "ref", "first_line" => "before_create :create_activation_digest"},
             indexmap! {"name" => "validates", "kind" => "call", "def_or_ref" => "ref", "first_line" => "validates :name,  presence: true, length: { maximum: 50 }"},
-            indexmap! {"name" => "VALID_EMAIL_REGEX", "kind" => "call", "def_or_ref" => "ref", "first_line" => "VALID_EMAIL_REGEX = /\\A[\\w+\\-.]+@[a-z\\d\\-.]+\\.[a-z]+\\z/i"},
+            indexmap! {"name" => "VALID_EMAIL_REGEX", "kind" => "constant", "def_or_ref" => "def", "first_line" => "VALID_EMAIL_REGEX = /\\A[\\w+\\-.]+@[a-z\\d\\-.]+\\.[a-z]+\\z/i"},
             indexmap! {"name" => "validates", "kind" => "call", "def_or_ref" => "ref", "first_line" => "validates :email, presence: true, length: { maximum: 255 },"},
-            indexmap! {"name" => "VALID_EMAIL_REGEX", "kind" => "call", "def_or_ref" => "ref", "first_line" => "format: { with: VALID_EMAIL_REGEX },"},
+            indexmap! {"name" => "VALID_EMAIL_REGEX", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "format: { with: VALID_EMAIL_REGEX },"},
             indexmap! {"name" => "has_secure_password", "kind" => "call", "def_or_ref" => "ref", "first_line" => "has_secure_password"},
             indexmap! {"name" => "validates", "kind" => "call", "def_or_ref" => "ref", "first_line" => "validates :password, presence: true, length: { minimum: 6 }, allow_nil: true"},
-            indexmap! {"name" => "User", "kind" => "call", "def_or_ref" => "ref", "first_line" => "def User.digest(string)"},
-            indexmap! {"name" => "digest", "kind" => "method", "def_or_ref" => "def", "first_line" => "def User.digest(string)"},
-            indexmap! {"name" => "ActiveModel", "kind" => "call", "def_or_ref" => "ref", "first_line" => "cost = ActiveModel::SecurePassword.min_cost ? BCrypt::Engine::MIN_COST :"},
-            indexmap! {"name" => "SecurePassword", "kind" => "call", "def_or_ref" => "ref", "first_line" => "cost = ActiveModel::SecurePassword.min_cost ? BCrypt::Engine::MIN_COST :"},
+            indexmap! {"name" => "User", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "def User.digest(string)"},
+            indexmap! {"name" => "digest", "kind" => "method", "def_or_ref" => "def", "first_line" => "def User.digest(string)", "modifiers" => "public static", "signature" => "(string)"},
+            indexmap! {"name" => "ActiveModel", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "cost = ActiveModel::SecurePassword.min_cost ? BCrypt::Engine::MIN_COST :"},
+            indexmap! {"name" => "SecurePassword", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "cost = ActiveModel::SecurePassword.min_cost ? BCrypt::Engine::MIN_COST :"},
             indexmap! {"name" => "min_cost", "kind" => "call", "def_or_ref" => "ref", "first_line" => "cost = ActiveModel::SecurePassword.min_cost ? BCrypt::Engine::MIN_COST :"},
-            indexmap! {"name" => "BCrypt", "kind" => "call", "def_or_ref" => "ref", "first_line" => "cost = ActiveModel::SecurePassword.min_cost ? BCrypt::Engine::MIN_COST :"},
-            indexmap! {"name" => "Engine", "kind" => "call", "def_or_ref" => "ref", "first_line" => "cost = ActiveModel::SecurePassword.min_cost ? BCrypt::Engine::MIN_COST :"},
-            indexmap! {"name" => "MIN_COST", "kind" => "call", "def_or_ref" => "ref", "first_line" => "cost = ActiveModel::SecurePassword.min_cost ? BCrypt::Engine::MIN_COST :"},
-            indexmap! {"name" => "BCrypt", "kind" => "call", "def_or_ref" => "ref", "first_line" => "BCrypt::Engine.cost"},
-            indexmap! {"name" => "Engine", "kind" => "call", "def_or_ref" => "ref", "first_line" => "BCrypt::Engine.cost"},
+            indexmap! {"name" => "BCrypt", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "cost = ActiveModel::SecurePassword.min_cost ? BCrypt::Engine::MIN_COST :"},
+            indexmap! {"name" => "Engine", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "cost = ActiveModel::SecurePassword.min_cost ? BCrypt::Engine::MIN_COST :"},
+            indexmap! {"name" => "MIN_COST", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "cost = ActiveModel::SecurePassword.min_cost ? BCrypt::Engine::MIN_COST :"},
+            indexmap! {"name" => "BCrypt", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "BCrypt::Engine.cost"},
+            indexmap! {"name" => "Engine", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "BCrypt::Engine.cost"},
             indexmap! {"name" => "cost", "kind" => "call", "def_or_ref" => "ref", "first_line" => "BCrypt::Engine.cost"},
-            indexmap! {"name" => "BCrypt", "kind" => "call", "def_or_ref" => "ref", "first_line" => "BCrypt::Password.create(string, cost: cost)"},
-            indexmap! {"name" => "Password", "kind" => "call", "def_or_ref" => "ref", "first_line" => "BCrypt::Password.create(string, cost: cost)"},
+            indexmap! {"name" => "BCrypt", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "BCrypt::Password.create(string, cost: cost)"},
+            indexmap! {"name" => "Password", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "BCrypt::Password.create(string, cost: cost)"},
             indexmap! {"name" => "create", "kind" => "call", "def_or_ref" => "ref", "first_line" => "BCrypt::Password.create(string, cost: cost)"},
-            indexmap! {"name" => "User", "kind" => "call", "def_or_ref" => "ref", "first_line" => "def User.new_token"},
-            indexmap! {"name" => "new_token", "kind" => "method", "def_or_ref" => "def", "first_line" => "def User.new_token"},
-            indexmap! {"name" => "SecureRandom", "kind" => "call", "def_or_ref" => "ref", "first_line" => "SecureRandom.urlsafe_base64"},
+            indexmap! {"name" => "string", "kind" => "local", "def_or_ref" => "ref", "first_line" => "BCrypt::Password.create(string, cost: cost)"},
+            indexmap! {"name" => "cost", "kind" => "local", "def_or_ref" => "ref", "first_line" => "BCrypt::Password.create(string, cost: cost)"},
+            indexmap! {"name" => "User", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "def User.new_token"},
+            indexmap! {"name" => "new_token", "kind" => "method", "def_or_ref" => "def", "first_line" => "def User.new_token", "modifiers" => "public static", "signature" => "()"},
+            indexmap! {"name" => "SecureRandom", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "SecureRandom.urlsafe_base64"},
             indexmap! {"name" => "urlsafe_base64", "kind" => "call", "def_or_ref" => "ref", "first_line" => "SecureRandom.urlsafe_base64"},
-            indexmap! {"name" => "remember", "kind" => "method", "def_or_ref" => "def", "first_line" => "def remember"},
-            indexmap! {"name" => "remember_token", "kind" => "call", "def_or_ref" => "ref", "first_line" => "self.remember_token = User.new_token"},
-            indexmap! {"name" => "User", "kind" => "call", "def_or_ref" => "ref", "first_line" => "self.remember_token = User.new_token"},
+            indexmap! {"name" => "remember", "kind" => "method", "def_or_ref" => "def", "first_line" => "def remember", "modifiers" => "public", "signature" => "()"},
+            indexmap! {"name" => "remember_token", "kind" => "attribute_writer", "def_or_ref" => "ref", "first_line" => "self.remember_token = User.new_token"},
+            indexmap! {"name" => "User", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "self.remember_token = User.new_token"},
             indexmap! {"name" => "new_token", "kind" => "call", "def_or_ref" => "ref", "first_line" => "self.remember_token = User.new_token"},
             indexmap! {"name" => "update_attribute", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:remember_digest, User.digest(remember_token))"},
-            indexmap! {"name" => "User", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:remember_digest, User.digest(remember_token))"},
+            indexmap! {"name" => "User", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "update_attribute(:remember_digest, User.digest(remember_token))"},
             indexmap! {"name" => "digest", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:remember_digest, User.digest(remember_token))"},
             indexmap! {"name" => "remember_token", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:remember_digest, User.digest(remember_token))"},
             indexmap! {"name" => "remember_digest", "kind" => "call", "def_or_ref" => "ref", "first_line" => "remember_digest"},
-            indexmap! {"name" => "session_token", "kind" => "method", "def_or_ref" => "def", "first_line" => "def session_token"},
+            indexmap! {"name" => "session_token", "kind" => "method", "def_or_ref" => "def", "first_line" => "def session_token", "modifiers" => "public", "signature" => "()"},
             indexmap! {"name" => "remember_digest", "kind" => "call", "def_or_ref" => "ref", "first_line" => "remember_digest || remember"},
             indexmap! {"name" => "remember", "kind" => "call", "def_or_ref" => "ref", "first_line" => "remember_digest || remember"},
-            indexmap! {"name" => "authenticated?", "kind" => "method", "def_or_ref" => "def", "first_line" => "def authenticated?(attribute, token)"},
+            indexmap! {"name" => "authenticated?", "kind" => "method", "def_or_ref" => "def", "first_line" => "def authenticated?(attribute, token)", "modifiers" => "public", "signature" => "(attribute, token)"},
             indexmap! {"name" => "send", "kind" => "call", "def_or_ref" => "ref", "first_line" => "digest = send(\"#{attribute}_digest\")"},
+            indexmap! {"name" => "attribute", "kind" => "local", "def_or_ref" => "ref", "first_line" => "digest = send(\"#{attribute}_digest\")"},
+            indexmap! {"name" => "digest", "kind" => "local", "def_or_ref" => "ref", "first_line" => "return false if digest.nil?"},
             indexmap! {"name" => "nil?", "kind" => "call", "def_or_ref" => "ref", "first_line" => "return false if digest.nil?"},
-            indexmap! {"name" => "BCrypt", "kind" => "call", "def_or_ref" => "ref", "first_line" => "BCrypt::Password.new(digest).is_password?(token)"},
-            indexmap! {"name" => "Password", "kind" => "call", "def_or_ref" => "ref", "first_line" => "BCrypt::Password.new(digest).is_password?(token)"},
+            indexmap! {"name" => "BCrypt", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "BCrypt::Password.new(digest).is_password?(token)"},
+            indexmap! {"name" => "Password", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "BCrypt::Password.new(digest).is_password?(token)"},
             indexmap! {"name" => "new", "kind" => "call", "def_or_ref" => "ref", "first_line" => "BCrypt::Password.new(digest).is_password?(token)"},
+            indexmap! {"name" => "digest", "kind" => "local", "def_or_ref" => "ref", "first_line" => "BCrypt::Password.new(digest).is_password?(token)"},
             indexmap! {"name" => "is_password?", "kind" => "call", "def_or_ref" => "ref", "first_line" => "BCrypt::Password.new(digest).is_password?(token)"},
-            indexmap! {"name" => "forget", "kind" => "method", "def_or_ref" => "def", "first_line" => "def forget"},
+            indexmap! {"name" => "token", "kind" => "local", "def_or_ref" => "ref", "first_line" => "BCrypt::Password.new(digest).is_password?(token)"},
+            indexmap! {"name" => "forget", "kind" => "method", "def_or_ref" => "def", "first_line" => "def forget", "modifiers" => "public", "signature" => "()"},
             indexmap! {"name" => "update_attribute", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:remember_digest, nil)"},
-            indexmap! {"name" => "activate", "kind" => "method", "def_or_ref" => "def", "first_line" => "def activate"},
+            indexmap! {"name" => "activate", "kind" => "method", "def_or_ref" => "def", "first_line" => "def activate", "modifiers" => "public", "signature" => "()"},
             indexmap! {"name" => "update_attribute", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:activated,    true)"},
             indexmap! {"name" => "update_attribute", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:activated_at, Time.zone.now)"},
-            indexmap! {"name" => "Time", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:activated_at, Time.zone.now)"},
+            indexmap! {"name" => "Time", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "update_attribute(:activated_at, Time.zone.now)"},
             indexmap! {"name" => "zone", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:activated_at, Time.zone.now)"},
             indexmap! {"name" => "now", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:activated_at, Time.zone.now)"},
-            indexmap! {"name" => "send_activation_email", "kind" => "method", "def_or_ref" => "def", "first_line" => "def send_activation_email"},
-            indexmap! {"name" => "UserMailer", "kind" => "call", "def_or_ref" => "ref", "first_line" => "UserMailer.account_activation(self).deliver_now"},
+            indexmap! {"name" => "send_activation_email", "kind" => "method", "def_or_ref" => "def", "first_line" => "def send_activation_email", "modifiers" => "public", "signature" => "()"},
+            indexmap! {"name" => "UserMailer", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "UserMailer.account_activation(self).deliver_now"},
             indexmap! {"name" => "account_activation", "kind" => "call", "def_or_ref" => "ref", "first_line" => "UserMailer.account_activation(self).deliver_now"},
             indexmap! {"name" => "deliver_now", "kind" => "call", "def_or_ref" => "ref", "first_line" => "UserMailer.account_activation(self).deliver_now"},
-            indexmap! {"name" => "create_reset_digest", "kind" => "method", "def_or_ref" => "def", "first_line" => "def create_reset_digest"},
-            indexmap! {"name" => "reset_token", "kind" => "call", "def_or_ref" => "ref", "first_line" => "self.reset_token = User.new_token"},
-            indexmap! {"name" => "User", "kind" => "call", "def_or_ref" => "ref", "first_line" => "self.reset_token = User.new_token"},
+            indexmap! {"name" => "create_reset_digest", "kind" => "method", "def_or_ref" => "def", "first_line" => "def create_reset_digest", "modifiers" => "public", "signature" => "()"},
+            indexmap! {"name" => "reset_token", "kind" => "attribute_writer", "def_or_ref" => "ref", "first_line" => "self.reset_token = User.new_token"},
+            indexmap! {"name" => "User", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "self.reset_token = User.new_token"},
             indexmap! {"name" => "new_token", "kind" => "call", "def_or_ref" => "ref", "first_line" => "self.reset_token = User.new_token"},
             indexmap! {"name" => "update_attribute", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:reset_digest,  User.digest(reset_token))"},
-            indexmap! {"name" => "User", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:reset_digest,  User.digest(reset_token))"},
+            indexmap! {"name" => "User", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "update_attribute(:reset_digest,  User.digest(reset_token))"},
             indexmap! {"name" => "digest", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:reset_digest,  User.digest(reset_token))"},
             indexmap! {"name" => "reset_token", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:reset_digest,  User.digest(reset_token))"},
             indexmap! {"name" => "update_attribute", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:reset_sent_at, Time.zone.now)"},
-            indexmap! {"name" => "Time", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:reset_sent_at, Time.zone.now)"},
+            indexmap! {"name" => "Time", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "update_attribute(:reset_sent_at, Time.zone.now)"},
             indexmap! {"name" => "zone", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:reset_sent_at, Time.zone.now)"},
             indexmap! {"name" => "now", "kind" => "call", "def_or_ref" => "ref", "first_line" => "update_attribute(:reset_sent_at, Time.zone.now)"},
-            indexmap! {"name" => "send_password_reset_email", "kind" => "method", "def_or_ref" => "def", "first_line" => "def send_password_reset_email"},
-            indexmap! {"name" => "UserMailer", "kind" => "call", "def_or_ref" => "ref", "first_line" => "UserMailer.password_reset(self).deliver_now"},
+            indexmap! {"name" => "send_password_reset_email", "kind" => "method", "def_or_ref" => "def", "first_line" => "def send_password_reset_email", "modifiers" => "public", "signature" => "()"},
+            indexmap! {"name" => "UserMailer", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "UserMailer.password_reset(self).deliver_now"},
             indexmap! {"name" => "password_reset", "kind" => "call", "def_or_ref" => "ref", "first_line" => "UserMailer.password_reset(self).deliver_now"},
             indexmap! {"name" => "deliver_now", "kind" => "call", "def_or_ref" => "ref", "first_line" => "UserMailer.password_reset(self).deliver_now"},
-            indexmap! {"name" => "password_reset_expired?", "kind" => "method", "def_or_ref" => "def", "first_line" => "def password_reset_expired?"},
+            indexmap! {"name" => "password_reset_expired?", "kind" => "method", "def_or_ref" => "def", "first_line" => "def password_reset_expired?", "modifiers" => "public", "signature" => "()"},
             indexmap! {"name" => "reset_sent_at", "kind" => "call", "def_or_ref" => "ref", "first_line" => "reset_sent_at < 2.hours.ago"},
             indexmap! {"name" => "hours", "kind" => "call", "def_or_ref" => "ref", "first_line" => "reset_sent_at < 2.hours.ago"},
             indexmap! {"name" => "ago", "kind" => "call", "def_or_ref" => "ref", "first_line" => "reset_sent_at < 2.hours.ago"},
-            indexmap! {"name" => "feed", "kind" => "method", "def_or_ref" => "def", "first_line" => "def feed"},
-            indexmap! {"name" => "Micropost", "kind" => "call", "def_or_ref" => "ref", "first_line" => "Micropost.where(\"user_id IN (#{following_ids})"},
+            indexmap! {"name" => "feed", "kind" => "method", "def_or_ref" => "def", "first_line" => "def feed", "modifiers" => "public", "signature" => "()"},
+            indexmap! {"name" => "Micropost", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "Micropost.where(\"user_id IN (#{following_ids})"},
             indexmap! {"name" => "where", "kind" => "call", "def_or_ref" => "ref", "first_line" => "Micropost.where(\"user_id IN (#{following_ids})"},
+            indexmap! {"name" => "user_id", "kind" => "column", "def_or_ref" => "ref", "first_line" => "Micropost.where(\"user_id IN (#{following_ids})", "sql" => "where"},
+            indexmap! {"name" => "following_ids", "kind" => "local", "def_or_ref" => "ref", "first_line" => "Micropost.where(\"user_id IN (#{following_ids})"},
+            indexmap! {"name" => "following_ids", "kind" => "sql_injection", "def_or_ref" => "ref", "first_line" => "Micropost.where(\"user_id IN (#{following_ids})", "sql" => "where"},
             indexmap! {"name" => "id", "kind" => "call", "def_or_ref" => "ref", "first_line" => "OR user_id = :user_id\", user_id: id)"},
             indexmap! {"name" => "includes", "kind" => "call", "def_or_ref" => "ref", "first_line" => ".includes(:user, image_attachment: :blob)"},
-            indexmap! {"name" => "follow", "kind" => "method", "def_or_ref" => "def", "first_line" => "def follow(other_user)"},
+            indexmap! {"name" => "follow", "kind" => "method", "def_or_ref" => "def", "first_line" => "def follow(other_user)", "modifiers" => "public", "signature" => "(other_user)"},
             indexmap! {"name" => "following", "kind" => "call", "def_or_ref" => "ref", "first_line" => "following << other_user unless self == other_user"},
-            indexmap! {"name" => "unfollow", "kind" => "method", "def_or_ref" => "def", "first_line" => "def unfollow(other_user)"},
+            indexmap! {"name" => "other_user", "kind" => "local", "def_or_ref" => "ref", "first_line" => "following << other_user unless self == other_user"},
+            indexmap! {"name" => "other_user", "kind" => "local", "def_or_ref" => "ref", "first_line" => "following << other_user unless self == other_user"},
+            indexmap! {"name" => "unfollow", "kind" => "method", "def_or_ref" => "def", "first_line" => "def unfollow(other_user)", "modifiers" => "public", "signature" => "(other_user)"},
             indexmap! {"name" => "following", "kind" => "call", "def_or_ref" => "ref", "first_line" => "following.delete(other_user)"},
             indexmap! {"name" => "delete", "kind" => "call", "def_or_ref" => "ref", "first_line" => "following.delete(other_user)"},
-            indexmap! {"name" => "following?", "kind" => "method", "def_or_ref" => "def", "first_line" => "def following?(other_user)"},
+            indexmap! {"name" => "other_user", "kind" => "local", "def_or_ref" => "ref", "first_line" => "following.delete(other_user)"},
+            indexmap! {"name" => "following?", "kind" => "method", "def_or_ref" => "def", "first_line" => "def following?(other_user)", "modifiers" => "public", "signature" => "(other_user)"},
             indexmap! {"name" => "following", "kind" => "call", "def_or_ref" => "ref", "first_line" => "following.include?(other_user)"},
             indexmap! {"name" => "include?", "kind" => "call", "def_or_ref" => "ref", "first_line" => "following.include?(other_user)"},
-            indexmap! {"name" => "private", "kind" => "call", "def_or_ref" => "ref", "first_line" => "private"},
-            indexmap! {"name" => "downcase_email", "kind" => "method", "def_or_ref" => "def", "first_line" => "def downcase_email"},
-            indexmap! {"name" => "email", "kind" => "call", "def_or_ref" => "ref", "first_line" => "self.email = email.downcase"},
+            indexmap! {"name" => "other_user", "kind" => "local", "def_or_ref" => "ref", "first_line" => "following.include?(other_user)"},
+            indexmap! {"name" => "private", "kind" => "visibility", "def_or_ref" => "ref", "first_line" => "private"},
+            indexmap! {"name" => "downcase_email", "kind" => "method", "def_or_ref" => "def", "first_line" => "def downcase_email", "modifiers" => "private", "signature" => "()"},
+            indexmap! {"name" => "email", "kind" => "attribute_writer", "def_or_ref" => "ref", "first_line" => "self.email = email.downcase"},
             indexmap! {"name" => "email", "kind" => "call", "def_or_ref" => "ref", "first_line" => "self.email = email.downcase"},
             indexmap! {"name" => "downcase", "kind" => "call", "def_or_ref" => "ref", "first_line" => "self.email = email.downcase"},
-            indexmap! {"name" => "create_activation_digest", "kind" => "method", "def_or_ref" => "def", "first_line" => "def create_activation_digest"},
-            indexmap! {"name" => "activation_token", "kind" => "call", "def_or_ref" => "ref", "first_line" => "self.activation_token  = User.new_token"},
-            indexmap! {"name" => "User", "kind" => "call", "def_or_ref" => "ref", "first_line" => "self.activation_token  = User.new_token"},
+            indexmap! {"name" => "create_activation_digest", "kind" => "method", "def_or_ref" => "def", "first_line" => "def create_activation_digest", "modifiers" => "private", "signature" => "()"},
+            indexmap! {"name" => "activation_token", "kind" => "attribute_writer", "def_or_ref" => "ref", "first_line" => "self.activation_token  = User.new_token"},
+            indexmap! {"name" => "User", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "self.activation_token  = User.new_token"},
             indexmap! {"name" => "new_token", "kind" => "call", "def_or_ref" => "ref", "first_line" => "self.activation_token  = User.new_token"},
-            indexmap! {"name" => "activation_digest", "kind" => "call", "def_or_ref" => "ref", "first_line" => "self.activation_digest = User.digest(activation_token)"},
-            indexmap! {"name" => "User", "kind" => "call", "def_or_ref" => "ref", "first_line" => "self.activation_digest = User.digest(activation_token)"},
+            indexmap! {"name" => "activation_digest", "kind" => "attribute_writer", "def_or_ref" => "ref", "first_line" => "self.activation_digest = User.digest(activation_token)"},
+            indexmap! {"name" => "User", "kind" => "constant", "def_or_ref" => "ref", "first_line" => "self.activation_digest = User.digest(activation_token)"},
             indexmap! {"name" => "digest", "kind" => "call", "def_or_ref" => "ref", "first_line" => "self.activation_digest = User.digest(activation_token)"},
             indexmap! {"name" => "activation_token", "kind" => "call", "def_or_ref" => "ref", "first_line" => "self.activation_digest = User.digest(activation_token)"}
         ];
@@ -268,9 +624,77 @@ mod tests {
 
         let expected: Vec<IndexMap<&str, &str>> = vec![
             indexmap! {"name" => "User", "kind" => "class", "def_or_ref" => "def", "first_line" => "class User extends Authenticatable"},
-            indexmap! {"name" => "setPasswordAttribute", "kind" => "function", "def_or_ref" => "def", "first_line" => "public function setPasswordAttribute($password)"},
-            indexmap! {"name" => "posts", "kind" => "function", "def_or_ref" => "def", "first_line" => "public function posts()"},
-            indexmap! {"name" => "hasMany", "kind" => "call", "def_or_ref" => "ref", "first_line" => "return $this->hasMany(Post::class);"}
+            indexmap! {"name" => "HasFactory", "kind" => "trait", "def_or_ref" => "ref", "first_line" => "use HasFactory, Notifiable;"},
+            indexmap! {"name" => "Notifiable", "kind" => "trait", "def_or_ref" => "ref", "first_line" => "use HasFactory, Notifiable;"},
+            indexmap! {"name" => "hidden", "kind" => "property", "def_or_ref" => "def", "first_line" => "protected $hidden = [", "modifiers" => "protected"},
+            indexmap! {"name" => "casts", "kind" => "property", "def_or_ref" => "def", "first_line" => "protected $casts = [", "modifiers" => "protected"},
+            indexmap! {"name" => "setPasswordAttribute", "kind" => "function", "def_or_ref" => "def", "first_line" => "public function setPasswordAttribute($password)", "modifiers" => "public", "signature" => "($password)"},
+            indexmap! {"name" => "password", "kind" => "property", "def_or_ref" => "def", "first_line" => "public function setPasswordAttribute($password)", "synthetic" => "mutator"},
+            indexmap! {"name" => "attributes", "kind" => "property", "def_or_ref" => "ref", "first_line" => "$this->attributes['password'] = bcrypt($password);"},
+            indexmap! {"name" => "posts", "kind" => "function", "def_or_ref" => "def", "first_line" => "public function posts()", "modifiers" => "public", "signature" => "()"},
+            indexmap! {"name" => "posts", "kind" => "property", "def_or_ref" => "def", "first_line" => "public function posts()", "synthetic" => "relation"},
+            indexmap! {"name" => "hasMany", "kind" => "call", "def_or_ref" => "ref", "first_line" => "return $this->hasMany(Post::class);"},
+            indexmap! {"name" => "Post", "kind" => "class", "def_or_ref" => "ref", "first_line" => "return $this->hasMany(Post::class);"}
+        ];
+
+        assert_eq!(tags, expected);
+    }
+
+    #[test]
+    fn it_should_allow_php_declarations() {
+        let source = read_fixture("Article.php");
+        let tags = parse_php(&source);
+
+        let expected: Vec<IndexMap<&str, &str>> = vec![
+            indexmap! {"name" => "Publishable", "kind" => "interface", "def_or_ref" => "def", "first_line" => "interface Publishable"},
+            indexmap! {"name" => "DRAFT", "kind" => "constant", "def_or_ref" => "def", "first_line" => "const DRAFT = 'draft';", "modifiers" => "public"},
+            indexmap! {"name" => "publish", "kind" => "function", "def_or_ref" => "def", "first_line" => "public function publish(): void;", "modifiers" => "public", "signature" => "(): void"},
+            indexmap! {"name" => "HasSlug", "kind" => "trait", "def_or_ref" => "def", "first_line" => "trait HasSlug"},
+            indexmap! {"name" => "separator", "kind" => "property", "def_or_ref" => "def", "first_line" => "private static $separator = '-';", "modifiers" => "private static"},
+            indexmap! {"name" => "title", "kind" => "function", "def_or_ref" => "def", "first_line" => "abstract protected function title(): string;", "modifiers" => "protected abstract", "signature" => "(): string"},
+            indexmap! {"name" => "slugify", "kind" => "function", "def_or_ref" => "def", "first_line" => "public static function slugify(string $value): string", "modifiers" => "public static", "signature" => "($value: string): string"},
+            indexmap! {"name" => "Status", "kind" => "enum", "def_or_ref" => "def", "first_line" => "enum Status: string"},
+            indexmap! {"name" => "Draft", "kind" => "enum_member", "def_or_ref" => "def", "first_line" => "case Draft = 'draft';"},
+            indexmap! {"name" => "Published", "kind" => "enum_member", "def_or_ref" => "def", "first_line" => "case Published = 'published';"},
+            indexmap! {"name" => "Article", "kind" => "class", "def_or_ref" => "def", "first_line" => "final class Article implements Publishable", "modifiers" => "final"},
+            indexmap! {"name" => "Publishable", "kind" => "implementation", "def_or_ref" => "ref", "first_line" => "final class Article implements Publishable"},
+            indexmap! {"name" => "HasSlug", "kind" => "trait", "def_or_ref" => "ref", "first_line" => "use HasSlug;"},
+            indexmap! {"name" => "publish", "kind" => "function", "def_or_ref" => "def", "first_line" => "public function publish(): void", "modifiers" => "public", "signature" => "(): void"},
+            indexmap! {"name" => "status", "kind" => "property", "def_or_ref" => "ref", "first_line" => "$this->status = Status::Published;"},
+            indexmap! {"name" => "Status", "kind" => "class", "def_or_ref" => "ref", "first_line" => "$this->status = Status::Published;"},
+            indexmap! {"name" => "Log", "kind" => "class", "def_or_ref" => "ref", "first_line" => "Log::info(static::class);"},
+            indexmap! {"name" => "info", "kind" => "call", "def_or_ref" => "ref", "first_line" => "Log::info(static::class);"}
+        ];
+
+        assert_eq!(tags, expected);
+    }
+
+    #[test]
+    fn it_should_refer_php_properties() {
+        let source = b"<?php\n$user = User::find(1);\necho $user->password;\nforeach ($user->posts as $post) {\n}\n";
+        let tags = parse_php(source);
+
+        let expected: Vec<IndexMap<&str, &str>> = vec![
+            indexmap! {"name" => "User", "kind" => "class", "def_or_ref" => "ref", "first_line" => "$user = User::find(1);"},
+            indexmap! {"name" => "find", "kind" => "call", "def_or_ref" => "ref", "first_line" => "$user = User::find(1);"},
+            indexmap! {"name" => "password", "kind" => "property", "def_or_ref" => "ref", "first_line" => "echo $user->password;"},
+            indexmap! {"name" => "posts", "kind" => "property", "def_or_ref" => "ref", "first_line" => "foreach ($user->posts as $post) {"}
+        ];
+
+        assert_eq!(tags, expected);
+    }
+
+    #[test]
+    fn it_should_mark_php_local_calls() {
+        let source = read_fixture("retry.php");
+        let tags = parse_php(&source);
+
+        let expected: Vec<IndexMap<&str, &str>> = vec![
+            indexmap! {"name" => "retry", "kind" => "function", "def_or_ref" => "def", "first_line" => "function retry(callable $callback, int $attempts = 3)", "signature" => "($callback: callable, $attempts: int = 3)"},
+            indexmap! {"name" => "report", "kind" => "call", "def_or_ref" => "ref", "first_line" => "$report = fn ($error) => $error->report();"},
+            indexmap! {"name" => "$callback", "kind" => "local", "def_or_ref" => "ref", "first_line" => "return $callback($attempt);"},
+            indexmap! {"name" => "$report", "kind" => "local", "def_or_ref" => "ref", "first_line" => "$report($error);"},
+            indexmap! {"name" => "$fallback", "kind" => "call", "def_or_ref" => "ref", "first_line" => "return $fallback();"}
         ];
 
         assert_eq!(tags, expected);
@@ -283,17 +707,70 @@ mod tests {
 
         let expected: Vec<IndexMap<&str, &str>> = vec![
             indexmap! {"name" => "Question", "kind" => "class", "def_or_ref" => "def", "first_line" => "class Question(models.Model):"},
+            indexmap! {"name" => "question_text", "kind" => "field", "def_or_ref" => "def", "first_line" => "question_text = models.CharField(max_length=200)"},
             indexmap! {"name" => "CharField", "kind" => "call", "def_or_ref" => "ref", "first_line" => "question_text = models.CharField(max_length=200)"},
+            indexmap! {"name" => "pub_date", "kind" => "field", "def_or_ref" => "def", "first_line" => "pub_date = models.DateTimeField('date published')"},
             indexmap! {"name" => "DateTimeField", "kind" => "call", "def_or_ref" => "ref", "first_line" => "pub_date = models.DateTimeField('date published')"},
-            indexmap! {"name" => "__str__", "kind" => "function", "def_or_ref" => "def", "first_line" => "def __str__(self):"},
-            indexmap! {"name" => "was_published_recently", "kind" => "function", "def_or_ref" => "def", "first_line" => "def was_published_recently(self):"},
+            indexmap! {"name" => "__str__", "kind" => "method", "def_or_ref" => "def", "first_line" => "def __str__(self):", "modifiers" => "public", "signature" => "()"},
+            indexmap! {"name" => "was_published_recently", "kind" => "method", "def_or_ref" => "def", "first_line" => "def was_published_recently(self):", "modifiers" => "public", "signature" => "()"},
             indexmap! {"name" => "now", "kind" => "call", "def_or_ref" => "ref", "first_line" => "now = timezone.now()"},
             indexmap! {"name" => "timedelta", "kind" => "call", "def_or_ref" => "ref", "first_line" => "return now - datetime.timedelta(days=1) <= self.pub_date <= now"},
             indexmap! {"name" => "Choice", "kind" => "class", "def_or_ref" => "def", "first_line" => "class Choice(models.Model):"},
+            indexmap! {"name" => "question", "kind" => "field", "def_or_ref" => "def", "first_line" => "question = models.ForeignKey(Question, on_delete=models.CASCADE)"},
             indexmap! {"name" => "ForeignKey", "kind" => "call", "def_or_ref" => "ref", "first_line" => "question = models.ForeignKey(Question, on_delete=models.CASCADE)"},
+            indexmap! {"name" => "choice_text", "kind" => "field", "def_or_ref" => "def", "first_line" => "choice_text = models.CharField(max_length=200)"},
             indexmap! {"name" => "CharField", "kind" => "call", "def_or_ref" => "ref", "first_line" => "choice_text = models.CharField(max_length=200)"},
+            indexmap! {"name" => "votes", "kind" => "field", "def_or_ref" => "def", "first_line" => "votes = models.IntegerField(default=0)"},
             indexmap! {"name" => "IntegerField", "kind" => "call", "def_or_ref" => "ref", "first_line" => "votes = models.IntegerField(default=0)"},
-            indexmap! {"name" => "__str__", "kind" => "function", "def_or_ref" => "def", "first_line" => "def __str__(self):"}
+            indexmap! {"name" => "__str__", "kind" => "method", "def_or_ref" => "def", "first_line" => "def __str__(self):", "modifiers" => "public", "signature" => "()"}
+        ];
+
+        assert_eq!(tags, expected);
+    }
+
+    #[test]
+    fn it_should_allow_py_definitions() {
+        let source = read_fixture("admin.py");
+        let tags = parse_py(&source);
+
+        let expected: Vec<IndexMap<&str, &str>> = vec![
+            indexmap! {"name" => "__all__", "kind" => "variable", "def_or_ref" => "def", "first_line" => "__all__ = [\"QuestionAdmin\", \"register_all\"]"},
+            indexmap! {"name" => "QuestionAdmin", "kind" => "export", "def_or_ref" => "ref", "first_line" => "__all__ = [\"QuestionAdmin\", \"register_all\"]"},
+            indexmap! {"name" => "register_all", "kind" => "export", "def_or_ref" => "ref", "first_line" => "__all__ = [\"QuestionAdmin\", \"register_all\"]"},
+            indexmap! {"name" => "MAX_CHOICES", "kind" => "constant", "def_or_ref" => "def", "first_line" => "MAX_CHOICES = 3"},
+            indexmap! {"name" => "default_site", "kind" => "variable", "def_or_ref" => "def", "first_line" => "default_site = admin.site"},
+            indexmap! {"name" => "ChoiceInline", "kind" => "class", "def_or_ref" => "def", "first_line" => "class ChoiceInline(admin.TabularInline):"},
+            indexmap! {"name" => "model", "kind" => "field", "def_or_ref" => "def", "first_line" => "model = Choice"},
+            indexmap! {"name" => "extra", "kind" => "field", "def_or_ref" => "def", "first_line" => "extra = MAX_CHOICES"},
+            indexmap! {"name" => "register", "kind" => "call", "def_or_ref" => "ref", "first_line" => "@admin.register(Question)"},
+            indexmap! {"name" => "QuestionAdmin", "kind" => "class", "def_or_ref" => "def", "first_line" => "class QuestionAdmin(admin.ModelAdmin):", "decorators" => "admin.register"},
+            indexmap! {"name" => "inlines", "kind" => "field", "def_or_ref" => "def", "first_line" => "inlines = [ChoiceInline]"},
+            indexmap! {"name" => "list_display", "kind" => "field", "def_or_ref" => "def", "first_line" => "list_display = (\"question_text\", \"pub_date\", \"was_published_recently\")"},
+            indexmap! {"name" => "display", "kind" => "call", "def_or_ref" => "ref", "first_line" => "@admin.display(boolean=True, description=\"Published recently?\")"},
+            indexmap! {"name" => "was_published_recently", "kind" => "method", "def_or_ref" => "def", "first_line" => "def was_published_recently(self, obj):", "modifiers" => "public", "signature" => "(obj)", "decorators" => "admin.display"},
+            indexmap! {"name" => "was_published_recently", "kind" => "call", "def_or_ref" => "ref", "first_line" => "return obj.was_published_recently()"},
+            indexmap! {"name" => "label", "kind" => "method", "def_or_ref" => "def", "first_line" => "def label(question):", "modifiers" => "public static", "signature" => "(question)", "decorators" => "staticmethod"},
+            indexmap! {"name" => "str", "kind" => "call", "def_or_ref" => "ref", "first_line" => "return str(question)"},
+            indexmap! {"name" => "register_all", "kind" => "function", "def_or_ref" => "def", "first_line" => "def register_all(site=default_site):", "signature" => "(site = default_site)"},
+            indexmap! {"name" => "register", "kind" => "function", "def_or_ref" => "def", "first_line" => "def register(model):", "signature" => "(model)"},
+            indexmap! {"name" => "register", "kind" => "call", "def_or_ref" => "ref", "first_line" => "site.register(model)"},
+            indexmap! {"name" => "register", "kind" => "call", "def_or_ref" => "ref", "first_line" => "register(Choice)"}
+        ];
+
+        assert_eq!(tags, expected);
+    }
+
+    #[test]
+    fn it_should_mark_py_local_calls() {
+        let source = read_fixture("retry.py");
+        let tags = parse_py(&source);
+
+        let expected: Vec<IndexMap<&str, &str>> = vec![
+            indexmap! {"name" => "retry", "kind" => "function", "def_or_ref" => "def", "first_line" => "def retry(func, attempts=3):", "signature" => "(func, attempts = 3)"},
+            indexmap! {"name" => "range", "kind" => "call", "def_or_ref" => "ref", "first_line" => "for attempt in range(attempts):"},
+            indexmap! {"name" => "func", "kind" => "local", "def_or_ref" => "ref", "first_line" => "return func(attempt)"},
+            indexmap! {"name" => "getLogger", "kind" => "call", "def_or_ref" => "ref", "first_line" => "report = logging.getLogger(__name__).error"},
+            indexmap! {"name" => "report", "kind" => "local", "def_or_ref" => "ref", "first_line" => "report(last_error)"}
         ];
 
         assert_eq!(tags, expected);
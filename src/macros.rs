@@ -0,0 +1,292 @@
+use std::ops::Range;
+use indexmap::IndexMap;
+use tree_sitter::{Node, Query, QueryCursor};
+use crate::inflector::singularize;
+use crate::syntax::{parse, text};
+
+// Definition which does not appear in source, but is created by a macro call at runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Synthetic {
+    pub name: String,
+    pub kind: &'static str,
+    // Name of the macro which creates this definition.
+    pub macro_name: String,
+    // Byte range of the macro argument (or the whole call) this definition comes from.
+    pub range: Range<usize>,
+}
+
+// Symbol or string literal passed to a macro call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MacroArgument {
+    pub value: String,
+    pub range: Range<usize>,
+}
+
+// Receiver-less method call in Ruby, like `attr_accessor :name` or `has_many :posts, dependent: :destroy`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MacroCall {
+    pub name: String,
+    pub arguments: Vec<MacroArgument>,
+    // Keyword options having literal values, symbols are stored without their leading colon.
+    pub options: IndexMap<String, String>,
+    pub range: Range<usize>,
+}
+
+impl MacroCall {
+    // Returns synthetic method definition named after given argument.
+    pub fn define(&self, name: String, argument: &MacroArgument) -> Synthetic {
+        Synthetic {
+            name,
+            kind: "method",
+            macro_name: self.name.clone(),
+            range: argument.range.clone(),
+        }
+    }
+}
+
+// Plug-in of the macro expansion, which knows definitions created by some macros.
+pub trait MacroExpander {
+    fn handles(&self, macro_name: &str) -> bool;
+
+    fn expand(&self, call: &MacroCall) -> Vec<Synthetic>;
+}
+
+// `attr_accessor`, `attr_reader` and `attr_writer`.
+pub struct AttributeMacros;
+
+impl MacroExpander for AttributeMacros {
+    fn handles(&self, macro_name: &str) -> bool {
+        matches!(macro_name, "attr_accessor" | "attr_reader" | "attr_writer")
+    }
+
+    fn expand(&self, call: &MacroCall) -> Vec<Synthetic> {
+        let mut synthetics = vec![];
+        for argument in &call.arguments {
+            if call.name != "attr_writer" {
+                synthetics.push(call.define(argument.value.clone(), argument));
+            }
+            if call.name != "attr_reader" {
+                synthetics.push(call.define(format!("{}=", argument.value), argument));
+            }
+        }
+        synthetics
+    }
+}
+
+// `delegate :name, to: :user`, optionally prefixed by `prefix: true` or `prefix: :author`.
+pub struct DelegateMacro;
+
+impl MacroExpander for DelegateMacro {
+    fn handles(&self, macro_name: &str) -> bool {
+        macro_name == "delegate"
+    }
+
+    fn expand(&self, call: &MacroCall) -> Vec<Synthetic> {
+        let prefix = match call.options.get("prefix").map(String::as_str) {
+            None | Some("false") => String::new(),
+            Some("true") => call.options.get("to").map(|to| format!("{}_", to)).unwrap_or_default(),
+            Some(prefix) => format!("{}_", prefix),
+        };
+        call.arguments.iter()
+            .map(|argument| call.define(format!("{}{}", prefix, argument.value), argument))
+            .collect()
+    }
+}
+
+// `define_method(:name)` and `alias_method :new_name, :old_name`.
+pub struct MethodMacros;
+
+impl MacroExpander for MethodMacros {
+    fn handles(&self, macro_name: &str) -> bool {
+        matches!(macro_name, "define_method" | "alias_method")
+    }
+
+    fn expand(&self, call: &MacroCall) -> Vec<Synthetic> {
+        call.arguments.first()
+            .map(|argument| call.define(argument.value.clone(), argument))
+            .into_iter()
+            .collect()
+    }
+}
+
+// ActiveRecord associations and scopes.
+pub struct ActiveRecordMacros;
+
+impl MacroExpander for ActiveRecordMacros {
+    fn handles(&self, macro_name: &str) -> bool {
+        matches!(macro_name, "has_many" | "has_and_belongs_to_many" | "has_one" | "belongs_to" | "scope")
+    }
+
+    fn expand(&self, call: &MacroCall) -> Vec<Synthetic> {
+        let argument = match call.arguments.first() {
+            Some(argument) => argument,
+            None => return vec![],
+        };
+        let name = &argument.value;
+        let names = match call.name.as_str() {
+            "has_many" | "has_and_belongs_to_many" => {
+                let ids = format!("{}_ids", singularize(name));
+                vec![name.clone(), format!("{}=", name), ids.clone(), format!("{}=", ids)]
+            }
+            "has_one" | "belongs_to" => vec![
+                name.clone(),
+                format!("{}=", name),
+                format!("build_{}", name),
+                format!("create_{}", name),
+                format!("create_{}!", name),
+                format!("reload_{}", name),
+            ],
+            _ => vec![name.clone()],
+        };
+        names.into_iter().map(|name| call.define(name, argument)).collect()
+    }
+}
+
+// Default expanders of Ruby macros.
+pub fn ruby_expanders() -> Vec<Box<dyn MacroExpander>> {
+    vec![
+        Box::new(AttributeMacros),
+        Box::new(DelegateMacro),
+        Box::new(MethodMacros),
+        Box::new(ActiveRecordMacros),
+    ]
+}
+
+// Synthesize definitions of all macro calls in Ruby source, which are handled by one of given expanders.
+pub fn expand_ruby(source: &[u8], expanders: &[Box<dyn MacroExpander>]) -> Vec<Synthetic> {
+    let tree = parse(tree_sitter_ruby::language(), source);
+
+    let query = Query::new(
+        tree_sitter_ruby::language(),
+        "(call method: (identifier) arguments: (argument_list)) @call",
+    ).unwrap();
+    let mut cursor = QueryCursor::new();
+
+    let mut synthetics = vec![];
+    for matched in cursor.matches(&query, tree.root_node(), source) {
+        let call = matched.captures[0].node;
+        if call.child_by_field_name("receiver").is_some() {
+            continue;
+        }
+        let name = text(call.child_by_field_name("method").unwrap(), source);
+        let expander = match expanders.iter().find(|expander| expander.handles(name)) {
+            Some(expander) => expander,
+            None => continue,
+        };
+        synthetics.extend(expander.expand(&read_call(name, call, source)));
+    }
+    synthetics
+}
+
+fn read_call(name: &str, call: Node, source: &[u8]) -> MacroCall {
+    let mut macro_call = MacroCall {
+        name: String::from(name),
+        arguments: vec![],
+        options: IndexMap::new(),
+        range: call.byte_range(),
+    };
+
+    let arguments = call.child_by_field_name("arguments").unwrap();
+    let mut cursor = arguments.walk();
+    for argument in arguments.named_children(&mut cursor) {
+        if argument.kind() == "pair" {
+            let key = argument.child_by_field_name("key").and_then(|key| literal(key, source));
+            let value = argument.child_by_field_name("value").and_then(|value| literal(value, source));
+            if let (Some(key), Some(value)) = (key, value) {
+                macro_call.options.insert(key, value);
+            }
+        } else if matches!(argument.kind(), "simple_symbol" | "string") {
+            if let Some(value) = literal(argument, source) {
+                macro_call.arguments.push(MacroArgument { value, range: argument.byte_range() });
+            }
+        }
+    }
+    macro_call
+}
+
+// Value of symbol, string (without interpolation) or other scalar literal.
+fn literal(node: Node, source: &[u8]) -> Option<String> {
+    let value = match node.kind() {
+        "simple_symbol" => text(node, source).trim_start_matches(':'),
+        "hash_key_symbol" | "true" | "false" | "nil" | "integer" | "float" => text(node, source),
+        "string" => match node.named_child_count() {
+            0 => "",
+            1 if node.named_child(0)?.kind() == "string_content" => text(node.named_child(0)?, source),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(String::from(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(synthetics: &[Synthetic]) -> Vec<(&str, &str)> {
+        synthetics.iter().map(|synthetic| (synthetic.name.as_str(), synthetic.macro_name.as_str())).collect()
+    }
+
+    #[test]
+    fn it_should_expand_ruby_macros() {
+        let source = br##"
+class Post < ApplicationRecord
+  belongs_to :author, class_name: "User"
+  delegate :name, :email, to: :author, prefix: true
+  delegate :title, to: :category
+  scope :published, -> { where(published: true) }
+  define_method(:publish!) { update(published: true) }
+  define_method("#{state}?") { true }
+  alias_method :headline, :title
+  attr_writer "draft"
+  Post.attr_reader :ignored
+end
+"##;
+        let synthetics = expand_ruby(source, &ruby_expanders());
+
+        assert_eq!(names(&synthetics), vec![
+            ("author", "belongs_to"),
+            ("author=", "belongs_to"),
+            ("build_author", "belongs_to"),
+            ("create_author", "belongs_to"),
+            ("create_author!", "belongs_to"),
+            ("reload_author", "belongs_to"),
+            ("author_name", "delegate"),
+            ("author_email", "delegate"),
+            ("title", "delegate"),
+            ("published", "scope"),
+            ("publish!", "define_method"),
+            ("headline", "alias_method"),
+            ("draft=", "attr_writer"),
+        ]);
+    }
+
+    #[test]
+    fn it_should_allow_custom_expanders() {
+        struct SecureToken;
+
+        impl MacroExpander for SecureToken {
+            fn handles(&self, macro_name: &str) -> bool {
+                macro_name == "has_secure_token"
+            }
+
+            fn expand(&self, call: &MacroCall) -> Vec<Synthetic> {
+                call.arguments.iter()
+                    .flat_map(|argument| vec![
+                        call.define(argument.value.clone(), argument),
+                        call.define(format!("regenerate_{}", argument.value), argument),
+                    ])
+                    .collect()
+            }
+        }
+
+        let source = b"class User\n  has_secure_token :auth_token\nend\n";
+        let synthetics = expand_ruby(source, &[Box::new(SecureToken)]);
+
+        assert_eq!(names(&synthetics), vec![
+            ("auth_token", "has_secure_token"),
+            ("regenerate_auth_token", "has_secure_token"),
+        ]);
+        assert_eq!(synthetics[0].range, 30..41);
+    }
+}
@@ -0,0 +1,121 @@
+use std::ops::Range;
+use std::path::Path;
+use crate::ddl::Table;
+use crate::schema::{self, Change};
+
+// Migrations of ORMs, replayed in order into the schema which they make, to compare it with models by `drift`.
+
+pub mod activerecord;
+pub mod django;
+pub mod eloquent;
+pub mod typeorm;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Migration {
+    // Class name, or the file name for the migrations named by files, like `CreateUsers` or `0001_initial`.
+    pub name: String,
+    // Version which migrations run in the order of, like `20230501120000` for `20230501120000_create_users.rb`.
+    pub version: String,
+    pub up: Vec<Change>,
+    // Changes reverting the migration, which are missing for the ones reverted by inverting `up`, like Rails `change` methods.
+    pub down: Vec<Change>,
+    pub file: String,
+    // Byte range of the class.
+    pub range: Range<usize>,
+}
+
+impl Migration {
+    fn new(name: &str, range: Range<usize>) -> Self {
+        Migration { name: String::from(name), version: String::new(), up: vec![], down: vec![], file: String::new(), range }
+    }
+}
+
+// Migrations declared in supplied source, choosing the ORM by the extension of file name.
+pub fn migrations(file_name: &str, source: &[u8]) -> Option<Vec<Migration>> {
+    let path = Path::new(file_name);
+    let extension = path.extension()?.to_str()?;
+    let migrations = match extension {
+        "js" | "mjs" | "cjs" => typeorm::migrations(tree_sitter_javascript::language(), source),
+        "ts" | "mts" | "cts" => typeorm::migrations(tree_sitter_typescript::language_typescript(), source),
+        "rb" => activerecord::migrations(source),
+        "php" => eloquent::migrations(source),
+        "py" => django::migrations(app_label(path).as_deref(), source),
+        _ => return None,
+    };
+    let stem = path.file_stem()?.to_str()?;
+    Some(migrations.into_iter()
+        .map(|migration| {
+            let name = if migration.name.is_empty() { String::from(stem) } else { migration.name };
+            Migration { version: version(stem, &name), name, file: String::from(file_name), ..migration }
+        })
+        .collect())
+}
+
+// Schema made by running the migrations in order of their versions.
+pub fn replay(migrations: &[Migration]) -> Vec<Table> {
+    let mut migrations: Vec<&Migration> = migrations.iter().collect();
+    migrations.sort_by(|migration, other| migration.version.cmp(&other.version));
+    schema::replay(migrations.into_iter().flat_map(|migration| migration.up.iter().cloned()))
+}
+
+// Digits prefixing the file name, like `2014_10_12_000000_create_users_table` or `0001_initial`,
+// or suffixing the class name, like `CreateUsers1680000000000` of TypeORM.
+fn version(stem: &str, name: &str) -> String {
+    let prefix: String = stem.chars().take_while(|char| char.is_ascii_digit() || *char == '_').filter(char::is_ascii_digit).collect();
+    if !prefix.is_empty() {
+        return prefix;
+    }
+    let suffix: Vec<char> = name.chars().rev().take_while(char::is_ascii_digit).collect();
+    suffix.into_iter().rev().collect()
+}
+
+// Label of Django app, named after the package containing `migrations/`, like `polls` for `polls/migrations/0001_initial.py`.
+fn app_label(path: &Path) -> Option<String> {
+    let directory = path.parent()?;
+    if directory.file_name()? != "migrations" {
+        return None;
+    }
+    directory.parent()?.file_name()?.to_str().map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_fixture;
+    use crate::drift::{check, Drift};
+    use crate::models::models;
+
+    #[test]
+    fn it_should_replay_migrations_in_order() {
+        let files: [(&str, &[u8]); 2] = [
+            ("db/migrate/20230502090000_add_name_to_users.rb", b"class AddNameToUsers < ActiveRecord::Migration[7.0]\n  def change\n    add_column :users, :name, :string\n    rename_column :users, :mail, :email\n  end\nend\n"),
+            ("db/migrate/20230501120000_create_users.rb", b"class CreateUsers < ActiveRecord::Migration[7.0]\n  def change\n    create_table :users do |t|\n      t.string :mail\n    end\n  end\nend\n"),
+        ];
+        let migrations: Vec<Migration> = files.iter().flat_map(|(file_name, source)| migrations(file_name, source).unwrap()).collect();
+        assert_eq!(migrations[0].version, "20230502090000");
+        assert_eq!(migrations[1].name, "CreateUsers");
+
+        let tables = replay(&migrations);
+        let columns: Vec<&str> = tables[0].columns.iter().map(|column| column.name.as_str()).collect();
+        assert_eq!(columns, vec!["id", "email", "name"]);
+    }
+
+    #[test]
+    fn it_should_compare_migrations_with_models() {
+        let source = read_fixture("0001_initial.py");
+        let mut migrations = migrations("polls/migrations/0001_initial.py", &source).unwrap();
+        assert_eq!((migrations[0].name.as_str(), migrations[0].version.as_str()), ("0001_initial", "0001"));
+        let source = b"from django.db import migrations, models
+
+class Migration(migrations.Migration):
+    dependencies = [('polls', '0001_initial')]
+    operations = [
+        migrations.AddField(model_name='choice', name='created_at', field=models.DateTimeField(null=True)),
+    ]
+";
+        migrations.extend(super::migrations("polls/migrations/0002_choice_created_at.py", source).unwrap());
+        let models = models("polls/models.py", &read_fixture("models.py")).unwrap();
+        let drifts: Vec<Drift> = check(&models, &replay(&migrations)).into_iter().map(|finding| finding.drift).collect();
+        assert_eq!(drifts, vec![Drift::ExtraColumn { table: String::from("polls_choice"), column: String::from("created_at") }]);
+    }
+}
@@ -0,0 +1,329 @@
+use indexmap::IndexMap;
+use tree_sitter::Node;
+use crate::ddl::{Column, ForeignKey, Table};
+use crate::inflector::{pluralize, singularize};
+use crate::models::types::data_type;
+use crate::models::{values, DataType, Orm, Value};
+use crate::schema::{self, Change};
+use crate::syntax::{children, parse, text, visit};
+use super::Migration;
+
+// ActiveRecord migrations, which inherit `ActiveRecord::Migration[7.0]` and change the schema by `def change`,
+// or by `def up` and `def down`. `db/schema.rb` is written in the same language.
+
+pub fn migrations(source: &[u8]) -> Vec<Migration> {
+    let tree = parse(tree_sitter_ruby::language(), source);
+    let mut migrations = vec![];
+    visit(tree.root_node(), &mut |node| {
+        if node.kind() != "class" {
+            return;
+        }
+        let superclass = node.child_by_field_name("superclass").and_then(|superclass| superclass.named_child(0));
+        let (name, superclass) = match (node.child_by_field_name("name"), superclass) {
+            (Some(name), Some(superclass)) => (name, superclass),
+            _ => return,
+        };
+        if !text(superclass, source).starts_with("ActiveRecord::Migration") {
+            return;
+        }
+
+        let mut migration = Migration::new(text(name, source), node.byte_range());
+        for method in children(node).into_iter().filter(|statement| statement.kind() == "method") {
+            match method.child_by_field_name("name").map_or("", |name| text(name, source)) {
+                "change" | "up" => migration.up.extend(changes(method, source)),
+                "down" => migration.down.extend(changes(method, source)),
+                _ => {}
+            }
+        }
+        migrations.push(migration);
+    });
+    migrations
+}
+
+// Changes made by the statements of node, like `add_column :users, :name, :string`.
+pub(crate) fn changes(node: Node, source: &[u8]) -> Vec<Change> {
+    let mut changes = vec![];
+    visit(node, &mut |node| {
+        if node.kind() != "call" || node.child_by_field_name("receiver").is_some() {
+            return;
+        }
+        let (arguments, options) = arguments(node, source);
+        let names: Vec<&str> = arguments.iter().filter_map(Value::as_str).collect();
+        let option = |key: &str| options.get(key).and_then(Value::as_str).map(String::from);
+
+        match (node.child_by_field_name("method").map_or("", |method| text(method, source)), names.as_slice()) {
+            ("create_table", [name, ..]) => changes.push(Change::CreateTable(create_table(name, &options, node, source))),
+            // Tables joining others, like `roles_users` for `create_join_table :users, :roles`.
+            ("create_join_table", [first, second, ..]) => {
+                let mut names = [*first, *second];
+                names.sort();
+                let mut table = Table::new(&option("table_name").unwrap_or_else(|| names.join("_")));
+                for name in names {
+                    table.columns.push(Column::new(&format!("{}_id", singularize(name)), DataType::BigInteger, false));
+                }
+                changes.push(Change::CreateTable(with_block(table, node, source)));
+            }
+            ("change_table", [table, ..]) => {
+                for call in block_calls(node, source) {
+                    changes.extend(column_changes(table, call, source));
+                }
+            }
+            ("add_column", [table, name, declared, ..]) => changes.push(Change::AddColumn { table: table.to_string(), column: column(name, declared, &options) }),
+            ("change_column", [table, name, declared, ..]) => changes.push(Change::ChangeColumn { table: table.to_string(), column: column(name, declared, &options) }),
+            // `remove_column :users, :age, :integer` names the type of column to revert it.
+            ("remove_column", [table, name, ..]) => changes.push(Change::RemoveColumn { table: table.to_string(), column: name.to_string() }),
+            ("remove_columns", [table, names @ ..]) => {
+                changes.extend(names.iter().map(|name| Change::RemoveColumn { table: table.to_string(), column: name.to_string() }));
+            }
+            ("rename_column", [table, from, to, ..]) => changes.push(Change::RenameColumn { table: table.to_string(), from: from.to_string(), to: to.to_string() }),
+            ("drop_table", names) => changes.extend(names.iter().map(|name| Change::DropTable(name.to_string()))),
+            ("rename_table", [from, to, ..]) => changes.push(Change::RenameTable { from: from.to_string(), to: to.to_string() }),
+            ("add_reference" | "add_belongs_to", [table, name, ..]) => changes.extend(references(table, name, &options)),
+            ("remove_reference" | "remove_belongs_to", [table, name, ..]) => changes.extend(remove_references(table, name, &options)),
+            ("add_timestamps", [table, ..]) => changes.extend(timestamps(table, &options)),
+            ("remove_timestamps", [table, ..]) => changes.extend(remove_timestamps(table)),
+            ("add_foreign_key", [from, to, ..]) => changes.push(add_foreign_key(from, to, &options)),
+            ("remove_foreign_key", [from, rest @ ..]) => changes.extend(remove_foreign_key(from, rest.first().copied(), &options)),
+            // SQL statements, like `execute "ALTER TABLE users ADD CONSTRAINT ..."`.
+            ("execute", [sql, ..]) => changes.extend(schema::sql_changes(sql).unwrap_or_default()),
+            _ => {}
+        }
+    });
+    changes
+}
+
+// Positional arguments and options of call.
+fn arguments(call: Node, source: &[u8]) -> (Vec<Value>, IndexMap<String, Value>) {
+    let arguments = call.child_by_field_name("arguments").map(children).unwrap_or_default();
+    let names = arguments.iter()
+        .filter(|argument| argument.is_named() && !matches!(argument.kind(), "pair" | "comment"))
+        .map(|argument| values::ruby(*argument, source))
+        .collect();
+    let options = arguments.iter()
+        .filter(|argument| argument.kind() == "pair")
+        .filter_map(|pair| Some((values::key(pair.child_by_field_name("key")?, source), values::ruby(pair.child_by_field_name("value")?, source))))
+        .collect();
+    (names, options)
+}
+
+fn create_table(name: &str, options: &IndexMap<String, Value>, call: Node, source: &[u8]) -> Table {
+    let mut table = Table::new(name);
+    // Tables have `id` keys of `bigint` unless `id: false`, `id: :uuid` or `primary_key: "uid"` are given.
+    match (options.get("id"), options.get("primary_key")) {
+        (_, Some(Value::Array(keys))) => table.primary_key = keys.iter().filter_map(Value::as_str).map(String::from).collect(),
+        (Some(Value::Boolean(false)), _) => {}
+        (id, key) => {
+            let key = key.and_then(Value::as_str).unwrap_or("id");
+            let data_type = id.and_then(Value::as_str).map_or(DataType::BigInteger, |id| data_type(Orm::ActiveRecord, id));
+            table.columns.push(Column::new(key, data_type, false));
+            table.primary_key.push(String::from(key));
+        }
+    }
+    with_block(table, call, source)
+}
+
+// Table with the columns defined in the block of call, like `t.string "email"` in `create_table "users" do |t| ... end`.
+fn with_block(table: Table, call: Node, source: &[u8]) -> Table {
+    let name = table.name.clone();
+    let mut tables = vec![table];
+    for call in block_calls(call, source) {
+        for change in column_changes(&name, call, source) {
+            schema::apply(&mut tables, change);
+        }
+    }
+    tables.remove(0)
+}
+
+// Calls on the parameter of the block of call, like `t.string "email"`.
+fn block_calls<'a>(call: Node<'a>, source: &[u8]) -> Vec<Node<'a>> {
+    let block = match call.child_by_field_name("block") {
+        Some(block) => block,
+        None => return vec![],
+    };
+    let parameter = block.child_by_field_name("parameters").and_then(|parameters| parameters.named_child(0)).map(|parameter| text(parameter, source));
+    let mut calls = vec![];
+    visit(block, &mut |node| {
+        let receiver = node.child_by_field_name("receiver").map(|receiver| text(receiver, source));
+        if node.kind() == "call" && receiver.is_some() && receiver == parameter {
+            calls.push(node);
+        }
+    });
+    calls
+}
+
+// Changes made by a call in the block of `create_table` or `change_table`, like `t.string "email", limit: 100, null: false`.
+fn column_changes(table: &str, call: Node, source: &[u8]) -> Vec<Change> {
+    let method = call.child_by_field_name("method").map_or("", |method| text(method, source));
+    let (arguments, options) = arguments(call, source);
+    let names: Vec<&str> = arguments.iter().filter_map(Value::as_str).collect();
+    let add = |column: Column| Change::AddColumn { table: String::from(table), column };
+
+    match (method, names.as_slice()) {
+        ("index" | "check_constraint" | "remove_index", _) => vec![],
+        ("timestamps", _) => timestamps(table, &options),
+        ("remove_timestamps", _) => remove_timestamps(table),
+        ("references" | "belongs_to", names) => names.iter().flat_map(|name| references(table, name, &options)).collect(),
+        ("remove_references" | "remove_belongs_to", names) => names.iter().flat_map(|name| remove_references(table, name, &options)).collect(),
+        ("remove", names) => names.iter().map(|name| Change::RemoveColumn { table: String::from(table), column: name.to_string() }).collect(),
+        ("rename", [from, to, ..]) => vec![Change::RenameColumn { table: String::from(table), from: from.to_string(), to: to.to_string() }],
+        ("change", [name, declared, ..]) => vec![Change::ChangeColumn { table: String::from(table), column: column(name, declared, &options) }],
+        // `t.column "name", :string`
+        ("column", [name, declared, ..]) => vec![add(column(name, declared, &options))],
+        ("primary_key", [name, rest @ ..]) => {
+            let declared = rest.first().copied().unwrap_or("bigint");
+            vec![add(column(name, declared, &options)), Change::AddPrimaryKey { table: String::from(table), columns: vec![name.to_string()] }]
+        }
+        ("foreign_key", [to, ..]) => vec![add_foreign_key(table, to, &options)],
+        ("remove_foreign_key", names) => remove_foreign_key(table, names.first().copied(), &options).into_iter().collect(),
+        // Columns of their types, like `t.string "email"`, while other calls don't change columns.
+        (declared, names) if data_type(Orm::ActiveRecord, declared) != DataType::Unknown => {
+            names.iter().map(|name| add(column(name, declared, &options))).collect()
+        }
+        _ => vec![],
+    }
+}
+
+fn column(name: &str, declared: &str, options: &IndexMap<String, Value>) -> Column {
+    let mut column = Column::new(name, data_type(Orm::ActiveRecord, declared), options.get("null") != Some(&Value::Boolean(false)));
+    column.length = options.get("limit").and_then(Value::as_length);
+    column.default = options.get("default").cloned();
+    column
+}
+
+// Key of reference, like `user_id` for `t.references "user", foreign_key: true`, and its type for polymorphic ones.
+fn references(table: &str, name: &str, options: &IndexMap<String, Value>) -> Vec<Change> {
+    let option = |key: &str| options.get(key);
+    let nullable = option("null") != Some(&Value::Boolean(false));
+    let data_type = option("type").and_then(Value::as_str).map_or(DataType::BigInteger, |id| data_type(Orm::ActiveRecord, id));
+    let add = |column: Column| Change::AddColumn { table: String::from(table), column };
+
+    let mut changes = vec![add(Column::new(&format!("{}_id", name), data_type, nullable))];
+    if option("polymorphic").is_some_and(Value::is_true) {
+        changes.push(add(Column::new(&format!("{}_type", name), DataType::String, nullable)));
+    }
+    let to_table = match option("foreign_key") {
+        Some(Value::Boolean(true)) => Some(pluralize(name)),
+        Some(foreign_key) => foreign_key.get("to_table").and_then(Value::as_str).map(String::from),
+        None => None,
+    };
+    if let Some(to_table) = to_table {
+        let foreign_key = ForeignKey { column: format!("{}_id", name), table: to_table, references: String::from("id") };
+        changes.push(Change::AddForeignKey { table: String::from(table), foreign_key });
+    }
+    changes
+}
+
+// Foreign key to table, like `add_foreign_key :articles, :authors` on `author_id` unless `column:` is given.
+fn add_foreign_key(table: &str, to: &str, options: &IndexMap<String, Value>) -> Change {
+    let option = |key: &str| options.get(key).and_then(Value::as_str).map(String::from);
+    Change::AddForeignKey {
+        table: String::from(table),
+        foreign_key: ForeignKey {
+            column: option("column").unwrap_or_else(|| format!("{}_id", singularize(to))),
+            table: String::from(to),
+            references: option("primary_key").unwrap_or_else(|| String::from("id")),
+        },
+    }
+}
+
+fn remove_foreign_key(table: &str, to: Option<&str>, options: &IndexMap<String, Value>) -> Option<Change> {
+    let column = options.get("column").and_then(Value::as_str).map(String::from)
+        .or_else(|| to.map(|to| format!("{}_id", singularize(to))));
+    column.map(|column| Change::RemoveForeignKey { table: String::from(table), column })
+}
+
+fn remove_references(table: &str, name: &str, options: &IndexMap<String, Value>) -> Vec<Change> {
+    let mut columns = vec![format!("{}_id", name)];
+    if options.get("polymorphic").is_some_and(Value::is_true) {
+        columns.push(format!("{}_type", name));
+    }
+    columns.into_iter().map(|column| Change::RemoveColumn { table: String::from(table), column }).collect()
+}
+
+// `created_at` and `updated_at`, which are not null since Rails 5.
+fn timestamps(table: &str, options: &IndexMap<String, Value>) -> Vec<Change> {
+    let nullable = options.get("null").is_some_and(Value::is_true);
+    ["created_at", "updated_at"].into_iter()
+        .map(|name| Change::AddColumn { table: String::from(table), column: Column::new(name, DataType::DateTime, nullable) })
+        .collect()
+}
+
+fn remove_timestamps(table: &str) -> Vec<Change> {
+    ["created_at", "updated_at"].into_iter().map(|column| Change::RemoveColumn { table: String::from(table), column: String::from(column) }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_read_activerecord_migrations() {
+        let source = b"class ChangeMicroposts < ActiveRecord::Migration[7.0]
+  def up
+    change_table :microposts do |t|
+      t.remove :picture
+      t.rename :body, :content
+      t.references :author, foreign_key: { to_table: :users }
+    end
+    create_join_table :microposts, :tags
+    execute \"ALTER TABLE microposts ADD CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id)\"
+  end
+
+  def down
+    drop_table :microposts_tags
+  end
+end
+";
+        let migrations = migrations(source);
+
+        assert_eq!(migrations.len(), 1);
+        assert_eq!(migrations[0].name, "ChangeMicroposts");
+        let table = |name: &str| String::from(name);
+        assert_eq!(migrations[0].up[..3], [
+            Change::RemoveColumn { table: table("microposts"), column: String::from("picture") },
+            Change::RenameColumn { table: table("microposts"), from: String::from("body"), to: String::from("content") },
+            Change::AddColumn { table: table("microposts"), column: Column::new("author_id", DataType::BigInteger, true) },
+        ]);
+        assert_eq!(migrations[0].up[3], Change::AddForeignKey {
+            table: table("microposts"),
+            foreign_key: ForeignKey { column: String::from("author_id"), table: table("users"), references: String::from("id") },
+        });
+        match &migrations[0].up[4] {
+            Change::CreateTable(join_table) => {
+                assert_eq!(join_table.name, "microposts_tags");
+                assert_eq!(join_table.columns.iter().map(|column| column.name.as_str()).collect::<Vec<_>>(), vec!["micropost_id", "tag_id"]);
+            }
+            change => panic!("unexpected change {:?}", change),
+        }
+        assert_eq!(migrations[0].up[5], Change::AddForeignKey {
+            table: table("microposts"),
+            foreign_key: ForeignKey { column: String::from("user_id"), table: table("users"), references: String::from("id") },
+        });
+        assert_eq!(migrations[0].down, vec![Change::DropTable(table("microposts_tags"))]);
+    }
+
+    #[test]
+    fn it_should_change_foreign_keys_of_tables() {
+        let source = b"class LinkArticles < ActiveRecord::Migration[7.0]
+  def change
+    change_table :articles do |t|
+      t.foreign_key :users
+      t.remove_foreign_key column: :editor_id
+      t.string :slug
+    end
+  end
+end
+";
+        let migrations = migrations(source);
+
+        let table = |name: &str| String::from(name);
+        assert_eq!(migrations[0].up, vec![
+            Change::AddForeignKey {
+                table: table("articles"),
+                foreign_key: ForeignKey { column: String::from("user_id"), table: table("users"), references: String::from("id") },
+            },
+            Change::RemoveForeignKey { table: table("articles"), column: String::from("editor_id") },
+            Change::AddColumn { table: table("articles"), column: Column::new("slug", DataType::String, true) },
+        ]);
+    }
+}
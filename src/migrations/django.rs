@@ -0,0 +1,260 @@
+use indexmap::IndexMap;
+use tree_sitter::Node;
+use crate::ddl::{Column, ForeignKey, Table};
+use crate::hierarchy::simple_name;
+use crate::models::types::data_type;
+use crate::models::{values, DataType, Orm, Value};
+use crate::schema::{self, Change};
+use crate::syntax::{children, parse, text, visit};
+use super::Migration;
+
+// Django migrations, which are `Migration` classes listing the operations changing the schema.
+// They are named after their files, like `0001_initial`, and their tables after the label of their app, like the ones of models.
+
+pub fn migrations(app_label: Option<&str>, source: &[u8]) -> Vec<Migration> {
+    let tree = parse(tree_sitter_python::language(), source);
+    let mut migrations = vec![];
+    visit(tree.root_node(), &mut |node| {
+        if node.kind() != "class_definition" {
+            return;
+        }
+        let bases = node.child_by_field_name("superclasses").map(children).unwrap_or_default();
+        let is_migration = bases.into_iter().rfind(|base| base.is_named()).is_some_and(|base| simple_name(text(base, source)) == "Migration");
+        let body = match node.child_by_field_name("body") {
+            Some(body) if is_migration => body,
+            _ => return,
+        };
+
+        let mut migration = Migration::new("", node.byte_range());
+        let mut app = App { label: app_label, tables: IndexMap::new() };
+        for assignment in children(body).into_iter().filter_map(|statement| statement.named_child(0)) {
+            let (left, right) = match (assignment.child_by_field_name("left"), assignment.child_by_field_name("right")) {
+                (Some(left), Some(right)) if assignment.kind() == "assignment" && right.kind() == "list" => (left, right),
+                _ => continue,
+            };
+            if text(left, source) == "operations" {
+                for operation in children(right).into_iter().filter(|operation| operation.kind() == "call") {
+                    migration.up.extend(app.changes(operation, source).unwrap_or_default());
+                }
+            }
+        }
+        migrations.push(migration);
+    });
+    migrations
+}
+
+// App which migrations belong to, with the tables declared by `options={'db_table': 'questions'}`.
+struct App<'a> {
+    label: Option<&'a str>,
+    tables: IndexMap<String, String>,
+}
+
+impl App<'_> {
+    // Table of model, like `polls_question` for `Question` or `'polls.question'`.
+    fn table(&self, model: &str) -> String {
+        let (label, model) = match model.split_once('.') {
+            Some((label, model)) => (Some(label), model.to_lowercase()),
+            None => (self.label, model.to_lowercase()),
+        };
+        if let Some(table) = self.tables.get(&model).filter(|_| label == self.label) {
+            return table.clone();
+        }
+        match label {
+            Some(label) => format!("{}_{}", label, model),
+            None => model,
+        }
+    }
+
+    // Changes made by operation, like `migrations.AddField(model_name='choice', name='votes', field=models.IntegerField(default=0))`.
+    fn changes(&mut self, operation: Node, source: &[u8]) -> Option<Vec<Change>> {
+        let function = operation.child_by_field_name("function").map_or("", |function| text(function, source));
+        let string = |parameter: Option<Node>| parameter.and_then(|parameter| values::python(parameter, source).as_str().map(String::from));
+
+        let changes = match simple_name(function) {
+            "CreateModel" => {
+                let [name, fields, options] = parameters(operation, ["name", "fields", "options"], source);
+                let name = string(name)?;
+                if let Some(db_table) = options.map(|options| values::python(options, source)).and_then(|options| options.get("db_table").and_then(Value::as_str).map(String::from)) {
+                    self.tables.insert(name.to_lowercase(), db_table);
+                }
+                let table = self.table(&name);
+                let mut tables = vec![Table::new(&table)];
+                // Fields are listed as pairs, like `('question_text', models.CharField(max_length=200))`.
+                for field in fields.map(children).unwrap_or_default().into_iter().filter(|field| field.kind() == "tuple") {
+                    let pair: Vec<Node> = children(field).into_iter().filter(|node| node.is_named()).collect();
+                    if let [field_name, field] = pair.as_slice() {
+                        for change in self.field_changes(&table, &name, &string(Some(*field_name))?, *field, source) {
+                            schema::apply(&mut tables, change);
+                        }
+                    }
+                }
+                tables.into_iter().map(Change::CreateTable).collect()
+            }
+            "DeleteModel" => vec![Change::DropTable(self.table(&string(parameters(operation, ["name"], source)[0])?))],
+            "RenameModel" => {
+                let [from, to] = parameters(operation, ["old_name", "new_name"], source);
+                vec![Change::RenameTable { from: self.table(&string(from)?), to: self.table(&string(to)?) }]
+            }
+            "AlterModelTable" => {
+                let [name, table] = parameters(operation, ["name", "table"], source);
+                let (name, table) = (string(name)?, string(table)?);
+                let from = self.table(&name);
+                self.tables.insert(name.to_lowercase(), table.clone());
+                vec![Change::RenameTable { from, to: table }]
+            }
+            "AddField" | "AlterField" => {
+                let [model, name, field] = parameters(operation, ["model_name", "name", "field"], source);
+                let model = string(model)?;
+                let changes = self.field_changes(&self.table(&model), &model, &string(name)?, field?, source);
+                if simple_name(function) == "AddField" {
+                    changes
+                } else {
+                    changes.into_iter()
+                        .map(|change| match change {
+                            Change::AddColumn { table, column } => Change::ChangeColumn { table, column },
+                            change => change,
+                        })
+                        .collect()
+                }
+            }
+            // Fields are removed or renamed without their types, so that their columns may be the keys of relations.
+            "RemoveField" => {
+                let [model, name] = parameters(operation, ["model_name", "name"], source);
+                let (table, name) = (self.table(&string(model)?), string(name)?);
+                [format!("{}_id", name), name].into_iter().map(|column| Change::RemoveColumn { table: table.clone(), column }).collect()
+            }
+            "RenameField" => {
+                let [model, from, to] = parameters(operation, ["model_name", "old_name", "new_name"], source);
+                let (table, from, to) = (self.table(&string(model)?), string(from)?, string(to)?);
+                vec![
+                    Change::RenameColumn { table: table.clone(), from: format!("{}_id", from), to: format!("{}_id", to) },
+                    Change::RenameColumn { table, from, to },
+                ]
+            }
+            _ => vec![],
+        };
+        Some(changes)
+    }
+
+    // Changes adding field to table, like `models.ForeignKey(on_delete=django.db.models.deletion.CASCADE, to='polls.question')`.
+    fn field_changes(&self, table: &str, model: &str, name: &str, field: Node, source: &[u8]) -> Vec<Change> {
+        let field_type = simple_name(field.child_by_field_name("function").map_or("", |function| text(function, source)));
+        let arguments = field.child_by_field_name("arguments").map(children).unwrap_or_default();
+        let options: IndexMap<&str, Value> = arguments.iter()
+            .filter(|argument| argument.kind() == "keyword_argument")
+            .filter_map(|argument| Some((text(argument.child_by_field_name("name")?, source), values::python(argument.child_by_field_name("value")?, source))))
+            .collect();
+        let option = |key: &str| options.get(key);
+        let nullable = option("null").is_some_and(Value::is_true);
+        // Related models are named by `to`, or by the first argument.
+        let target = option("to").cloned()
+            .or_else(|| arguments.iter().find(|argument| argument.is_named() && argument.kind() != "keyword_argument").map(|argument| values::python(*argument, source)))
+            .and_then(|target| target.as_str().map(|target| if target == "self" { String::from(model) } else { String::from(target) }));
+        let add = |column: Column| Change::AddColumn { table: String::from(table), column };
+
+        match field_type {
+            "ForeignKey" | "OneToOneField" => {
+                let column = option("db_column").and_then(Value::as_str).map_or_else(|| format!("{}_id", name), String::from);
+                let mut changes = vec![add(Column::new(&column, DataType::BigInteger, nullable))];
+                if let Some(target) = target {
+                    // Keys refer to the primary keys of tables, which are resolved once the tables are made.
+                    let foreign_key = ForeignKey { column, table: self.table(&target), references: String::new() };
+                    changes.push(Change::AddForeignKey { table: String::from(table), foreign_key });
+                }
+                changes
+            }
+            // Intermediate models given by `through` have their own tables.
+            "ManyToManyField" if option("through").is_none() => {
+                let target = match target {
+                    Some(target) => target,
+                    None => return vec![],
+                };
+                let join_table = option("db_table").and_then(Value::as_str).map_or_else(|| format!("{}_{}", table, name), String::from);
+                let (from, to) = (model.to_lowercase(), target.rsplit('.').next().unwrap_or(&target).to_lowercase());
+                let columns = match from == to {
+                    true => [(format!("from_{}_id", from), String::from(table)), (format!("to_{}_id", to), self.table(&target))],
+                    false => [(format!("{}_id", from), String::from(table)), (format!("{}_id", to), self.table(&target))],
+                };
+                let mut created = Table::new(&join_table);
+                created.columns.push(Column::new("id", DataType::BigInteger, false));
+                created.primary_key.push(String::from("id"));
+                for (column, referred) in columns {
+                    created.columns.push(Column::new(&column, DataType::BigInteger, false));
+                    created.foreign_keys.push(ForeignKey { column, table: referred, references: String::new() });
+                }
+                vec![Change::CreateTable(created)]
+            }
+            field_type if field_type.ends_with("Field") => {
+                let column_name = option("db_column").and_then(Value::as_str).unwrap_or(name);
+                let mut column = Column::new(column_name, data_type(Orm::Django, field_type), nullable);
+                column.length = option("max_length").and_then(Value::as_length);
+                column.default = option("default").cloned();
+                let mut changes = vec![add(column)];
+                if option("primary_key").is_some_and(Value::is_true) {
+                    changes.push(Change::AddPrimaryKey { table: String::from(table), columns: vec![String::from(column_name)] });
+                }
+                changes
+            }
+            _ => vec![],
+        }
+    }
+}
+
+// Arguments of call by the names of parameters, which are given by keywords or positions.
+fn parameters<'a, const N: usize>(call: Node<'a>, names: [&str; N], source: &[u8]) -> [Option<Node<'a>>; N] {
+    let arguments: Vec<Node> = call.child_by_field_name("arguments").map(children).unwrap_or_default()
+        .into_iter()
+        .filter(|argument| argument.is_named() && argument.kind() != "comment")
+        .collect();
+    names.map(|name| {
+        let keyword = arguments.iter()
+            .filter(|argument| argument.kind() == "keyword_argument")
+            .find(|argument| argument.child_by_field_name("name").is_some_and(|keyword| text(keyword, source) == name))
+            .and_then(|argument| argument.child_by_field_name("value"));
+        let position = names.iter().position(|other| *other == name).unwrap_or(0);
+        keyword.or_else(|| arguments.get(position).filter(|argument| argument.kind() != "keyword_argument").copied())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_fixture;
+
+    #[test]
+    fn it_should_read_django_migrations() {
+        let source = read_fixture("0001_initial.py");
+        let migrations = migrations(Some("polls"), &source);
+
+        assert_eq!(migrations.len(), 1);
+        let tables = schema::replay(migrations[0].up.clone());
+        assert_eq!(tables.iter().map(|table| table.name.as_str()).collect::<Vec<_>>(), vec!["polls_question", "polls_choice"]);
+        let columns: Vec<_> = tables[1].columns.iter().map(|column| (column.name.as_str(), column.data_type, column.length, column.nullable)).collect();
+        assert_eq!(columns, vec![
+            ("id", DataType::BigInteger, None, false),
+            ("choice_text", DataType::String, Some(200), false),
+            ("votes", DataType::Integer, None, false),
+            ("question_id", DataType::BigInteger, None, false),
+        ]);
+        assert_eq!(tables[1].primary_key, vec!["id"]);
+        assert_eq!(tables[1].foreign_keys, vec![ForeignKey { column: String::from("question_id"), table: String::from("polls_question"), references: String::from("id") }]);
+
+        let source = b"class Migration(migrations.Migration):
+    operations = [
+        migrations.AlterModelTable('question', 'questions'),
+        migrations.RenameField(model_name='choice', old_name='question', new_name='poll'),
+        migrations.AddField('choice', 'tags', models.ManyToManyField(to='polls.tag')),
+    ]
+";
+        let changes = &super::migrations(Some("polls"), source)[0].up;
+        assert_eq!(changes[0], Change::RenameTable { from: String::from("polls_question"), to: String::from("questions") });
+        assert_eq!(changes[1], Change::RenameColumn { table: String::from("polls_choice"), from: String::from("question_id"), to: String::from("poll_id") });
+        match &changes[3] {
+            Change::CreateTable(join_table) => {
+                assert_eq!(join_table.name, "polls_choice_tags");
+                assert_eq!(join_table.columns.iter().map(|column| column.name.as_str()).collect::<Vec<_>>(), vec!["id", "choice_id", "tag_id"]);
+            }
+            change => panic!("unexpected change {:?}", change),
+        }
+    }
+}
@@ -0,0 +1,356 @@
+use tree_sitter::Node;
+use crate::ddl::{Column, ForeignKey, Table};
+use crate::hierarchy::simple_name;
+use crate::inflector::{pluralize, underscore};
+use crate::models::{values, DataType, Value};
+use crate::schema::{self, Change};
+use crate::syntax::{children, parse, text, visit};
+use super::Migration;
+
+// Laravel migrations, which are classes extending `Migration`, often anonymous ones returned by the file.
+// They change the schema by `Schema::create('users', function (Blueprint $table) { ... })` in `up` and `down` methods.
+
+pub fn migrations(source: &[u8]) -> Vec<Migration> {
+    let tree = parse(tree_sitter_php::language(), source);
+    let mut migrations = vec![];
+    visit(tree.root_node(), &mut |node| {
+        // Anonymous classes are declared by `new class extends Migration { ... }`.
+        if !matches!(node.kind(), "class_declaration" | "anonymous_class" | "object_creation_expression") {
+            return;
+        }
+        let body = node.child_by_field_name("body").or_else(|| children(node).into_iter().find(|child| child.kind() == "declaration_list"));
+        let methods: Vec<Node> = body.map(children).unwrap_or_default()
+            .into_iter()
+            .filter(|declaration| declaration.kind() == "method_declaration")
+            .collect();
+        let method = |name: &str| methods.iter().find(|method| method.child_by_field_name("name").is_some_and(|method_name| text(method_name, source) == name));
+        let up = match method("up") {
+            Some(up) => up,
+            None => return,
+        };
+
+        let name = node.child_by_field_name("name").map_or("", |name| text(name, source));
+        let mut migration = Migration::new(name, node.byte_range());
+        migration.up = changes(*up, source);
+        migration.down = method("down").map(|down| changes(*down, source)).unwrap_or_default();
+        migrations.push(migration);
+    });
+    migrations
+}
+
+// Changes made by the calls of `Schema` facade in method, like `Schema::table('users', function (Blueprint $table) { ... })`.
+fn changes(method: Node, source: &[u8]) -> Vec<Change> {
+    let mut changes = vec![];
+    visit(method, &mut |node| {
+        if node.kind() != "scoped_call_expression" {
+            return;
+        }
+        let scope = node.child_by_field_name("scope").map_or("", |scope| text(scope, source));
+        if simple_name(scope) != "Schema" {
+            return;
+        }
+        let arguments = arguments(node);
+        let name = |index: usize| arguments.get(index).and_then(|argument| match values::php(*argument, source) {
+            Value::String(name) => Some(name),
+            _ => None,
+        });
+        let table = match name(0) {
+            Some(table) => table,
+            None => return,
+        };
+        let blueprint = arguments.last().map_or(vec![], |closure| statements(*closure, source));
+
+        match node.child_by_field_name("name").map_or("", |name| text(name, source)) {
+            "create" => {
+                let mut tables = vec![Table::new(&table)];
+                for statement in blueprint {
+                    for change in column_changes(&table, &statement, source) {
+                        schema::apply(&mut tables, change);
+                    }
+                }
+                changes.push(Change::CreateTable(tables.remove(0)));
+            }
+            "table" => changes.extend(blueprint.iter().flat_map(|statement| column_changes(&table, statement, source))),
+            "drop" | "dropIfExists" => changes.push(Change::DropTable(table)),
+            "rename" => changes.extend(name(1).map(|to| Change::RenameTable { from: table, to })),
+            _ => {}
+        }
+    });
+    changes
+}
+
+// Call on the blueprint with the calls chained to it, like `$table->string('email', 100)->nullable()->unique()`.
+struct Statement<'a> {
+    method: String,
+    arguments: Vec<Node<'a>>,
+    modifiers: Vec<(String, Vec<Node<'a>>)>,
+}
+
+impl Statement<'_> {
+    fn modifier(&self, name: &str) -> Option<&[Node<'_>]> {
+        self.modifiers.iter().find(|(modifier, _)| modifier == name).map(|(_, arguments)| arguments.as_slice())
+    }
+}
+
+// Statements of closure calling methods of the blueprint given to it.
+fn statements<'a>(closure: Node<'a>, source: &[u8]) -> Vec<Statement<'a>> {
+    let mut statements = vec![];
+    visit(closure, &mut |node| {
+        if node.kind() != "expression_statement" {
+            return;
+        }
+        let mut call = match node.named_child(0) {
+            Some(call) if call.kind() == "member_call_expression" => call,
+            _ => return,
+        };
+        let mut calls = vec![];
+        while call.kind() == "member_call_expression" {
+            let name = call.child_by_field_name("name").map_or("", |name| text(name, source));
+            calls.push((String::from(name), arguments(call)));
+            call = match call.child_by_field_name("object") {
+                Some(object) => object,
+                None => return,
+            };
+        }
+        if call.kind() != "variable_name" {
+            return;
+        }
+        let (method, arguments) = calls.remove(calls.len() - 1);
+        calls.reverse();
+        statements.push(Statement { method, arguments, modifiers: calls });
+    });
+    statements
+}
+
+// Changes made by a statement in the closure of `Schema::create` or `Schema::table`, like `$table->string('email')->nullable()`.
+fn column_changes(table: &str, statement: &Statement, source: &[u8]) -> Vec<Change> {
+    let strings = |arguments: &[Node]| -> Vec<String> {
+        arguments.iter()
+            .flat_map(|argument| match values::php(*argument, source) {
+                Value::String(name) => vec![name],
+                Value::Array(names) => names.iter().filter_map(Value::as_str).map(String::from).collect(),
+                _ => vec![],
+            })
+            .collect()
+    };
+    let names = strings(&statement.arguments);
+    let name = names.first().cloned().unwrap_or_default();
+    let add = |column: Column| Change::AddColumn { table: String::from(table), column };
+    let remove = |column: &str| Change::RemoveColumn { table: String::from(table), column: String::from(column) };
+    let key = |column: &str| Change::AddPrimaryKey { table: String::from(table), columns: vec![String::from(column)] };
+
+    let mut changes = match statement.method.as_str() {
+        // Keys, like `$table->id()` for `id` of `bigint`.
+        "id" | "bigIncrements" | "increments" | "smallIncrements" | "mediumIncrements" | "tinyIncrements" => {
+            let name = if name.is_empty() { String::from("id") } else { name };
+            let data_type = if matches!(statement.method.as_str(), "id" | "bigIncrements") { DataType::BigInteger } else { DataType::Integer };
+            return vec![add(Column::new(&name, data_type, false)), key(&name)];
+        }
+        "primary" => return vec![Change::AddPrimaryKey { table: String::from(table), columns: names }],
+        // Foreign keys, like `$table->foreign('user_id')->references('id')->on('users')`.
+        "foreign" => {
+            let references = statement.modifier("references").map(strings).unwrap_or_default();
+            let referred = statement.modifier("on").map(strings).unwrap_or_default();
+            return names.into_iter()
+                .enumerate()
+                .filter_map(|(index, column)| {
+                    let foreign_key = ForeignKey { column, table: referred.first()?.clone(), references: references.get(index).cloned().unwrap_or_default() };
+                    Some(Change::AddForeignKey { table: String::from(table), foreign_key })
+                })
+                .collect();
+        }
+        "dropForeign" => {
+            // Foreign keys are dropped by names unless their columns are listed, like `dropForeign(['user_id'])`.
+            let is_list = statement.arguments.first().is_some_and(|argument| argument.kind() == "array_creation_expression");
+            if !is_list {
+                return vec![];
+            }
+            return names.into_iter().map(|column| Change::RemoveForeignKey { table: String::from(table), column }).collect();
+        }
+        "dropColumn" | "dropColumns" => return names.iter().map(|name| remove(name)).collect(),
+        "renameColumn" => {
+            return match names.as_slice() {
+                [from, to, ..] => vec![Change::RenameColumn { table: String::from(table), from: from.clone(), to: to.clone() }],
+                _ => vec![],
+            };
+        }
+        "dropTimestamps" | "dropTimestampsTz" => return vec![remove("created_at"), remove("updated_at")],
+        "dropSoftDeletes" | "dropSoftDeletesTz" => return vec![remove("deleted_at")],
+        "dropRememberToken" => return vec![remove("remember_token")],
+        "dropMorphs" => return vec![remove(&format!("{}_type", name)), remove(&format!("{}_id", name))],
+        "timestamps" | "timestampsTz" | "nullableTimestamps" => {
+            return ["created_at", "updated_at"].into_iter().map(|name| add(Column::new(name, DataType::DateTime, true))).collect();
+        }
+        "softDeletes" | "softDeletesTz" => {
+            let name = if name.is_empty() { String::from("deleted_at") } else { name.clone() };
+            vec![add(Column::new(&name, DataType::DateTime, true))]
+        }
+        "rememberToken" => {
+            let mut column = Column::new("remember_token", DataType::String, true);
+            column.length = Some(100);
+            vec![add(column)]
+        }
+        // Polymorphic keys, like `commentable_id` and `commentable_type` for `$table->morphs('commentable')`.
+        "morphs" | "nullableMorphs" | "uuidMorphs" | "nullableUuidMorphs" => {
+            let nullable = statement.method.starts_with("nullable");
+            let data_type = if statement.method.contains("Uuid") { DataType::Uuid } else { DataType::BigInteger };
+            vec![
+                add(Column::new(&format!("{}_type", name), DataType::String, nullable)),
+                add(Column::new(&format!("{}_id", name), data_type, nullable)),
+            ]
+        }
+        // Keys of the models given by class, like `user_id` for `$table->foreignIdFor(User::class)`.
+        "foreignIdFor" => {
+            let model = statement.arguments.first().map_or("", |model| text(*model, source));
+            let model = simple_name(model.strip_suffix("::class").unwrap_or(model));
+            let name = names.get(1).cloned().unwrap_or_else(|| format!("{}_id", underscore(model)));
+            vec![add(Column::new(&name, DataType::BigInteger, false))]
+        }
+        method => match blueprint_type(method) {
+            Some(data_type) if !name.is_empty() => {
+                let mut column = Column::new(&name, data_type, false);
+                // Lengths are the second arguments of `string` and `char`.
+                if matches!(method, "string" | "char") {
+                    column.length = statement.arguments.get(1).and_then(|length| values::php(*length, source).as_length()).or(Some(255));
+                }
+                vec![add(column)]
+            }
+            _ => return vec![],
+        },
+    };
+
+    // Modifiers chained to columns, like `->nullable()->default('draft')` or `->constrained()`.
+    for change in changes.iter_mut() {
+        if let Change::AddColumn { column, .. } = change {
+            if let Some(arguments) = statement.modifier("nullable") {
+                column.nullable = arguments.first().is_none_or(|nullable| values::php(*nullable, source).is_true());
+            }
+            if let Some(default) = statement.modifier("default").and_then(|arguments| arguments.first()) {
+                column.default = Some(values::php(*default, source));
+            }
+        }
+    }
+    if statement.modifier("primary").is_some() {
+        changes.push(key(&name));
+    }
+    if let Some(arguments) = statement.modifier("constrained") {
+        let column = match changes.first() {
+            Some(Change::AddColumn { column, .. }) => column.name.clone(),
+            _ => name.clone(),
+        };
+        // `constrained()` infers the table from the column, like `users` for `user_id`.
+        let referred = strings(arguments).into_iter().next()
+            .or_else(|| column.strip_suffix("_id").map(pluralize))
+            .unwrap_or_default();
+        let references = strings(arguments).get(1).cloned().unwrap_or_else(|| String::from("id"));
+        changes.push(Change::AddForeignKey { table: String::from(table), foreign_key: ForeignKey { column, table: referred, references } });
+    }
+    // Columns changed by `->change()` replace the existing ones.
+    if statement.modifier("change").is_some() {
+        return changes.into_iter()
+            .map(|change| match change {
+                Change::AddColumn { table, column } => Change::ChangeColumn { table, column },
+                change => change,
+            })
+            .collect();
+    }
+    changes
+}
+
+// Type of the column which a method of `Blueprint` adds, like `bigInteger` or `foreignId`.
+fn blueprint_type(method: &str) -> Option<DataType> {
+    let data_type = match method {
+        "string" | "char" | "enum" | "set" | "ipAddress" | "macAddress" => DataType::String,
+        "text" | "tinyText" | "mediumText" | "longText" => DataType::Text,
+        "integer" | "tinyInteger" | "smallInteger" | "mediumInteger" | "unsignedInteger" | "unsignedTinyInteger"
+            | "unsignedSmallInteger" | "unsignedMediumInteger" | "year" => DataType::Integer,
+        "bigInteger" | "unsignedBigInteger" | "foreignId" => DataType::BigInteger,
+        "float" | "double" => DataType::Float,
+        "decimal" | "unsignedDecimal" => DataType::Decimal,
+        "boolean" => DataType::Boolean,
+        "date" => DataType::Date,
+        "dateTime" | "dateTimeTz" | "timestamp" | "timestampTz" => DataType::DateTime,
+        "time" | "timeTz" => DataType::Time,
+        "json" | "jsonb" => DataType::Json,
+        "binary" => DataType::Binary,
+        "uuid" | "foreignUuid" => DataType::Uuid,
+        _ => return None,
+    };
+    Some(data_type)
+}
+
+// Expressions passed to call, unwrapping the argument nodes.
+fn arguments(call: Node) -> Vec<Node> {
+    call.child_by_field_name("arguments")
+        .map(children)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|argument| argument.is_named())
+        .filter_map(|argument| match argument.kind() {
+            "argument" => argument.named_child(0),
+            _ => Some(argument),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_read_laravel_migrations() {
+        let source = b"<?php
+
+use Illuminate\\Database\\Migrations\\Migration;
+use Illuminate\\Database\\Schema\\Blueprint;
+use Illuminate\\Support\\Facades\\Schema;
+
+return new class extends Migration
+{
+    public function up(): void
+    {
+        Schema::create('posts', function (Blueprint $table) {
+            $table->id();
+            $table->foreignId('user_id')->constrained();
+            $table->string('title', 100);
+            $table->text('body')->nullable();
+            $table->timestamps();
+        });
+        Schema::table('users', function (Blueprint $table) {
+            $table->renameColumn('mail', 'email');
+            $table->dropColumn(['votes', 'avatar']);
+        });
+    }
+
+    public function down(): void
+    {
+        Schema::dropIfExists('posts');
+    }
+};
+";
+        let migrations = migrations(source);
+
+        assert_eq!(migrations.len(), 1);
+        assert_eq!(migrations[0].down, vec![Change::DropTable(String::from("posts"))]);
+        let table = match &migrations[0].up[0] {
+            Change::CreateTable(table) => table,
+            change => panic!("unexpected change {:?}", change),
+        };
+        let columns: Vec<_> = table.columns.iter().map(|column| (column.name.as_str(), column.data_type, column.length, column.nullable)).collect();
+        assert_eq!(columns, vec![
+            ("id", DataType::BigInteger, None, false),
+            ("user_id", DataType::BigInteger, None, false),
+            ("title", DataType::String, Some(100), false),
+            ("body", DataType::Text, None, true),
+            ("created_at", DataType::DateTime, None, true),
+            ("updated_at", DataType::DateTime, None, true),
+        ]);
+        assert_eq!(table.primary_key, vec!["id"]);
+        assert_eq!(table.foreign_keys, vec![ForeignKey { column: String::from("user_id"), table: String::from("users"), references: String::from("id") }]);
+        assert_eq!(migrations[0].up[1..], [
+            Change::RenameColumn { table: String::from("users"), from: String::from("mail"), to: String::from("email") },
+            Change::RemoveColumn { table: String::from("users"), column: String::from("votes") },
+            Change::RemoveColumn { table: String::from("users"), column: String::from("avatar") },
+        ]);
+    }
+}
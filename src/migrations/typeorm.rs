@@ -0,0 +1,246 @@
+use tree_sitter::{Language, Node};
+use crate::ddl::{Column, ForeignKey, Table};
+use crate::models::types::sql_data_type;
+use crate::models::{values, Value};
+use crate::schema::{self, Change};
+use crate::syntax::{children, parse, text, visit};
+use super::Migration;
+
+// TypeORM migrations, which are classes implementing `MigrationInterface` by `up` and `down` methods.
+// They change the schema by the methods of `QueryRunner`, or by SQL statements given to `queryRunner.query`.
+
+pub fn migrations(language: Language, source: &[u8]) -> Vec<Migration> {
+    let tree = parse(language, source);
+    let mut migrations = vec![];
+    visit(tree.root_node(), &mut |node| {
+        if !matches!(node.kind(), "class_declaration" | "class") {
+            return;
+        }
+        let methods: Vec<Node> = node.child_by_field_name("body").map(children).unwrap_or_default()
+            .into_iter()
+            .filter(|member| member.kind() == "method_definition")
+            .collect();
+        let method = |name: &str| methods.iter().find(|method| method.child_by_field_name("name").is_some_and(|method_name| text(method_name, source) == name));
+        let up = match method("up") {
+            Some(up) => up,
+            None => return,
+        };
+
+        let name = node.child_by_field_name("name").map_or("", |name| text(name, source));
+        let mut migration = Migration::new(name, node.byte_range());
+        migration.up = changes(*up, source);
+        migration.down = method("down").map(|down| changes(*down, source)).unwrap_or_default();
+        migrations.push(migration);
+    });
+    migrations
+}
+
+// Changes made by the calls of `QueryRunner` in method, like `await queryRunner.addColumn("post", new TableColumn({ ... }))`.
+fn changes(method: Node, source: &[u8]) -> Vec<Change> {
+    let mut changes = vec![];
+    visit(method, &mut |node| {
+        if node.kind() != "call_expression" {
+            return;
+        }
+        let function = match node.child_by_field_name("function") {
+            Some(function) if function.kind() == "member_expression" => function,
+            _ => return,
+        };
+        let arguments: Vec<Node> = node.child_by_field_name("arguments").map(children).unwrap_or_default()
+            .into_iter()
+            .filter(|argument| argument.is_named() && argument.kind() != "comment")
+            .collect();
+        // Tables and columns are given by names, or by the objects describing them.
+        let name = |index: usize| {
+            let argument = arguments.get(index)?;
+            match values::javascript(*argument, source) {
+                Value::String(name) => Some(name),
+                _ => options(*argument, source)?.get("name").and_then(Value::as_str).map(String::from),
+            }
+        };
+        let option = |index: usize| arguments.get(index).and_then(|argument| options(*argument, source));
+        let items = |index: usize| arguments.get(index).filter(|argument| argument.kind() == "array").map(|array| children(*array)).unwrap_or_default();
+
+        let method = function.child_by_field_name("property").map_or("", |property| text(property, source));
+        let table = match name(0) {
+            Some(table) => table,
+            None => return,
+        };
+        match method {
+            "query" => {
+                let sql = unescape(&table);
+                changes.extend(schema::sql_changes(&sql).unwrap_or_default());
+            }
+            "createTable" => changes.extend(option(0).map(|options| Change::CreateTable(create_table(&table, &options)))),
+            "dropTable" => changes.push(Change::DropTable(table)),
+            "renameTable" => changes.extend(name(1).map(|to| Change::RenameTable { from: table, to })),
+            "addColumn" => changes.extend(option(1).map(|options| Change::AddColumn { table, column: column(&options) })),
+            "addColumns" => {
+                let columns = items(1).into_iter().filter_map(|item| options(item, source));
+                changes.extend(columns.map(|options| Change::AddColumn { table: table.clone(), column: column(&options) }));
+            }
+            "dropColumn" => changes.extend(name(1).map(|column| Change::RemoveColumn { table, column })),
+            "dropColumns" => {
+                let columns = items(1).into_iter().filter(|item| item.is_named()).filter_map(|item| match values::javascript(item, source) {
+                    Value::String(column) => Some(column),
+                    _ => options(item, source)?.get("name").and_then(Value::as_str).map(String::from),
+                });
+                changes.extend(columns.map(|column| Change::RemoveColumn { table: table.clone(), column }));
+            }
+            "renameColumn" => {
+                if let (Some(from), Some(to)) = (name(1), name(2)) {
+                    changes.push(Change::RenameColumn { table, from, to });
+                }
+            }
+            // `changeColumn("post", "title", new TableColumn({ name: "headline", ... }))` renames columns besides changing them.
+            "changeColumn" => {
+                if let (Some(from), Some(options)) = (name(1), option(2)) {
+                    let column = column(&options);
+                    if column.name != from {
+                        changes.push(Change::RenameColumn { table: table.clone(), from, to: column.name.clone() });
+                    }
+                    changes.push(Change::ChangeColumn { table, column });
+                }
+            }
+            "createPrimaryKey" => {
+                let columns = arguments.get(1).map(|columns| values::javascript(*columns, source));
+                if let Some(Value::Array(columns)) = columns {
+                    changes.push(Change::AddPrimaryKey { table, columns: columns.iter().filter_map(Value::as_str).map(String::from).collect() });
+                }
+            }
+            "createForeignKey" => changes.extend(option(1).into_iter().flat_map(|options| foreign_keys(&options)).map(|foreign_key| Change::AddForeignKey { table: table.clone(), foreign_key })),
+            "createForeignKeys" => {
+                let foreign_keys = items(1).into_iter().filter_map(|item| options(item, source)).flat_map(|options| foreign_keys(&options));
+                changes.extend(foreign_keys.map(|foreign_key| Change::AddForeignKey { table: table.clone(), foreign_key }));
+            }
+            // Foreign keys are dropped by names unless they are described by objects.
+            "dropForeignKey" => {
+                let columns = option(1).into_iter().flat_map(|options| foreign_keys(&options)).map(|foreign_key| foreign_key.column);
+                changes.extend(columns.map(|column| Change::RemoveForeignKey { table: table.clone(), column }));
+            }
+            _ => {}
+        }
+    });
+    changes
+}
+
+// Options describing table, column or foreign key, like `new TableColumn({ name: "title", type: "varchar" })`.
+fn options(node: Node, source: &[u8]) -> Option<Value> {
+    let object = match node.kind() {
+        "new_expression" => node.child_by_field_name("arguments")?.named_child(0)?,
+        _ => node,
+    };
+    Some(values::javascript(object, source)).filter(|options| matches!(options, Value::Object(_)))
+}
+
+// `new Table({ name: "post", columns: [...], foreignKeys: [...] })`
+fn create_table(name: &str, options: &Value) -> Table {
+    let mut table = Table::new(name);
+    let list = |key: &str| match options.get(key) {
+        Some(Value::Array(items)) => items.clone(),
+        _ => vec![],
+    };
+    for options in list("columns") {
+        let column = column(&options);
+        if options.get("isPrimary").is_some_and(Value::is_true) {
+            table.primary_key.push(column.name.clone());
+        }
+        table.columns.push(column);
+    }
+    for options in list("foreignKeys") {
+        table.foreign_keys.extend(foreign_keys(&options));
+    }
+    table
+}
+
+// `{ name: "title", type: "varchar", length: "255", isNullable: true, default: "'draft'" }`, whose defaults are SQL expressions.
+fn column(options: &Value) -> Column {
+    let option = |key: &str| options.get(key);
+    let name = option("name").and_then(Value::as_str).unwrap_or_default();
+    let declared = option("type").and_then(Value::as_str).unwrap_or_default();
+    let is_primary = option("isPrimary").is_some_and(Value::is_true);
+    let mut column = Column::new(name, sql_data_type(declared), option("isNullable").is_some_and(Value::is_true) && !is_primary);
+    column.length = option("length").and_then(|length| match length {
+        Value::String(length) => length.parse().ok(),
+        length => length.as_length(),
+    });
+    column.default = option("default").map(|default| match default {
+        Value::String(default) => schema::expression(default),
+        default => default.clone(),
+    });
+    column
+}
+
+// `{ columnNames: ["authorId"], referencedTableName: "user", referencedColumnNames: ["id"] }`
+fn foreign_keys(options: &Value) -> Vec<ForeignKey> {
+    let names = |key: &str| match options.get(key) {
+        Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).map(String::from).collect(),
+        _ => vec![],
+    };
+    let table = options.get("referencedTableName").and_then(Value::as_str).unwrap_or_default();
+    let references = names("referencedColumnNames");
+    names("columnNames").into_iter()
+        .enumerate()
+        .map(|(index, column)| ForeignKey { column, table: String::from(table), references: references.get(index).cloned().unwrap_or_default() })
+        .collect()
+}
+
+// SQL given by string literal, like `"ALTER TABLE \"post\" ADD \"title\" varchar"`.
+fn unescape(literal: &str) -> String {
+    literal.replace("\\\"", "\"").replace("\\'", "'").replace("\\`", "`").replace("\\n", "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DataType;
+
+    #[test]
+    fn it_should_read_typeorm_migrations() {
+        let source = br#"import { MigrationInterface, QueryRunner, Table, TableColumn, TableForeignKey } from "typeorm"
+
+export class PostRefactoring1680000000000 implements MigrationInterface {
+    async up(queryRunner: QueryRunner): Promise<void> {
+        await queryRunner.query(`CREATE TABLE "user" ("id" SERIAL NOT NULL, "name" character varying(100) NOT NULL, CONSTRAINT "PK_user" PRIMARY KEY ("id"))`)
+        await queryRunner.createTable(new Table({
+            name: "post",
+            columns: [
+                { name: "id", type: "int", isPrimary: true, isGenerated: true },
+                { name: "title", type: "varchar", length: "255" },
+                { name: "status", type: "varchar", isNullable: true, default: "'draft'" },
+            ],
+        }), true)
+        await queryRunner.addColumn("post", new TableColumn({ name: "authorId", type: "int" }))
+        await queryRunner.createForeignKey("post", new TableForeignKey({
+            columnNames: ["authorId"],
+            referencedTableName: "user",
+            referencedColumnNames: ["id"],
+        }))
+        await queryRunner.renameColumn("post", "title", "headline")
+    }
+
+    async down(queryRunner: QueryRunner): Promise<void> {
+        await queryRunner.query("DROP TABLE \"post\"")
+    }
+}
+"#;
+        let migrations = migrations(tree_sitter_typescript::language_typescript(), source);
+
+        assert_eq!(migrations.len(), 1);
+        assert_eq!(migrations[0].name, "PostRefactoring1680000000000");
+        assert_eq!(migrations[0].down, vec![Change::DropTable(String::from("post"))]);
+
+        let tables = schema::replay(migrations[0].up.clone());
+        assert_eq!(tables[0].primary_key, vec!["id"]);
+        let columns: Vec<_> = tables[1].columns.iter().map(|column| (column.name.as_str(), column.data_type, column.length, column.nullable)).collect();
+        assert_eq!(columns, vec![
+            ("id", DataType::Integer, None, false),
+            ("headline", DataType::String, Some(255), false),
+            ("status", DataType::String, None, true),
+            ("authorId", DataType::Integer, None, false),
+        ]);
+        assert_eq!(tables[1].columns[2].default, Some(Value::String(String::from("draft"))));
+        assert_eq!(tables[1].primary_key, vec!["id"]);
+        assert_eq!(tables[1].foreign_keys, vec![ForeignKey { column: String::from("authorId"), table: String::from("user"), references: String::from("id") }]);
+    }
+}
@@ -0,0 +1,287 @@
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+use indexmap::IndexMap;
+
+// ORM models normalized across frameworks, recognized by the conventions of each ORM.
+
+pub mod activerecord;
+pub mod constants;
+pub mod django;
+pub mod eloquent;
+pub mod hooks;
+pub mod objection;
+pub mod rules;
+pub mod scopes;
+pub mod tables;
+pub mod typeorm;
+pub mod types;
+pub mod values;
+
+pub use types::DataType;
+pub use values::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orm {
+    ActiveRecord,
+    Django,
+    Eloquent,
+    Objection,
+    TypeOrm,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Model {
+    pub name: String,
+    pub orm: Orm,
+    // Module or namespace enclosing the model, like `Admin` or `App\Models`.
+    pub namespace: Option<String>,
+    // Django app declared by `Meta.app_label`, see `tables` for the one named after the package.
+    pub app_label: Option<String>,
+    // Table name declared by the model, like `self.table_name = "people"`, see `tables` for the effective one.
+    pub table: Option<String>,
+    pub fields: Vec<Field>,
+    pub relations: Vec<Relation>,
+    pub validations: Vec<Validation>,
+    pub hooks: Vec<Hook>,
+    pub scopes: Vec<Scope>,
+    // Attributes and relations left out when the model is serialized, like Eloquent `$hidden` or TypeORM `@Exclude()`.
+    pub hidden: Vec<String>,
+    pub file: String,
+    // Byte range of the class.
+    pub range: Range<usize>,
+}
+
+impl Model {
+    fn new(name: &str, orm: Orm, range: Range<usize>) -> Self {
+        Model {
+            name: String::from(name),
+            orm,
+            namespace: None,
+            app_label: None,
+            table: None,
+            fields: vec![],
+            relations: vec![],
+            validations: vec![],
+            hooks: vec![],
+            scopes: vec![],
+            hidden: vec![],
+            file: String::new(),
+            range,
+        }
+    }
+
+    // Replaces references to the constants of the file by their values in the arguments of validations.
+    fn resolve(&mut self, constants: &IndexMap<String, Value>) {
+        for validation in &mut self.validations {
+            validation.value = validation.value.resolve(constants);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: String,
+    // Type as the ORM names it, like `CharField`, `datetime` or `int`.
+    pub field_type: Option<String>,
+    pub data_type: DataType,
+    // Maximum length of strings, like `max_length=200` or `{ length: 255 }`.
+    pub length: Option<u32>,
+    pub nullable: bool,
+    pub default: Option<Value>,
+    pub primary_key: bool,
+    pub range: Range<usize>,
+}
+
+impl Field {
+    // Field of the type, which is nullable unless the ORM declares otherwise, like databases do.
+    fn new(name: &str, orm: Orm, field_type: Option<String>, range: Range<usize>) -> Self {
+        Field {
+            name: String::from(name),
+            data_type: field_type.as_deref().map_or(DataType::Unknown, |field_type| types::data_type(orm, field_type)),
+            field_type,
+            length: None,
+            nullable: true,
+            default: None,
+            primary_key: false,
+            range,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelationKind {
+    // The foreign key is held by the model itself, like `belongs_to`, `@ManyToOne` or `models.ForeignKey`.
+    BelongsTo,
+    HasOne,
+    HasMany,
+    ManyToMany,
+}
+
+impl fmt::Display for RelationKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self {
+            RelationKind::BelongsTo => "belongs_to",
+            RelationKind::HasOne => "has_one",
+            RelationKind::HasMany => "has_many",
+            RelationKind::ManyToMany => "many_to_many",
+        };
+        write!(f, "{}", kind)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Relation {
+    pub name: String,
+    pub kind: RelationKind,
+    // Related model, which is missing for polymorphic relations.
+    pub target: Option<String>,
+    // Column holding the key, on the model for `BelongsTo` and on the target otherwise.
+    pub foreign_key: Option<String>,
+    // Relation or join table the relation goes through, like `active_relationships` or `post_categories`.
+    pub through: Option<String>,
+    // Relation of the target pointing back to the model, like `posts` for `(cover) => cover.posts`.
+    pub inverse: Option<String>,
+    // Options as declared, like `dependent: :destroy` or `{ cascade: true }`.
+    pub options: IndexMap<String, Value>,
+    pub range: Range<usize>,
+}
+
+impl Relation {
+    fn new(name: &str, kind: RelationKind, range: Range<usize>) -> Self {
+        Relation {
+            name: String::from(name),
+            kind,
+            target: None,
+            foreign_key: None,
+            through: None,
+            inverse: None,
+            options: IndexMap::new(),
+            range,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Validation {
+    pub field: String,
+    // Rule as the ORM names it, like `presence`, `maxLength` or `IsEmail`, see `rules` for the normalized ones.
+    pub rule: String,
+    // Argument of the rule, like `{ maximum: 255 }` for `length: { maximum: 255 }`, or `true` for rules without any.
+    pub value: Value,
+    // Conditions and other options applying to the rule, like `if: :admin?` or `allow_nil: true`.
+    pub options: IndexMap<String, Value>,
+    pub range: Range<usize>,
+}
+
+impl Validation {
+    fn new(field: &str, rule: &str, value: Value, range: Range<usize>) -> Self {
+        Validation { field: String::from(field), rule: String::from(rule), value, options: IndexMap::new(), range }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hook {
+    // Event as the ORM names it, like `before_save`, `creating` or `BeforeInsert`.
+    pub event: String,
+    // Method called on the event, which is missing for inline callbacks like closures.
+    pub callback: Option<String>,
+    // Byte range of the registration, like `before_save :downcase_email` or `@BeforeInsert()`.
+    pub range: Range<usize>,
+    // Byte range of the method defining the callback, which is missing when it is not defined in the file.
+    pub definition: Option<Range<usize>>,
+}
+
+impl Hook {
+    fn new(event: &str, callback: Option<String>, range: Range<usize>) -> Self {
+        Hook { event: String::from(event), callback, range, definition: None }
+    }
+}
+
+// Named query of the model, like `scope :active, -> { where(active: true) }` or `scopeActive($query)` for `User::active()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scope {
+    // Name called on the model or its receiver, like `active` or `published` of `Question.objects.published()`.
+    pub name: String,
+    // Manager or repository providing the scope, like `objects` or `PostRepository`, which is missing for the scopes of models.
+    pub receiver: Option<String>,
+    // Byte range of the definition, like the `scope` call or the method.
+    pub range: Range<usize>,
+}
+
+impl Scope {
+    fn new(name: &str, receiver: Option<String>, range: Range<usize>) -> Self {
+        Scope { name: String::from(name), receiver, range }
+    }
+}
+
+// Models declared in supplied source, choosing the ORM by the extension of file name.
+pub fn models(file_name: &str, source: &[u8]) -> Option<Vec<Model>> {
+    let extension = Path::new(file_name).extension()?.to_str()?;
+    let models = match extension {
+        "js" | "mjs" | "cjs" | "jsx" => objection::models(tree_sitter_javascript::language(), source),
+        "ts" | "mts" | "cts" | "tsx" => {
+            let language = match extension {
+                "tsx" => tree_sitter_typescript::language_tsx(),
+                _ => tree_sitter_typescript::language_typescript(),
+            };
+            let mut models = typeorm::models(language, source);
+            models.extend(objection::models(language, source));
+            models.sort_by_key(|model| model.range.start);
+            models
+        }
+        "rb" => activerecord::models(source),
+        "php" => eloquent::models(source),
+        "py" => django::models(source),
+        _ => return None,
+    };
+    Some(models.into_iter().map(|model| Model { file: String::from(file_name), ..model }).collect())
+}
+
+// Content of string literal without quotes, unless it is interpolated, like `"#{id}"` of Ruby or `` `${id}` `` of JavaScript.
+fn unquote(literal: &str) -> Option<String> {
+    let literal = literal.trim();
+    let quote = literal.chars().next()?;
+    if !matches!(quote, '"' | '\'' | '`') || literal.len() < 2 || !literal.ends_with(quote) {
+        return None;
+    }
+    let content = &literal[1..literal.len() - 1];
+    if (quote == '"' && content.contains("#{")) || (quote == '`' && content.contains("${")) {
+        return None;
+    }
+    Some(String::from(content))
+}
+
+// Content of PHP string literal without quotes, unless variables are interpolated into it, like `"$id"`.
+fn unquote_php(literal: &str) -> Option<String> {
+    unquote(literal).filter(|content| !literal.trim().starts_with('"') || !content.contains('$'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_fixture;
+
+    #[test]
+    fn it_should_pick_orm_by_extension() {
+        let fixtures = [("Animal.js", Orm::Objection), ("Post.ts", Orm::TypeOrm), ("user.rb", Orm::ActiveRecord), ("models.py", Orm::Django)];
+        for (fixture_name, orm) in fixtures {
+            let models = models(fixture_name, &read_fixture(fixture_name)).unwrap();
+            assert!(!models.is_empty());
+            assert!(models.iter().all(|model| model.orm == orm && model.file == fixture_name));
+        }
+        assert_eq!(models("README.md", b""), None);
+    }
+
+    #[test]
+    fn it_should_unquote_strings() {
+        assert_eq!(unquote("'animals'"), Some(String::from("animals")));
+        assert_eq!(unquote("\"users\""), Some(String::from("users")));
+        assert_eq!(unquote("\"#{prefix}_users\""), None);
+        assert_eq!(unquote("users"), None);
+        assert_eq!(unquote("\"$1.00\""), Some(String::from("$1.00")));
+        assert_eq!(unquote("`${prefix}_users`"), None);
+        assert_eq!(unquote_php("\"$1.00\""), None);
+        assert_eq!(unquote_php("'$1.00'"), Some(String::from("$1.00")));
+    }
+}
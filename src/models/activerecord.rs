@@ -0,0 +1,407 @@
+use indexmap::IndexMap;
+use tree_sitter::Node;
+use crate::hierarchy::simple_name;
+use crate::inflector::{camelize, singularize, underscore};
+use crate::syntax::{children, parse, text, visit};
+use super::{constants, unquote, values, Field, Hook, Model, Orm, Relation, RelationKind, Scope, Validation, Value};
+
+// ActiveRecord models, which inherit `ApplicationRecord`, `ActiveRecord::Base` or abstract classes inheriting them.
+
+const BASES: [&str; 2] = ["ApplicationRecord", "ActiveRecord::Base"];
+
+const HOOKS: [&str; 24] = [
+    "after_initialize", "after_find", "after_touch",
+    "before_validation", "after_validation",
+    "before_save", "around_save", "after_save",
+    "before_create", "around_create", "after_create",
+    "before_update", "around_update", "after_update",
+    "before_destroy", "around_destroy", "after_destroy",
+    "before_commit", "after_commit", "after_rollback",
+    "after_create_commit", "after_update_commit", "after_destroy_commit", "after_save_commit",
+];
+
+// Methods of `ActiveRecord::QueryMethods` and the like, which return relations.
+const QUERY_METHODS: [&str; 24] = [
+    "all", "none", "where", "rewhere", "order", "reorder", "in_order_of", "joins", "left_joins", "left_outer_joins",
+    "includes", "preload", "eager_load", "references", "limit", "offset", "select", "distinct", "group", "having",
+    "unscoped", "merge", "excluding", "extending",
+];
+
+// Options of `validates` which are not rules, but conditions or messages of them.
+const VALIDATION_OPTIONS: [&str; 8] = ["if", "unless", "on", "allow_nil", "allow_blank", "message", "strict", "except_on"];
+
+pub fn models(source: &[u8]) -> Vec<Model> {
+    let tree = parse(tree_sitter_ruby::language(), source);
+    let constants = constants::ruby(tree.root_node(), source);
+    let mut abstracts = vec![];
+    visit(tree.root_node(), &mut |node| {
+        if node.kind() == "class" && is_abstract(node, source) {
+            abstracts.extend(node.child_by_field_name("name").map(|name| simple_name(text(name, source))));
+        }
+    });
+
+    let mut models = vec![];
+    visit(tree.root_node(), &mut |node| {
+        if node.kind() != "class" {
+            return;
+        }
+        let superclass = node.child_by_field_name("superclass").and_then(|superclass| superclass.named_child(0));
+        let (name, superclass) = match (node.child_by_field_name("name"), superclass) {
+            (Some(name), Some(superclass)) => (name, text(superclass, source)),
+            _ => return,
+        };
+        if (!BASES.contains(&superclass) && !abstracts.contains(&simple_name(superclass))) || is_abstract(node, source) {
+            return;
+        }
+
+        let mut model = Model::new(text(name, source), Orm::ActiveRecord, node.byte_range());
+        model.namespace = namespace(node, source);
+        for statement in children(node) {
+            match statement.kind() {
+                "assignment" => read_table_name(&mut model, statement, source),
+                "call" if statement.child_by_field_name("receiver").is_none() => read_macro(&mut model, statement, source),
+                "method" => read_serialization(&mut model, statement, source),
+                "singleton_method" => read_class_method(&mut model, statement, source),
+                _ => {}
+            }
+        }
+        // Callbacks are usually defined after registering them, often as private methods.
+        for hook in &mut model.hooks {
+            hook.definition = children(node).into_iter()
+                .filter(|statement| statement.kind() == "method")
+                .find(|method| method.child_by_field_name("name").map(|name| text(name, source)) == hook.callback.as_deref())
+                .map(|method| method.byte_range());
+        }
+        model.resolve(&constants);
+        models.push(model);
+    });
+    resolve_through(&mut models);
+    models
+}
+
+// Whether the class declares itself abstract, which has no table, like `self.abstract_class = true` or `primary_abstract_class`.
+fn is_abstract(class: Node, source: &[u8]) -> bool {
+    children(class).into_iter().any(|statement| match statement.kind() {
+        "assignment" => {
+            statement.child_by_field_name("left").is_some_and(|left| text(left, source) == "self.abstract_class")
+                && statement.child_by_field_name("right").is_some_and(|right| text(right, source) == "true")
+        }
+        "identifier" => text(statement, source) == "primary_abstract_class",
+        _ => false,
+    })
+}
+
+// Targets of relations through other relations, which are the targets of their source relations on the models in between,
+// like `source: :followed` of `Relationship` for `has_many :following, through: :active_relationships, source: :followed`.
+fn resolve_through(models: &mut [Model]) {
+    let mut targets = vec![];
+    for (index, model) in models.iter().enumerate() {
+        for (position, relation) in model.relations.iter().enumerate().filter(|(_, relation)| relation.target.is_none()) {
+            let Some(through) = relation.through.as_deref() else {
+                continue;
+            };
+            let Some(between) = model.relations.iter().find(|other| other.name == through).and_then(|other| other.target.as_deref()) else {
+                continue;
+            };
+            let sources = match relation.options.get("source").and_then(Value::as_str) {
+                Some(source) => vec![String::from(source)],
+                None => vec![relation.name.clone(), singularize(&relation.name)],
+            };
+            let target = models.iter()
+                .find(|other| other.name == between)
+                .and_then(|other| other.relations.iter().find(|source| sources.contains(&source.name)))
+                .and_then(|source| source.target.clone());
+            targets.extend(target.map(|target| (index, position, target)));
+        }
+    }
+    for (index, position, target) in targets {
+        models[index].relations[position].target = Some(target);
+    }
+}
+
+// Modules and classes enclosing the class, like `Admin::Billing`.
+fn namespace(class: Node, source: &[u8]) -> Option<String> {
+    let mut names = vec![];
+    let mut parent = class.parent();
+    while let Some(node) = parent {
+        if matches!(node.kind(), "module" | "class") {
+            names.extend(node.child_by_field_name("name").map(|name| text(name, source)));
+        }
+        parent = node.parent();
+    }
+    names.reverse();
+    Some(names.join("::")).filter(|namespace| !namespace.is_empty())
+}
+
+// `self.table_name = "people"`
+fn read_table_name(model: &mut Model, assignment: Node, source: &[u8]) {
+    let left = match assignment.child_by_field_name("left") {
+        Some(left) if left.kind() == "call" => left,
+        _ => return,
+    };
+    let receiver = left.child_by_field_name("receiver").map_or("", |receiver| text(receiver, source));
+    let method = left.child_by_field_name("method").map_or("", |method| text(method, source));
+    if receiver == "self" && method == "table_name" {
+        model.table = assignment.child_by_field_name("right").and_then(|right| string(right, source));
+    }
+}
+
+fn read_macro(model: &mut Model, call: Node, source: &[u8]) {
+    let name = call.child_by_field_name("method").map_or("", |method| text(method, source));
+    let arguments = call.child_by_field_name("arguments").map(children).unwrap_or_default();
+    let names: Vec<String> = arguments.iter().filter_map(|argument| string(*argument, source)).collect();
+    let options: IndexMap<String, Value> = arguments.iter()
+        .filter(|argument| argument.kind() == "pair")
+        .filter_map(|pair| Some((values::key(pair.child_by_field_name("key")?, source), values::ruby(pair.child_by_field_name("value")?, source))))
+        .collect();
+
+    if let Some(kind) = relation_kind(name) {
+        if let Some(relation) = names.first() {
+            model.relations.push(relation_of(&model.name, relation, kind, options, call));
+        }
+    } else if HOOKS.contains(&name) {
+        if names.is_empty() {
+            // Callback given as a block.
+            model.hooks.push(Hook::new(name, None, call.byte_range()));
+        }
+        for callback in names {
+            model.hooks.push(Hook::new(name, Some(callback), call.byte_range()));
+        }
+    } else if name == "scope" {
+        if let Some(scope) = names.first() {
+            model.scopes.push(Scope::new(scope, None, call.byte_range()));
+        }
+    } else if name == "validates" {
+        let (validation_options, rules): (IndexMap<_, _>, IndexMap<_, _>) = options.into_iter()
+            .partition(|(option, _)| VALIDATION_OPTIONS.contains(&option.as_str()));
+        for field in &names {
+            for (rule, value) in &rules {
+                let mut validation = Validation::new(field, rule, value.clone(), call.byte_range());
+                validation.options = validation_options.clone();
+                model.validations.push(validation);
+            }
+        }
+    } else if let Some(rule) = name.strip_prefix("validates_").and_then(|name| name.strip_suffix("_of")) {
+        // Old style validations, like `validates_length_of :name, maximum: 50`.
+        let (validation_options, options): (IndexMap<_, _>, IndexMap<_, _>) = options.into_iter()
+            .partition(|(option, _)| VALIDATION_OPTIONS.contains(&option.as_str()));
+        let value = if options.is_empty() { Value::Boolean(true) } else { Value::Object(options) };
+        for field in names {
+            let mut validation = Validation::new(&field, rule, value.clone(), call.byte_range());
+            validation.options = validation_options.clone();
+            model.validations.push(validation);
+        }
+    } else if name == "attribute" {
+        // Attributes like `attribute :nickname, :string, limit: 20, default: ""`.
+        let mut names = names.into_iter();
+        if let Some(name) = names.next() {
+            let mut field = Field::new(&name, Orm::ActiveRecord, names.next(), call.byte_range());
+            field.length = options.get("limit").and_then(|limit| limit.as_length());
+            field.default = options.get("default").cloned();
+            model.fields.push(field);
+        }
+    }
+}
+
+// Attributes left out of JSON by overriding serialization, like `super(options.merge(except: [:password_digest]))` in `def as_json`.
+fn read_serialization(model: &mut Model, method: Node, source: &[u8]) {
+    let name = method.child_by_field_name("name").map_or("", |name| text(name, source));
+    if !matches!(name, "as_json" | "serializable_hash") {
+        return;
+    }
+    visit(method, &mut |node| {
+        let (key, value) = match (node.kind(), node.child_by_field_name("key"), node.child_by_field_name("value")) {
+            ("pair", Some(key), Some(value)) => (key, value),
+            _ => return,
+        };
+        if values::key(key, source) != "except" {
+            return;
+        }
+        match values::ruby(value, source) {
+            Value::Array(names) => model.hidden.extend(names.iter().filter_map(Value::as_str).map(String::from)),
+            Value::String(name) => model.hidden.push(name),
+            _ => {}
+        }
+    });
+}
+
+// Class methods returning relations are scopes, like `def self.recent` returning `order(created_at: :desc)`.
+fn read_class_method(model: &mut Model, method: Node, source: &[u8]) {
+    let object = method.child_by_field_name("object").map_or("", |object| text(object, source));
+    let name = match method.child_by_field_name("name") {
+        Some(name) if object == "self" || object == model.name => name,
+        _ => return,
+    };
+    // The last statement is returned, whose innermost call is a query on the model, like `where(...)` of `where(...).order(...)`.
+    let returned = children(method).into_iter()
+        .rfind(|child| child.is_named() && child.start_byte() >= name.end_byte() && !matches!(child.kind(), "comment" | "method_parameters"));
+    let mut call = match returned {
+        Some(call) => call,
+        None => return,
+    };
+    while let Some(receiver) = call.child_by_field_name("receiver").filter(|receiver| receiver.kind() == "call") {
+        call = receiver;
+    }
+    let receiver = call.child_by_field_name("receiver");
+    let query = match (call.kind(), receiver.map(|receiver| (receiver.kind(), text(receiver, source)))) {
+        // `published.order(...)` of scopes chained on the model.
+        ("call", Some(("identifier", scope))) => scope,
+        ("call", None | Some(("self", _))) => call.child_by_field_name("method").map_or("", |method| text(method, source)),
+        ("call", Some((_, receiver))) if receiver == model.name => call.child_by_field_name("method").map_or("", |method| text(method, source)),
+        ("identifier", _) => text(call, source),
+        _ => return,
+    };
+    if QUERY_METHODS.contains(&query) || model.scopes.iter().any(|scope| scope.name == query) {
+        model.scopes.push(Scope::new(text(name, source), None, method.byte_range()));
+    }
+}
+
+fn relation_kind(name: &str) -> Option<RelationKind> {
+    match name {
+        "belongs_to" => Some(RelationKind::BelongsTo),
+        "has_one" => Some(RelationKind::HasOne),
+        "has_many" => Some(RelationKind::HasMany),
+        "has_and_belongs_to_many" => Some(RelationKind::ManyToMany),
+        _ => None,
+    }
+}
+
+// Relation declared like `has_many :following, through: :active_relationships, source: :followed`.
+// Rails infers the target and foreign key from the names unless they are given by options.
+fn relation_of(model: &str, name: &str, kind: RelationKind, options: IndexMap<String, Value>, call: Node) -> Relation {
+    let option = |key: &str| options.get(key).and_then(|value| value.as_str()).map(String::from);
+    let mut relation = Relation::new(name, kind, call.byte_range());
+
+    let polymorphic = options.get("polymorphic") == Some(&Value::Boolean(true));
+    relation.target = match option("class_name") {
+        Some(class_name) => Some(String::from(class_name.trim_start_matches("::"))),
+        // Targets of relations through other relations are the ones of their sources, see `resolve_through`.
+        None if polymorphic || options.contains_key("through") => None,
+        None => {
+            let source = option("source").unwrap_or_else(|| String::from(name));
+            Some(camelize(&match kind {
+                RelationKind::HasMany | RelationKind::ManyToMany => singularize(&source),
+                _ => source,
+            }))
+        }
+    };
+    relation.through = option("through").or_else(|| option("join_table"));
+    relation.inverse = option("inverse_of");
+    relation.foreign_key = option("foreign_key").or_else(|| match kind {
+        RelationKind::BelongsTo => Some(format!("{}_id", name)),
+        // Relations through other relations use the keys of these relations.
+        _ if relation.through.is_some() => None,
+        RelationKind::HasOne | RelationKind::HasMany => {
+            Some(format!("{}_id", option("as").unwrap_or_else(|| underscore(model))))
+        }
+        RelationKind::ManyToMany => Some(format!("{}_id", underscore(model))),
+    });
+    relation.options = options;
+    relation
+}
+
+// Value of symbol or string literal.
+fn string(node: Node, source: &[u8]) -> Option<String> {
+    match node.kind() {
+        "simple_symbol" => Some(String::from(text(node, source).trim_start_matches(':'))),
+        "string" => unquote(text(node, source)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_fixture;
+    use crate::models::DataType;
+
+    #[test]
+    fn it_should_read_activerecord_models() {
+        let source = read_fixture("user.rb");
+        let models = models(&source);
+
+        assert_eq!(models.len(), 1);
+        let user = &models[0];
+        assert_eq!(user.name, "User");
+        assert_eq!(user.table, None);
+
+        let relations: Vec<_> = user.relations.iter()
+            .map(|relation| (relation.name.as_str(), relation.kind, relation.target.as_deref(), relation.foreign_key.as_deref(), relation.through.as_deref()))
+            .collect();
+        assert_eq!(relations, vec![
+            ("microposts", RelationKind::HasMany, Some("Micropost"), Some("user_id"), None),
+            ("active_relationships", RelationKind::HasMany, Some("Relationship"), Some("follower_id"), None),
+            ("passive_relationships", RelationKind::HasMany, Some("Relationship"), Some("followed_id"), None),
+            // `Relationship` isn't in the file to resolve `followed` and `follower` by.
+            ("following", RelationKind::HasMany, None, None, Some("active_relationships")),
+            ("followers", RelationKind::HasMany, None, None, Some("passive_relationships")),
+        ]);
+        assert_eq!(user.relations[0].options.get("dependent"), Some(&Value::String(String::from("destroy"))));
+
+        let validations: Vec<(&str, &str)> = user.validations.iter().map(|validation| (validation.field.as_str(), validation.rule.as_str())).collect();
+        assert_eq!(validations, vec![
+            ("name", "presence"),
+            ("name", "length"),
+            ("email", "presence"),
+            ("email", "length"),
+            ("email", "format"),
+            ("email", "uniqueness"),
+            ("password", "presence"),
+            ("password", "length"),
+        ]);
+
+        let hooks: Vec<(&str, Option<&str>)> = user.hooks.iter().map(|hook| (hook.event.as_str(), hook.callback.as_deref())).collect();
+        assert_eq!(hooks, vec![("before_save", Some("downcase_email")), ("before_create", Some("create_activation_digest"))]);
+        // `User.digest` and `User.new_token` don't query users.
+        assert_eq!(user.scopes, vec![]);
+    }
+
+    #[test]
+    fn it_should_resolve_relations_through_others() {
+        let source = b"class Relationship < ApplicationRecord\n  belongs_to :follower, class_name: \"User\"\n  belongs_to :followed, class_name: \"User\"\nend\nclass User < ApplicationRecord\n  has_many :active_relationships, class_name: \"Relationship\", foreign_key: \"follower_id\"\n  has_many :following, through: :active_relationships, source: :followed\n  has_many :tags, through: :posts\nend\n";
+        let models = models(source);
+
+        let targets: Vec<_> = models[1].relations.iter().map(|relation| (relation.name.as_str(), relation.target.as_deref())).collect();
+        assert_eq!(targets, vec![("active_relationships", Some("Relationship")), ("following", Some("User")), ("tags", None)]);
+    }
+
+    #[test]
+    fn it_should_skip_abstract_classes() {
+        let source = b"class ApplicationRecord < ActiveRecord::Base\n  primary_abstract_class\nend\nclass Auditable < ApplicationRecord\n  self.abstract_class = true\nend\nclass Invoice < Auditable\n  belongs_to :customer\nend\n";
+        let models = models(source);
+
+        assert_eq!(models.iter().map(|model| model.name.as_str()).collect::<Vec<_>>(), vec!["Invoice"]);
+    }
+
+    #[test]
+    fn it_should_read_scopes() {
+        let source = b"class Post < ApplicationRecord\n  scope :published, -> { where(published: true) }\n  def self.recent(limit = 10)\n    published.order(created_at: :desc).limit(limit)\n  end\n  def Post.by(author)\n    where(author: author)\n  end\n  def self.export\n    CSV.generate { |csv| all.each { |post| csv << post.attributes } }\n  end\nend\n";
+        let models = models(source);
+
+        let scopes: Vec<_> = models[0].scopes.iter().map(|scope| (scope.name.as_str(), &source[scope.range.clone()][..12])).collect();
+        assert_eq!(scopes, vec![("published", &b"scope :publi"[..]), ("recent", &b"def self.rec"[..]), ("by", &b"def Post.by("[..])]);
+    }
+
+    #[test]
+    fn it_should_read_declared_table_and_attributes() {
+        let source = b"module Crm\nclass Person < ActiveRecord::Base\n  self.table_name = \"people\"\n  belongs_to :imageable, polymorphic: true\n  has_many :pictures, as: :imageable, inverse_of: :imageable\n  attribute :nickname, :string, limit: 20, default: \"\"\n  validates_uniqueness_of :nickname\n  after_commit do\n  end\n  def as_json(options = {})\n    super(options.merge(except: [:password_digest, :token]))\n  end\nend\nend\nclass Plain\nend\n";
+        let models = models(source);
+
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].table.as_deref(), Some("people"));
+        assert_eq!(models[0].namespace.as_deref(), Some("Crm"));
+        assert_eq!(models[0].fields[0].name, "nickname");
+        assert_eq!(models[0].fields[0].field_type.as_deref(), Some("string"));
+        assert_eq!(models[0].fields[0].data_type, DataType::String);
+        assert_eq!(models[0].fields[0].length, Some(20));
+        assert_eq!(models[0].fields[0].default, Some(Value::String(String::new())));
+        assert_eq!(models[0].validations[0].rule, "uniqueness");
+        assert_eq!(models[0].hooks[0].callback, None);
+        assert_eq!(models[0].hidden, vec!["password_digest", "token"]);
+
+        let relations = &models[0].relations;
+        assert_eq!((relations[0].target.as_deref(), relations[0].foreign_key.as_deref()), (None, Some("imageable_id")));
+        assert_eq!((relations[1].target.as_deref(), relations[1].foreign_key.as_deref()), (Some("Picture"), Some("imageable_id")));
+        assert_eq!(relations[1].inverse.as_deref(), Some("imageable"));
+    }
+}
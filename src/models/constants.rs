@@ -0,0 +1,118 @@
+use indexmap::IndexMap;
+use tree_sitter::Node;
+use crate::syntax::{children, text, visit};
+use super::values::{self, Value};
+
+// Constants declared in the same file as models, like `VALID_EMAIL_REGEX = /.../i`, to resolve the references to them.
+// Constants referring to constants declared before them are resolved too.
+
+// `const MAX_LENGTH = 255`, declared at the top level of module.
+pub fn javascript(root: Node, source: &[u8]) -> IndexMap<String, Value> {
+    let mut constants = IndexMap::new();
+    for statement in children(root) {
+        let declaration = match statement.kind() {
+            "export_statement" => match statement.child_by_field_name("declaration") {
+                Some(declaration) => declaration,
+                None => continue,
+            },
+            _ => statement,
+        };
+        // TypeScript doesn't name the keyword by the `kind` field as JavaScript does.
+        let is_const = declaration.child(0).is_some_and(|keyword| text(keyword, source) == "const");
+        if declaration.kind() != "lexical_declaration" || !is_const {
+            continue;
+        }
+        for declarator in children(declaration).into_iter().filter(|child| child.kind() == "variable_declarator") {
+            if let (Some(name), Some(value)) = (declarator.child_by_field_name("name"), declarator.child_by_field_name("value")) {
+                if name.kind() == "identifier" {
+                    let value = values::javascript(value, source).resolve(&constants);
+                    constants.insert(String::from(text(name, source)), value);
+                }
+            }
+        }
+    }
+    constants
+}
+
+// `VALID_EMAIL_REGEX = /.../i`, declared anywhere as Ruby constants are.
+pub fn ruby(root: Node, source: &[u8]) -> IndexMap<String, Value> {
+    let mut constants = IndexMap::new();
+    visit(root, &mut |node| {
+        if node.kind() != "assignment" {
+            return;
+        }
+        if let (Some(name), Some(value)) = (node.child_by_field_name("left"), node.child_by_field_name("right")) {
+            if name.kind() == "constant" {
+                let value = values::ruby(value, source).resolve(&constants);
+                constants.insert(String::from(text(name, source)), value);
+            }
+        }
+    });
+    constants
+}
+
+// `const MAX_LENGTH = 255;`, declared by classes and referred by `self::MAX_LENGTH` or `static::MAX_LENGTH`.
+pub fn php(root: Node, source: &[u8]) -> IndexMap<String, Value> {
+    let mut constants = IndexMap::new();
+    visit(root, &mut |node| {
+        if node.kind() != "const_declaration" {
+            return;
+        }
+        for element in children(node).into_iter().filter(|child| child.kind() == "const_element") {
+            if let (Some(name), Some(value)) = (element.named_child(0), element.named_child(1)) {
+                let value = values::php(value, source).resolve(&constants);
+                for scope in ["self", "static"] {
+                    constants.insert(format!("{}::{}", scope, text(name, source)), value.clone());
+                }
+            }
+        }
+    });
+    constants
+}
+
+// `EMAIL_REGEX = r"..."`, assigned to upper case names in modules or class bodies.
+pub fn python(root: Node, source: &[u8]) -> IndexMap<String, Value> {
+    let mut constants = IndexMap::new();
+    visit(root, &mut |node| {
+        let scope = node.parent().and_then(|statement| statement.parent());
+        let is_declared = scope.is_some_and(|scope| match scope.kind() {
+            "module" => true,
+            "block" => scope.parent().is_some_and(|parent| parent.kind() == "class_definition"),
+            _ => false,
+        });
+        if node.kind() != "assignment" || !is_declared {
+            return;
+        }
+        if let (Some(name), Some(value)) = (node.child_by_field_name("left"), node.child_by_field_name("right")) {
+            let name = text(name, source);
+            let is_constant = name.chars().all(|char| char.is_ascii_uppercase() || char.is_ascii_digit() || char == '_')
+                && name.starts_with(|char: char| char.is_ascii_uppercase());
+            if is_constant {
+                let value = values::python(value, source).resolve(&constants);
+                constants.insert(String::from(name), value);
+            }
+        }
+    });
+    constants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::parse;
+
+    #[test]
+    fn it_should_read_constants() {
+        let source = b"const MAX = 255;\nexport const LIMITS = { max: MAX };\nlet mutable = 1;\nfunction f() { const LOCAL = 2; }\n";
+        let tree = parse(tree_sitter_javascript::language(), source);
+        let constants = javascript(tree.root_node(), source);
+        assert_eq!(constants.keys().collect::<Vec<_>>(), vec!["MAX", "LIMITS"]);
+        assert_eq!(constants["LIMITS"].get("max"), Some(&Value::Number(255.0)));
+
+        let source = b"EMAIL_RE = r'^\\S+@\\S+$'\nclass User:\n    MAX_AGE = 120\n    name = 'x'\ndef f():\n    LOCAL = 1\n";
+        let tree = parse(tree_sitter_python::language(), source);
+        let constants = python(tree.root_node(), source);
+        assert_eq!(constants.keys().collect::<Vec<_>>(), vec!["EMAIL_RE", "MAX_AGE"]);
+        assert_eq!(constants["EMAIL_RE"], Value::String(String::from("^\\S+@\\S+$")));
+    }
+}
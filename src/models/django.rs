@@ -0,0 +1,379 @@
+use indexmap::IndexMap;
+use tree_sitter::Node;
+use crate::hierarchy::simple_name;
+use crate::syntax::{children, parse, text, visit};
+use super::{constants, unquote, values, Field, Hook, Model, Orm, Relation, RelationKind, Scope, Validation, Value};
+
+// Django models, which inherit `models.Model` or abstract models inheriting it.
+
+const RELATIONS: [&str; 3] = ["ForeignKey", "OneToOneField", "ManyToManyField"];
+
+// Options of fields which validate their values.
+const VALIDATIONS: [&str; 6] = ["max_length", "unique", "choices", "blank", "null", "max_digits"];
+
+// Methods which models override to run code around persistence.
+const HOOKS: [&str; 4] = ["save", "delete", "clean", "full_clean"];
+
+pub fn models(source: &[u8]) -> Vec<Model> {
+    let tree = parse(tree_sitter_python::language(), source);
+    let constants = constants::python(tree.root_node(), source);
+    let managers = managers(tree.root_node(), source);
+
+    let mut models: Vec<Model> = vec![];
+    // Abstract models have no tables, but their fields are copied to the models inheriting them.
+    let mut abstracts: Vec<Model> = vec![];
+    visit(tree.root_node(), &mut |node| {
+        if node.kind() != "class_definition" {
+            return;
+        }
+        let bases: Vec<&str> = node.child_by_field_name("superclasses").map(children).unwrap_or_default()
+            .into_iter()
+            .filter(|base| base.is_named())
+            .map(|base| simple_name(text(base, source)))
+            .collect();
+        let inherited: Vec<&Model> = abstracts.iter().filter(|model| bases.contains(&model.name.as_str())).collect();
+        let is_model = bases.last() == Some(&"Model") || !inherited.is_empty();
+        let (name, body) = match (node.child_by_field_name("name"), node.child_by_field_name("body")) {
+            (Some(name), Some(body)) if is_model => (name, body),
+            _ => return,
+        };
+
+        let mut model = Model::new(text(name, source), Orm::Django, node.byte_range());
+        for base in inherited {
+            model.fields.extend(base.fields.iter().cloned());
+            model.relations.extend(base.relations.iter().cloned());
+            model.validations.extend(base.validations.iter().cloned());
+        }
+        let mut is_abstract = false;
+        for statement in children(body) {
+            match statement.kind() {
+                "expression_statement" => {
+                    read_field(&mut model, statement, source);
+                    read_manager(&mut model, statement, &managers, source);
+                }
+                "class_definition" => is_abstract |= read_meta(&mut model, statement, source),
+                "function_definition" => read_hook(&mut model, statement, source),
+                "decorated_definition" => {
+                    if let Some(definition) = statement.child_by_field_name("definition") {
+                        read_hook(&mut model, definition, source);
+                    }
+                }
+                _ => {}
+            }
+        }
+        model.resolve(&constants);
+        if is_abstract {
+            abstracts.push(model);
+        } else {
+            models.push(model);
+        }
+    });
+
+    // Signal receivers, like `@receiver(post_save, sender=Question)`.
+    visit(tree.root_node(), &mut |node| {
+        if node.kind() != "decorated_definition" || node.parent().is_some_and(|parent| parent.kind() != "module") {
+            return;
+        }
+        let definition = node.child_by_field_name("definition");
+        let callback = definition
+            .and_then(|definition| definition.child_by_field_name("name"))
+            .map(|name| String::from(text(name, source)));
+        for decorator in children(node).into_iter().filter(|child| child.kind() == "decorator") {
+            let call = match decorator.named_child(0) {
+                Some(call) if call.kind() == "call" => call,
+                _ => continue,
+            };
+            let function = call.child_by_field_name("function").map_or("", |function| text(function, source));
+            if simple_name(function) != "receiver" {
+                continue;
+            }
+            let arguments = call.child_by_field_name("arguments").map(children).unwrap_or_default();
+            let signal = arguments.iter().find(|argument| argument.is_named() && argument.kind() != "keyword_argument");
+            let sender = arguments.iter()
+                .filter(|argument| argument.kind() == "keyword_argument")
+                .find(|argument| argument.child_by_field_name("name").is_some_and(|name| text(name, source) == "sender"))
+                .and_then(|argument| argument.child_by_field_name("value"));
+            if let (Some(signal), Some(sender)) = (signal, sender) {
+                let sender = simple_name(text(sender, source));
+                if let Some(model) = models.iter_mut().find(|model| model.name == sender) {
+                    let mut hook = Hook::new(simple_name(text(*signal, source)), callback.clone(), decorator.byte_range());
+                    // The function itself, without the decorators registering it.
+                    hook.definition = definition.map(|definition| definition.byte_range());
+                    model.hooks.push(hook);
+                }
+            }
+        }
+    });
+    models
+}
+
+// `question_text = models.CharField(max_length=200)`
+fn read_field(model: &mut Model, statement: Node, source: &[u8]) {
+    let assignment = match statement.named_child(0) {
+        Some(assignment) if assignment.kind() == "assignment" => assignment,
+        _ => return,
+    };
+    let (name, call) = match (assignment.child_by_field_name("left"), assignment.child_by_field_name("right")) {
+        (Some(name), Some(call)) if name.kind() == "identifier" && call.kind() == "call" => (name, call),
+        _ => return,
+    };
+    let function = call.child_by_field_name("function").map_or("", |function| text(function, source));
+    let field_type = simple_name(function);
+    if !field_type.ends_with("Field") && !RELATIONS.contains(&field_type) {
+        return;
+    }
+
+    let name = String::from(text(name, source));
+    let arguments = call.child_by_field_name("arguments").map(children).unwrap_or_default();
+    let options: IndexMap<&str, Node> = arguments.into_iter()
+        .filter(|argument| argument.kind() == "keyword_argument")
+        .filter_map(|argument| Some((text(argument.child_by_field_name("name")?, source), argument)))
+        .collect();
+
+    if RELATIONS.contains(&field_type) {
+        let relation = relation_of(&model.name, &name, field_type, call, source);
+        model.relations.push(Relation { range: statement.byte_range(), ..relation });
+    } else {
+        // Django fields are required unless they are declared with `null=True`.
+        let option = |option: &str| options.get(option).and_then(|argument| argument.child_by_field_name("value")).map(|value| values::python(value, source));
+        let mut field = Field::new(&name, Orm::Django, Some(String::from(field_type)), statement.byte_range());
+        field.length = option("max_length").and_then(|length| length.as_length());
+        field.nullable = option("null").is_some_and(|null| null.is_true());
+        field.default = option("default");
+        field.primary_key = option("primary_key").is_some_and(|primary_key| primary_key.is_true());
+        model.fields.push(field);
+    }
+
+    for (option, argument) in options {
+        let value = match argument.child_by_field_name("value") {
+            Some(value) => value,
+            None => continue,
+        };
+        if option == "validators" && value.kind() == "list" {
+            // Validators are listed as functions or instances, like `[validate_slug, MinLengthValidator(8)]`.
+            for validator in children(value).into_iter().filter(|validator| validator.is_named()) {
+                let (function, arguments) = match validator.kind() {
+                    "call" => (validator.child_by_field_name("function"), validator.child_by_field_name("arguments").map(children).unwrap_or_default()),
+                    _ => (Some(validator), vec![]),
+                };
+                let rule = String::from(simple_name(function.map_or("", |function| text(function, source))));
+                let value = arguments.into_iter()
+                    .find(|argument| argument.is_named() && argument.kind() != "keyword_argument")
+                    .map_or(Value::Boolean(true), |argument| values::python(argument, source));
+                model.validations.push(Validation::new(&name, &rule, value, validator.byte_range()));
+            }
+        } else if VALIDATIONS.contains(&option) {
+            let value = values::python(value, source);
+            model.validations.push(Validation::new(&name, option, value, argument.byte_range()));
+        }
+    }
+}
+
+// Custom managers and querysets of the file by their names, with their public methods, like `PublishedManager` of `class PublishedManager(models.Manager)`.
+fn managers<'a>(root: Node<'a>, source: &'a [u8]) -> IndexMap<&'a str, Vec<Node<'a>>> {
+    let mut managers = IndexMap::new();
+    visit(root, &mut |node| {
+        if node.kind() != "class_definition" {
+            return;
+        }
+        let bases = node.child_by_field_name("superclasses").map(children).unwrap_or_default();
+        let is_manager = bases.into_iter()
+            .filter(|base| base.is_named())
+            .map(|base| simple_name(text(base, source)))
+            .any(|base| base.ends_with("Manager") || base.ends_with("QuerySet"));
+        let (name, body) = match (node.child_by_field_name("name"), node.child_by_field_name("body")) {
+            (Some(name), Some(body)) if is_manager => (name, body),
+            _ => return,
+        };
+        let methods = children(body).into_iter()
+            .filter_map(|statement| match statement.kind() {
+                "function_definition" => Some(statement),
+                "decorated_definition" => statement.child_by_field_name("definition").filter(|definition| definition.kind() == "function_definition"),
+                _ => None,
+            })
+            .filter(|method| method.child_by_field_name("name").is_some_and(|name| !text(name, source).starts_with('_') && text(name, source) != "get_queryset"))
+            .collect();
+        managers.insert(text(name, source), methods);
+    });
+    managers
+}
+
+// Methods of managers are scopes of the models they are assigned to,
+// like `objects = PublishedManager()`, `objects = PostQuerySet.as_manager()` or `objects = models.Manager.from_queryset(PostQuerySet)()`.
+fn read_manager(model: &mut Model, statement: Node, managers: &IndexMap<&str, Vec<Node>>, source: &[u8]) {
+    let assignment = match statement.named_child(0) {
+        Some(assignment) if assignment.kind() == "assignment" => assignment,
+        _ => return,
+    };
+    let (name, call) = match (assignment.child_by_field_name("left"), assignment.child_by_field_name("right")) {
+        (Some(name), Some(call)) if name.kind() == "identifier" && call.kind() == "call" => (text(name, source), call),
+        _ => return,
+    };
+    let function = match call.child_by_field_name("function") {
+        Some(function) => function,
+        None => return,
+    };
+    let classes: Vec<&str> = match function.kind() {
+        // `models.Manager.from_queryset(PostQuerySet)()`
+        "call" => function.child_by_field_name("arguments").map(children).unwrap_or_default()
+            .into_iter()
+            .filter(|argument| argument.is_named())
+            .map(|argument| text(argument, source))
+            .collect(),
+        "attribute" if function.child_by_field_name("attribute").is_some_and(|method| text(method, source) == "as_manager") => {
+            function.child_by_field_name("object").map(|class| text(class, source)).into_iter().collect()
+        }
+        _ => vec![text(function, source)],
+    };
+    for class in classes {
+        for method in managers.get(simple_name(class)).into_iter().flatten() {
+            let method_name = method.child_by_field_name("name").map_or("", |method_name| text(method_name, source));
+            if !model.scopes.iter().any(|scope| scope.name == method_name && scope.receiver.as_deref() == Some(name)) {
+                model.scopes.push(Scope::new(method_name, Some(String::from(name)), method.byte_range()));
+            }
+        }
+    }
+}
+
+// Relation declared like `models.ForeignKey(Question, on_delete=models.CASCADE)`.
+fn relation_of(model: &str, name: &str, field_type: &str, call: Node, source: &[u8]) -> Relation {
+    let kind = match field_type {
+        "ManyToManyField" => RelationKind::ManyToMany,
+        // One-to-one fields hold the key like foreign keys, only they are unique.
+        _ => RelationKind::BelongsTo,
+    };
+    let mut relation = Relation::new(name, kind, call.byte_range());
+
+    let arguments = call.child_by_field_name("arguments").map(children).unwrap_or_default();
+    let mut target = None;
+    for argument in arguments.into_iter().filter(|argument| argument.is_named()) {
+        match argument.kind() {
+            "keyword_argument" => {
+                if let (Some(option), Some(value)) = (argument.child_by_field_name("name"), argument.child_by_field_name("value")) {
+                    relation.options.insert(String::from(text(option, source)), values::python(value, source));
+                }
+            }
+            _ if target.is_none() => target = Some(values::python(argument, source)),
+            _ => {}
+        }
+    }
+
+    let option = |key: &str| relation.options.get(key).and_then(|value| value.as_text()).map(String::from);
+    // Models are referred by class or by name, optionally prefixed by their app like `"polls.Question"`.
+    relation.target = target.as_ref().or_else(|| relation.options.get("to"))
+        .and_then(|target| target.as_text())
+        .map(|target| String::from(if target == "self" { model } else { simple_name(target) }));
+    relation.foreign_key = match kind {
+        RelationKind::BelongsTo => Some(option("db_column").unwrap_or_else(|| format!("{}_id", name))),
+        _ => None,
+    };
+    relation.through = option("through").map(|through| String::from(simple_name(&through))).or_else(|| option("db_table"));
+    relation.inverse = option("related_name").filter(|related_name| related_name != "+");
+    relation
+}
+
+// `class Meta: db_table = "polls_question"` and `app_label = "polls"`, returning whether `abstract = True` declares the model abstract.
+fn read_meta(model: &mut Model, class: Node, source: &[u8]) -> bool {
+    if class.child_by_field_name("name").map_or("", |name| text(name, source)) != "Meta" {
+        return false;
+    }
+    let mut is_abstract = false;
+    let body = class.child_by_field_name("body").map(children).unwrap_or_default();
+    for assignment in body.into_iter().filter_map(|statement| statement.named_child(0)) {
+        if assignment.kind() != "assignment" {
+            continue;
+        }
+        let left = assignment.child_by_field_name("left").map_or("", |left| text(left, source));
+        let right = assignment.child_by_field_name("right").map_or("", |right| text(right, source));
+        match left {
+            "db_table" => model.table = unquote(right),
+            "app_label" => model.app_label = unquote(right),
+            "abstract" => is_abstract = right == "True",
+            _ => {}
+        }
+    }
+    is_abstract
+}
+
+// Overridden `save`, `delete` and `clean` methods.
+fn read_hook(model: &mut Model, function: Node, source: &[u8]) {
+    let name = function.child_by_field_name("name").map_or("", |name| text(name, source));
+    if HOOKS.contains(&name) {
+        // Overrides register themselves.
+        let mut hook = Hook::new(name, Some(String::from(name)), function.byte_range());
+        hook.definition = Some(function.byte_range());
+        model.hooks.push(hook);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_fixture;
+    use crate::models::DataType;
+
+    #[test]
+    fn it_should_read_django_models() {
+        let source = read_fixture("models.py");
+        let models = models(&source);
+
+        let names: Vec<&str> = models.iter().map(|model| model.name.as_str()).collect();
+        assert_eq!(names, vec!["Question", "Choice"]);
+
+        let fields: Vec<(&str, Option<&str>)> = models[1].fields.iter().map(|field| (field.name.as_str(), field.field_type.as_deref())).collect();
+        assert_eq!(fields, vec![("choice_text", Some("CharField")), ("votes", Some("IntegerField"))]);
+        let votes = &models[1].fields[1];
+        assert_eq!((votes.data_type, votes.nullable, votes.default.clone()), (DataType::Integer, false, Some(Value::Number(0.0))));
+        let question_text = &models[0].fields[0];
+        assert_eq!((question_text.data_type, question_text.length, question_text.primary_key), (DataType::String, Some(200), false));
+        let question = &models[1].relations[0];
+        assert_eq!(question.name, "question");
+        assert_eq!(question.kind, RelationKind::BelongsTo);
+        assert_eq!(question.target.as_deref(), Some("Question"));
+        assert_eq!(question.foreign_key.as_deref(), Some("question_id"));
+        assert_eq!(question.options.get("on_delete"), Some(&Value::Expression(String::from("models.CASCADE"))));
+
+        let validations: Vec<(&str, &str)> = models[0].validations.iter().map(|validation| (validation.field.as_str(), validation.rule.as_str())).collect();
+        assert_eq!(validations, vec![("question_text", "max_length")]);
+        assert_eq!(models[0].table, None);
+    }
+
+    #[test]
+    fn it_should_read_meta_and_hooks() {
+        let source = b"class Tag(models.Model):\n    code = models.UUIDField(primary_key=True)\n    name = models.SlugField(unique=True, null=True)\n    parent = models.OneToOneField('self', null=True, related_name='child')\n    posts = models.ManyToManyField('blog.Post', through='Tagging')\n\n    class Meta:\n        db_table = 'tags'\n\n    def save(self, *args, **kwargs):\n        super().save(*args, **kwargs)\n\n@receiver(post_delete, sender=Tag)\ndef forget(sender, instance, **kwargs):\n    pass\n";
+        let models = models(source);
+
+        assert_eq!(models[0].table.as_deref(), Some("tags"));
+        assert_eq!((models[0].fields[0].data_type, models[0].fields[0].primary_key), (DataType::Uuid, true));
+        assert!(models[0].fields[1].nullable);
+        assert_eq!(models[0].validations[0].rule, "unique");
+        let hooks: Vec<(&str, Option<&str>)> = models[0].hooks.iter().map(|hook| (hook.event.as_str(), hook.callback.as_deref())).collect();
+        assert_eq!(hooks, vec![("save", Some("save")), ("post_delete", Some("forget"))]);
+
+        let relations = &models[0].relations;
+        assert_eq!(relations[0].target.as_deref(), Some("Tag"));
+        assert_eq!(relations[0].inverse.as_deref(), Some("child"));
+        assert_eq!(relations[1].kind, RelationKind::ManyToMany);
+        assert_eq!(relations[1].target.as_deref(), Some("Post"));
+        assert_eq!(relations[1].through.as_deref(), Some("Tagging"));
+        assert_eq!(relations[1].foreign_key, None);
+    }
+
+    #[test]
+    fn it_should_inherit_fields_of_abstract_models() {
+        let source = b"class TimeStamped(models.Model):\n    created = models.DateTimeField(auto_now_add=True)\n\n    class Meta:\n        abstract = True\n\nclass Post(TimeStamped):\n    title = models.CharField(max_length=200)\n";
+        let models = models(source);
+
+        assert_eq!(models.iter().map(|model| model.name.as_str()).collect::<Vec<_>>(), vec!["Post"]);
+        assert_eq!(models[0].fields.iter().map(|field| field.name.as_str()).collect::<Vec<_>>(), vec!["created", "title"]);
+    }
+
+    #[test]
+    fn it_should_read_managers() {
+        let source = b"class PostQuerySet(models.QuerySet):\n    def published(self):\n        return self.filter(published=True)\n\n    def _visible(self):\n        return self\n\nclass Post(models.Model):\n    objects = PostQuerySet.as_manager()\n    drafts = models.Manager.from_queryset(PostQuerySet)()\n    everything = models.Manager()\n";
+        let models = models(source);
+
+        let scopes: Vec<_> = models[0].scopes.iter().map(|scope| (scope.name.as_str(), scope.receiver.as_deref(), scope.range.start)).collect();
+        assert_eq!(scopes, vec![("published", Some("objects"), 41), ("published", Some("drafts"), 41)]);
+    }
+}
@@ -0,0 +1,390 @@
+use tree_sitter::Node;
+use crate::hierarchy::simple_name;
+use crate::inflector::underscore;
+use crate::syntax::{children, parse, text, visit};
+use super::{constants, types, unquote_php, values, Field, Hook, Model, Orm, Relation, RelationKind, Scope, Validation, Value};
+
+// Eloquent models, which extend `Model` or one of its subclasses shipped by Laravel.
+
+const BASES: [&str; 4] = ["Model", "Authenticatable", "Pivot", "MorphPivot"];
+
+const HOOKS: [&str; 14] = [
+    "retrieved", "creating", "created", "updating", "updated", "saving", "saved",
+    "deleting", "deleted", "trashed", "forceDeleting", "forceDeleted", "restoring", "restored",
+];
+
+// Properties listing attributes of the model.
+const FIELDS: [&str; 5] = ["$fillable", "$guarded", "$hidden", "$visible", "$dates"];
+
+pub fn models(source: &[u8]) -> Vec<Model> {
+    let tree = parse(tree_sitter_php::language(), source);
+    let constants = constants::php(tree.root_node(), source);
+
+    let mut models = vec![];
+    visit(tree.root_node(), &mut |node| {
+        if node.kind() != "class_declaration" {
+            return;
+        }
+        let is_model = children(node).into_iter()
+            .filter(|child| child.kind() == "base_clause")
+            .flat_map(children)
+            .any(|base| base.is_named() && BASES.contains(&simple_name(text(base, source))));
+        let (name, body) = match (node.child_by_field_name("name"), node.child_by_field_name("body")) {
+            (Some(name), Some(body)) if is_model => (name, body),
+            _ => return,
+        };
+
+        let mut model = Model::new(text(name, source), Orm::Eloquent, node.byte_range());
+        model.namespace = namespace(node, source);
+        for declaration in children(body) {
+            match declaration.kind() {
+                "property_declaration" => read_property(&mut model, declaration, source),
+                "method_declaration" => read_method(&mut model, declaration, source),
+                _ => {}
+            }
+        }
+        // Callbacks registered on boot may name methods of the model, like `static::saving('normalize')`.
+        for hook in model.hooks.iter_mut().filter(|hook| hook.definition.is_none()) {
+            hook.definition = children(body).into_iter()
+                .filter(|declaration| declaration.kind() == "method_declaration")
+                .find(|method| method.child_by_field_name("name").map(|name| text(name, source)) == hook.callback.as_deref())
+                .map(|method| method.byte_range());
+        }
+        model.resolve(&constants);
+        models.push(model);
+    });
+    models
+}
+
+// Namespace of the class, declared by a block enclosing it or by the last statement preceding it.
+fn namespace(class: Node, source: &[u8]) -> Option<String> {
+    let mut parent = class.parent();
+    while let Some(node) = parent {
+        if node.kind() == "namespace_definition" {
+            return node.child_by_field_name("name").map(|name| String::from(text(name, source)));
+        }
+        parent = node.parent();
+    }
+    let root = class.parent()?;
+    children(root).into_iter()
+        .rfind(|child| child.kind() == "namespace_definition" && child.end_byte() <= class.start_byte())
+        .and_then(|definition| definition.child_by_field_name("name"))
+        .map(|name| String::from(text(name, source)))
+}
+
+fn read_property(model: &mut Model, declaration: Node, source: &[u8]) {
+    for element in children(declaration).into_iter().filter(|child| child.kind() == "property_element") {
+        let name = element.named_child(0).map_or("", |name| text(name, source));
+        let value = match element.named_child(1).and_then(|initializer| initializer.named_child(0)) {
+            Some(value) => value,
+            None => continue,
+        };
+
+        match name {
+            "$table" => model.table = unquote_php(text(value, source)),
+            "$primaryKey" => {
+                if let Some(key) = unquote_php(text(value, source)) {
+                    push_field(model, key, None, element).primary_key = true;
+                }
+            }
+            // Default values of attributes.
+            "$attributes" => {
+                for (key, default) in elements(value) {
+                    if let Some(field) = key.and_then(|key| unquote_php(text(key, source))) {
+                        push_field(model, field, None, element).default = Some(values::php(default, source));
+                    }
+                }
+            }
+            "$casts" => {
+                for (key, cast) in elements(value) {
+                    if let Some(field) = key.and_then(|key| unquote_php(text(key, source))) {
+                        let field_type = unquote_php(text(cast, source)).or_else(|| class_name(cast, source));
+                        push_field(model, field, field_type, element);
+                    }
+                }
+            }
+            "$rules" => {
+                for (key, rules) in elements(value) {
+                    if let Some(field) = key.and_then(|key| unquote_php(text(key, source))) {
+                        for (rule, value) in self::rules(rules, source) {
+                            model.validations.push(Validation::new(&field, &rule, value, rules.byte_range()));
+                        }
+                    }
+                }
+            }
+            // Events dispatched to classes, like `'saved' => UserSaved::class`.
+            "$dispatchesEvents" => {
+                for (key, class) in elements(value) {
+                    if let Some(event) = key.and_then(|key| unquote_php(text(key, source))) {
+                        model.hooks.push(Hook::new(&event, class_name(class, source), class.byte_range()));
+                    }
+                }
+            }
+            _ if FIELDS.contains(&name) => {
+                for (_, field) in elements(value) {
+                    if let Some(field) = unquote_php(text(field, source)).filter(|field| field != "*") {
+                        if name == "$hidden" {
+                            model.hidden.push(field.clone());
+                        }
+                        push_field(model, field, None, element);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// Attributes are listed by several properties, so the first one declares the field and later ones may give its type.
+fn push_field<'a>(model: &'a mut Model, name: String, field_type: Option<String>, element: Node) -> &'a mut Field {
+    let index = match model.fields.iter().position(|field| field.name == name) {
+        Some(index) => index,
+        None => {
+            model.fields.push(Field::new(&name, Orm::Eloquent, None, element.byte_range()));
+            model.fields.len() - 1
+        }
+    };
+    let field = &mut model.fields[index];
+    if let (None, Some(field_type)) = (&field.field_type, field_type) {
+        field.data_type = types::data_type(Orm::Eloquent, &field_type);
+        field.field_type = Some(field_type);
+    }
+    field
+}
+
+fn read_method(model: &mut Model, method: Node, source: &[u8]) {
+    let name = method.child_by_field_name("name").map_or("", |name| text(name, source));
+    let body = match method.child_by_field_name("body") {
+        Some(body) => body,
+        None => return,
+    };
+
+    // Mutators run when attributes are set, like `setPasswordAttribute($value)` for `password`.
+    let is_mutator = name.strip_prefix("set").and_then(|name| name.strip_suffix("Attribute")).is_some_and(|attribute| !attribute.is_empty());
+    if is_mutator {
+        let mut hook = Hook::new("set", Some(String::from(name)), method.byte_range());
+        hook.definition = Some(method.byte_range());
+        model.hooks.push(hook);
+        return;
+    }
+
+    // Local scopes are called without their prefix, like `scopePopular($query)` for `Tag::popular()`.
+    if let Some(scope) = name.strip_prefix("scope").filter(|scope| scope.starts_with(|char: char| char.is_uppercase())) {
+        let mut chars = scope.chars();
+        let scope: String = chars.next().into_iter().flat_map(char::to_lowercase).chain(chars).collect();
+        model.scopes.push(Scope::new(&scope, None, method.byte_range()));
+        return;
+    }
+
+    // Events registered on boot, like `static::creating(function ($user) {})`.
+    if matches!(name, "boot" | "booted") {
+        visit(body, &mut |node| {
+            if node.kind() != "scoped_call_expression" {
+                return;
+            }
+            let scope = node.child_by_field_name("scope").map_or("", |scope| text(scope, source));
+            let event = node.child_by_field_name("name").map_or("", |event| text(event, source));
+            if !matches!(scope, "static" | "self") || !HOOKS.contains(&event) {
+                return;
+            }
+            let callback = arguments(node).first().and_then(|argument| unquote_php(text(*argument, source)));
+            model.hooks.push(Hook::new(event, callback, node.byte_range()));
+        });
+        return;
+    }
+
+    // Relations returned by methods, like `return $this->hasMany(Post::class);`.
+    let returned = children(body).into_iter()
+        .filter(|statement| statement.kind() == "return_statement")
+        .find_map(|statement| statement.named_child(0));
+    let mut call = match returned {
+        Some(call) => call,
+        None => return,
+    };
+    // Relations may be refined by chained calls, like `$this->belongsToMany(Role::class)->withTimestamps()`.
+    let mut chained = vec![];
+    while call.kind() == "member_call_expression" {
+        let object = match call.child_by_field_name("object") {
+            Some(object) => object,
+            None => return,
+        };
+        let method_name = call.child_by_field_name("name").map_or("", |method_name| text(method_name, source));
+        if text(object, source) == "$this" {
+            if let Some(mut relation) = relation_of(&model.name, name, method_name, &arguments(call), source) {
+                relation.range = method.byte_range();
+                for (option, arguments) in chained.into_iter().rev() {
+                    relation.options.insert(option, arguments);
+                }
+                model.relations.push(relation);
+            }
+            return;
+        }
+        chained.push((String::from(method_name), option(&arguments(call), source)));
+        call = object;
+    }
+}
+
+// Relation returned like `$this->hasMany(Post::class)`, with keys given by positional arguments or inferred from the names.
+fn relation_of(model: &str, name: &str, method_name: &str, arguments: &[Node], source: &[u8]) -> Option<Relation> {
+    let kind = match method_name {
+        "belongsTo" | "morphTo" => RelationKind::BelongsTo,
+        "hasOne" | "hasOneThrough" | "morphOne" => RelationKind::HasOne,
+        "hasMany" | "hasManyThrough" | "morphMany" => RelationKind::HasMany,
+        "belongsToMany" | "morphToMany" | "morphedByMany" => RelationKind::ManyToMany,
+        _ => return None,
+    };
+    let mut relation = Relation::new(name, kind, 0..0);
+    let argument = |index: usize| {
+        arguments.get(index).and_then(|argument| class_name(*argument, source).or_else(|| unquote_php(text(*argument, source))))
+    };
+
+    if method_name != "morphTo" {
+        relation.target = argument(0).map(|target| String::from(simple_name(&target)));
+    }
+    let key = format!("{}_id", underscore(model));
+    relation.foreign_key = match method_name {
+        "belongsTo" => argument(1).or_else(|| Some(format!("{}_id", underscore(name)))),
+        "morphTo" => argument(2).or_else(|| Some(format!("{}_id", argument(0).unwrap_or_else(|| underscore(name))))),
+        "morphOne" | "morphMany" | "morphToMany" | "morphedByMany" => argument(1).map(|morph| format!("{}_id", morph)),
+        "hasOneThrough" | "hasManyThrough" => argument(2).or(Some(key)),
+        "belongsToMany" => argument(2).or(Some(key)),
+        _ => argument(1).or(Some(key)),
+    };
+    relation.through = match method_name {
+        "hasOneThrough" | "hasManyThrough" => argument(1).map(|through| String::from(simple_name(&through))),
+        "belongsToMany" => argument(1),
+        "morphToMany" | "morphedByMany" => argument(2),
+        _ => None,
+    };
+    Some(relation)
+}
+
+// Arguments of chained call as option, like `withPivot('active', 'created_by')`.
+fn option(arguments: &[Node], source: &[u8]) -> Value {
+    match arguments {
+        [] => Value::Boolean(true),
+        [argument] => values::php(*argument, source),
+        _ => Value::Array(arguments.iter().map(|argument| values::php(*argument, source)).collect()),
+    }
+}
+
+// Keys and values of array literal, keys are missing for lists.
+fn elements<'a>(array: Node<'a>) -> Vec<(Option<Node<'a>>, Node<'a>)> {
+    if array.kind() != "array_creation_expression" {
+        return vec![];
+    }
+    children(array).into_iter()
+        .filter(|child| child.kind() == "array_element_initializer")
+        .filter_map(|element| {
+            let mut walker = element.walk();
+            let nodes: Vec<Node> = element.named_children(&mut walker).collect();
+            match nodes.as_slice() {
+                [value] => Some((None, *value)),
+                [key, value] => Some((Some(*key), *value)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+// Expressions passed to call, unwrapping the argument nodes.
+fn arguments(call: Node) -> Vec<Node> {
+    call.child_by_field_name("arguments")
+        .map(children)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|argument| argument.is_named())
+        .filter_map(|argument| match argument.kind() {
+            "argument" => argument.named_child(0),
+            _ => Some(argument),
+        })
+        .collect()
+}
+
+// Class of `Post::class`.
+fn class_name(node: Node, source: &[u8]) -> Option<String> {
+    if node.kind() != "class_constant_access_expression" {
+        return None;
+    }
+    let (class, constant) = (node.named_child(0)?, node.named_child(1)?);
+    if text(constant, source) != "class" {
+        return None;
+    }
+    Some(String::from(simple_name(text(class, source))))
+}
+
+// Rules given as a string like `'required|max:255'` or as an array of them, with their parameters.
+// Numeric parameters are taken as numbers, and the other ones are kept as written, like `users,email` for `unique:users,email`.
+fn rules(rules: Node, source: &[u8]) -> Vec<(String, Value)> {
+    let rules: Vec<String> = match rules.kind() {
+        "array_creation_expression" => elements(rules).into_iter().filter_map(|(_, rule)| unquote_php(text(rule, source))).collect(),
+        _ => unquote_php(text(rules, source)).into_iter().flat_map(|rules| rules.split('|').map(String::from).collect::<Vec<_>>()).collect(),
+    };
+    rules.iter()
+        .filter(|rule| !rule.trim().is_empty())
+        .map(|rule| match rule.trim().split_once(':') {
+            Some((name, parameters)) => {
+                let value = parameters.parse().map_or_else(|_| Value::String(String::from(parameters)), Value::Number);
+                (String::from(name), value)
+            }
+            None => (String::from(rule.trim()), Value::Boolean(true)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_fixture;
+    use crate::models::DataType;
+
+    #[test]
+    fn it_should_read_eloquent_models() {
+        let source = read_fixture("User.php");
+        let models = models(&source);
+
+        assert_eq!(models.len(), 1);
+        let user = &models[0];
+        assert_eq!(user.name, "User");
+        assert_eq!(user.namespace.as_deref(), Some("App\\Models"));
+        assert_eq!(user.table, None);
+
+        let fields: Vec<(&str, Option<&str>)> = user.fields.iter().map(|field| (field.name.as_str(), field.field_type.as_deref())).collect();
+        assert_eq!(fields, vec![("password", None), ("remember_token", None), ("email_verified_at", Some("datetime"))]);
+        assert_eq!(user.fields[2].data_type, DataType::DateTime);
+        assert_eq!(user.hidden, vec!["password", "remember_token"]);
+
+        assert_eq!(user.relations.len(), 1);
+        let posts = &user.relations[0];
+        assert_eq!(posts.name, "posts");
+        assert_eq!(posts.kind, RelationKind::HasMany);
+        assert_eq!(posts.target.as_deref(), Some("Post"));
+        assert_eq!(posts.foreign_key.as_deref(), Some("user_id"));
+    }
+
+    #[test]
+    fn it_should_read_rules_and_events() {
+        let source = b"<?php\nclass Tag extends Model {\n    protected $table = 'labels';\n    protected $primaryKey = 'slug';\n    protected $attributes = ['hits' => 0];\n    protected $casts = ['hits' => 'integer'];\n    public static $rules = ['name' => 'required|max:50'];\n    protected $dispatchesEvents = ['saved' => TagSaved::class];\n    protected static function booted() {\n        static::creating(function ($tag) {});\n    }\n    public function articles() {\n        return $this->belongsToMany(Article::class, 'taggings')->withTimestamps();\n    }\n    public function author() {\n        return $this->belongsTo('App\\\\Models\\\\User', 'created_by');\n    }\n    public function scopePopular($query) {\n        return $query->where('hits', '>', 100);\n    }\n}\n";
+        let models = models(source);
+
+        assert_eq!(models[0].table.as_deref(), Some("labels"));
+        let fields: Vec<_> = models[0].fields.iter().map(|field| (field.name.as_str(), field.data_type, field.primary_key, field.default.clone())).collect();
+        assert_eq!(fields, vec![("slug", DataType::Unknown, true, None), ("hits", DataType::Integer, false, Some(Value::Number(0.0)))]);
+        let validations: Vec<(&str, &Value)> = models[0].validations.iter().map(|validation| (validation.rule.as_str(), &validation.value)).collect();
+        assert_eq!(validations, vec![("required", &Value::Boolean(true)), ("max", &Value::Number(50.0))]);
+        let hooks: Vec<(&str, Option<&str>)> = models[0].hooks.iter().map(|hook| (hook.event.as_str(), hook.callback.as_deref())).collect();
+        assert_eq!(hooks, vec![("saved", Some("TagSaved")), ("creating", None)]);
+
+        let relations = &models[0].relations;
+        assert_eq!(relations[0].kind, RelationKind::ManyToMany);
+        assert_eq!(relations[0].through.as_deref(), Some("taggings"));
+        assert_eq!(relations[0].foreign_key.as_deref(), Some("tag_id"));
+        assert_eq!(relations[0].options.get("withTimestamps"), Some(&Value::Boolean(true)));
+        assert_eq!(relations[1].target.as_deref(), Some("User"));
+        assert_eq!(relations[1].foreign_key.as_deref(), Some("created_by"));
+        assert_eq!(relations.len(), 2);
+
+        let scopes: Vec<(&str, &[u8])> = models[0].scopes.iter().map(|scope| (scope.name.as_str(), &source[scope.range.start..scope.range.start + 29])).collect();
+        assert_eq!(scopes, vec![("popular", &b"public function scopePopular("[..])]);
+    }
+}
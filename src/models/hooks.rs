@@ -0,0 +1,77 @@
+use crate::line_number;
+use super::{Hook, Model};
+
+// Report of the hooks of models, linking each registration to the method defining its callback,
+// to audit the side effects of persistence.
+
+// Lines like `app/models/user.rb:14: User before_save downcase_email -> app/models/user.rb:116`,
+// for the models declared in the source.
+pub fn report(models: &[Model], source: &[u8]) -> String {
+    let mut lines = vec![];
+    for model in models {
+        for hook in &model.hooks {
+            let registration = format!("{}:{}: {} {}", model.file, line_number(source, hook.range.start), model.name, hook.event);
+            lines.push(format!("{} {}", registration, callback(hook, model, source)));
+        }
+    }
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+fn callback(hook: &Hook, model: &Model, source: &[u8]) -> String {
+    match (&hook.callback, &hook.definition) {
+        (Some(callback), Some(definition)) => format!("{} -> {}:{}", callback, model.file, line_number(source, definition.start)),
+        // Callbacks defined elsewhere, like inherited methods or classes of events.
+        (Some(callback), None) => format!("{} -> (not defined in file)", callback),
+        (None, _) => String::from("(inline)"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_fixture;
+    use crate::models::models;
+
+    fn report_of(file_name: &str, source: &[u8]) -> String {
+        report(&models(file_name, source).unwrap(), source)
+    }
+
+    #[test]
+    fn it_should_link_activerecord_callbacks() {
+        let source = read_fixture("user.rb");
+        assert_eq!(report_of("app/models/user.rb", &source), "\
+app/models/user.rb:14: User before_save downcase_email -> app/models/user.rb:116
+app/models/user.rb:15: User before_create create_activation_digest -> app/models/user.rb:121
+");
+
+        let source = b"class Post < ApplicationRecord\n  after_commit do\n  end\n  after_save :touch_author\nend\n";
+        assert_eq!(report_of("post.rb", source), "\
+post.rb:2: Post after_commit (inline)
+post.rb:4: Post after_save touch_author -> (not defined in file)
+");
+    }
+
+    #[test]
+    fn it_should_link_decorated_and_overridden_callbacks() {
+        let source = b"@Entity()\nclass Account {\n  @BeforeInsert()\n  @BeforeUpdate()\n  normalize() {}\n}\n";
+        assert_eq!(report_of("Account.ts", source), "\
+Account.ts:3: Account BeforeInsert normalize -> Account.ts:5
+Account.ts:4: Account BeforeUpdate normalize -> Account.ts:5
+");
+
+        let source = b"class Person extends Model {\n  $beforeInsert() {\n    this.createdAt = new Date()\n  }\n}\n";
+        assert_eq!(report_of("Person.js", source), "Person.js:2: Person beforeInsert $beforeInsert -> Person.js:2\n");
+
+        let source = b"class Question(models.Model):\n    def save(self, *args, **kwargs):\n        super().save(*args, **kwargs)\n\n@receiver(post_save, sender=Question)\ndef notify(sender, instance, **kwargs):\n    pass\n";
+        assert_eq!(report_of("polls/models.py", source), "\
+polls/models.py:2: Question save save -> polls/models.py:2
+polls/models.py:5: Question post_save notify -> polls/models.py:6
+");
+    }
+
+    #[test]
+    fn it_should_link_eloquent_mutators() {
+        let source = read_fixture("User.php");
+        assert_eq!(report_of("app/Models/User.php", &source), "app/Models/User.php:36: User set setPasswordAttribute -> app/Models/User.php:36\n");
+    }
+}
@@ -0,0 +1,314 @@
+use std::path::Path;
+use tree_sitter::{Language, Node};
+use crate::hierarchy::simple_name;
+use crate::syntax::{children, parse, text, visit};
+use super::{constants, types, unquote, values, DataType, Field, Hook, Model, Orm, Relation, RelationKind, Validation, Value};
+
+// Objection.js models, which extend `Model` and describe themselves by static properties.
+
+const HOOKS: [&str; 9] = [
+    "$beforeInsert", "$afterInsert", "$beforeUpdate", "$afterUpdate", "$beforeDelete", "$afterDelete",
+    "$afterFind", "$beforeValidate", "$afterValidate",
+];
+
+// JSON Schema keywords validating the values of properties.
+const VALIDATIONS: [&str; 10] = [
+    "minLength", "maxLength", "pattern", "format", "enum", "minimum", "maximum", "exclusiveMinimum", "exclusiveMaximum", "multipleOf",
+];
+
+pub fn models(language: Language, source: &[u8]) -> Vec<Model> {
+    let tree = parse(language, source);
+    let constants = constants::javascript(tree.root_node(), source);
+
+    let mut models = vec![];
+    visit(tree.root_node(), &mut |node| {
+        if !matches!(node.kind(), "class_declaration" | "abstract_class_declaration") {
+            return;
+        }
+        let (name, body) = match (node.child_by_field_name("name"), node.child_by_field_name("body")) {
+            (Some(name), Some(body)) if superclass(node, source) == Some("Model") => (name, body),
+            _ => return,
+        };
+
+        let mut model = Model::new(text(name, source), Orm::Objection, node.byte_range());
+        // Objection identifies models by `id` unless `idColumn` names other columns.
+        let mut id_columns = vec![String::from("id")];
+        for member in children(body) {
+            let name = match member.child_by_field_name("name").or_else(|| member.child_by_field_name("property")) {
+                Some(name) => text(name, source),
+                None => continue,
+            };
+            if HOOKS.contains(&name) && member.kind() == "method_definition" {
+                let mut hook = Hook::new(name.trim_start_matches('$'), Some(String::from(name)), member.byte_range());
+                hook.definition = Some(member.byte_range());
+                model.hooks.push(hook);
+                continue;
+            }
+            let value = match static_value(member) {
+                Some(value) => value,
+                None => continue,
+            };
+            match name {
+                "tableName" => model.table = unquote(text(value, source)),
+                "idColumn" => id_columns = items(value, source),
+                "jsonSchema" => read_schema(&mut model, value, source),
+                "relationMappings" => read_relations(&mut model, value, source),
+                _ => {}
+            }
+        }
+        for field in model.fields.iter_mut().filter(|field| id_columns.contains(&field.name)) {
+            field.primary_key = true;
+            field.nullable = false;
+        }
+        model.resolve(&constants);
+        models.push(model);
+    });
+    models
+}
+
+// Simple name of the class extended by the class.
+fn superclass<'a>(class: Node, source: &'a [u8]) -> Option<&'a str> {
+    let heritage = children(class).into_iter().find(|child| child.kind() == "class_heritage")?;
+    let clause = heritage.named_child(0)?;
+    // TypeScript wraps the superclass in the extends clause.
+    let superclass = match clause.kind() {
+        "extends_clause" => clause.named_child(0)?,
+        _ => clause,
+    };
+    Some(simple_name(text(superclass, source)))
+}
+
+// Value of static property, given by a getter like `static get tableName() { return 'animals' }` or a field like `static tableName = 'animals'`.
+fn static_value(member: Node) -> Option<Node> {
+    if !children(member).iter().any(|child| child.kind() == "static") {
+        return None;
+    }
+    let value = match member.kind() {
+        "method_definition" => returned(member.child_by_field_name("body")?)?,
+        "field_definition" | "public_field_definition" => member.child_by_field_name("value")?,
+        _ => return None,
+    };
+    Some(unwrap(value))
+}
+
+// Object returned by function or arrow function, like `() => ({ ... })`.
+fn unwrap(node: Node) -> Node {
+    match node.kind() {
+        "parenthesized_expression" => node.named_child(0).map_or(node, unwrap),
+        "arrow_function" | "function" => match node.child_by_field_name("body") {
+            Some(body) if body.kind() == "statement_block" => returned(body).map_or(node, unwrap),
+            Some(body) => unwrap(body),
+            None => node,
+        },
+        _ => node,
+    }
+}
+
+fn returned(block: Node) -> Option<Node> {
+    children(block).into_iter()
+        .filter(|statement| statement.kind() == "return_statement")
+        .find_map(|statement| statement.named_child(0))
+}
+
+// Fields and validations of JSON Schema, like `{ required: ['name'], properties: { name: { type: 'string' } } }`.
+fn read_schema(model: &mut Model, schema: Node, source: &[u8]) {
+    let mut required = vec![];
+    for (key, value) in pairs(schema, source) {
+        match key.as_str() {
+            "required" => {
+                for field in items(value, source) {
+                    let validation = Validation::new(&field, "required", Value::Boolean(true), value.byte_range());
+                    model.validations.push(validation);
+                    required.push(field);
+                }
+            }
+            "properties" => {
+                for (name, property) in pairs(value, source) {
+                    let mut field = Field::new(&name, Orm::Objection, None, property.byte_range());
+                    let mut format = None;
+                    for (keyword, value) in pairs(property, source) {
+                        match keyword.as_str() {
+                            "type" => {
+                                // Nullable properties list `null` among their types.
+                                let types = items(value, source);
+                                field.nullable = types.iter().any(|field_type| field_type == "null");
+                                field.field_type = types.into_iter().find(|field_type| field_type != "null");
+                            }
+                            "format" => format = items(value, source).into_iter().next(),
+                            "maxLength" => field.length = values::javascript(value, source).as_length(),
+                            "default" => field.default = Some(values::javascript(value, source)),
+                            _ => {}
+                        }
+                        if VALIDATIONS.contains(&keyword.as_str()) {
+                            let validation = Validation::new(&name, &keyword, values::javascript(value, source), value.byte_range());
+                            model.validations.push(validation);
+                        }
+                    }
+                    // Formats refine strings, like `date-time` or `uuid`.
+                    let data_type = |declared: Option<&str>| declared.map_or(DataType::Unknown, |declared| types::data_type(Orm::Objection, declared));
+                    field.data_type = match data_type(format.as_deref()) {
+                        DataType::Unknown => data_type(field.field_type.as_deref()),
+                        data_type => data_type,
+                    };
+                    model.fields.push(field);
+                }
+            }
+            _ => {}
+        }
+    }
+    // Properties which are not required may be missing, which are stored as null.
+    for field in model.fields.iter_mut().filter(|field| !required.contains(&field.name)) {
+        field.nullable = true;
+    }
+}
+
+// Relations like `owner: { relation: Model.BelongsToOneRelation, modelClass: Person, join: { from: 'animals.ownerId', to: 'persons.id' } }`.
+fn read_relations(model: &mut Model, mappings: Node, source: &[u8]) {
+    for (name, mapping) in pairs(mappings, source) {
+        let mut kind = None;
+        let mut relation = Relation::new(&name, RelationKind::HasMany, mapping.byte_range());
+        for (key, value) in pairs(mapping, source) {
+            match key.as_str() {
+                "relation" => {
+                    kind = match simple_name(text(value, source)) {
+                        "BelongsToOneRelation" => Some(RelationKind::BelongsTo),
+                        "HasOneRelation" | "HasOneThroughRelation" => Some(RelationKind::HasOne),
+                        "HasManyRelation" => Some(RelationKind::HasMany),
+                        "ManyToManyRelation" => Some(RelationKind::ManyToMany),
+                        _ => None,
+                    }
+                }
+                "modelClass" => relation.target = model_class(value, source),
+                _ => {
+                    relation.options.insert(key, values::javascript(value, source));
+                }
+            }
+        }
+        let kind = match kind {
+            Some(kind) => kind,
+            None => continue,
+        };
+        relation.kind = kind;
+
+        // Columns are referred with their tables, like `animals.ownerId`.
+        let join = relation.options.get("join");
+        let column = |reference: Option<&values::Value>| {
+            reference.and_then(|reference| reference.as_str()).map(|reference| String::from(reference.rsplit('.').next().unwrap_or(reference)))
+        };
+        let through = join.and_then(|join| join.get("through"));
+        relation.foreign_key = match kind {
+            RelationKind::BelongsTo => column(join.and_then(|join| join.get("from"))),
+            _ if through.is_some() => column(through.and_then(|through| through.get("from"))),
+            _ => column(join.and_then(|join| join.get("to"))),
+        };
+        relation.through = through
+            .and_then(|through| through.get("from"))
+            .and_then(|from| from.as_str())
+            .and_then(|from| from.split_once('.'))
+            .map(|(table, _)| String::from(table));
+        model.relations.push(relation);
+    }
+}
+
+// Model class given by class, path of its module or required module, like `Person`, `'./Person'` or `require('./Person')`.
+fn model_class(node: Node, source: &[u8]) -> Option<String> {
+    match node.kind() {
+        "identifier" | "member_expression" => Some(String::from(simple_name(text(node, source)))),
+        "string" => {
+            let path = unquote(text(node, source))?;
+            Some(String::from(Path::new(&path).file_stem()?.to_str()?))
+        }
+        "call_expression" => model_class(node.child_by_field_name("arguments")?.named_child(0)?, source),
+        // Paths relative to the module, like `__dirname + '/Movie'`.
+        "binary_expression" => model_class(node.child_by_field_name("right")?, source),
+        _ => None,
+    }
+}
+
+// Keys and values of object literal.
+fn pairs<'a>(object: Node<'a>, source: &[u8]) -> Vec<(String, Node<'a>)> {
+    if object.kind() != "object" {
+        return vec![];
+    }
+    children(object).into_iter()
+        .filter_map(|pair| match pair.kind() {
+            "pair" => {
+                let key = pair.child_by_field_name("key")?;
+                let key = match key.kind() {
+                    "string" => unquote(text(key, source))?,
+                    _ => String::from(text(key, source)),
+                };
+                Some((key, pair.child_by_field_name("value")?))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+// Strings given alone or listed in array, like `'string'` or `['integer', 'null']`.
+fn items(node: Node, source: &[u8]) -> Vec<String> {
+    match node.kind() {
+        "array" => children(node).into_iter().filter_map(|item| unquote(text(item, source))).collect(),
+        _ => unquote(text(node, source)).into_iter().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_fixture;
+
+    #[test]
+    fn it_should_read_objection_models() {
+        let source = read_fixture("Animal.js");
+        let models = models(tree_sitter_javascript::language(), &source);
+
+        assert_eq!(models.len(), 1);
+        let animal = &models[0];
+        assert_eq!(animal.name, "Animal");
+        assert_eq!(animal.table.as_deref(), Some("animals"));
+
+        let fields: Vec<(&str, Option<&str>)> = animal.fields.iter().map(|field| (field.name.as_str(), field.field_type.as_deref())).collect();
+        assert_eq!(fields, vec![("id", Some("integer")), ("ownerId", Some("integer")), ("name", Some("string")), ("species", Some("string"))]);
+        let flags: Vec<_> = animal.fields.iter().map(|field| (field.data_type, field.length, field.nullable, field.primary_key)).collect();
+        assert_eq!(flags, vec![
+            (DataType::Integer, None, false, true),
+            (DataType::Integer, None, true, false),
+            (DataType::String, Some(255), false, false),
+            (DataType::String, Some(255), true, false),
+        ]);
+
+        let validations: Vec<(&str, &str)> = animal.validations.iter().map(|validation| (validation.field.as_str(), validation.rule.as_str())).collect();
+        assert_eq!(validations, vec![
+            ("name", "required"),
+            ("name", "minLength"),
+            ("name", "maxLength"),
+            ("species", "minLength"),
+            ("species", "maxLength"),
+        ]);
+
+        assert_eq!(animal.relations.len(), 1);
+        let owner = &animal.relations[0];
+        assert_eq!(owner.name, "owner");
+        assert_eq!(owner.kind, RelationKind::BelongsTo);
+        assert_eq!(owner.target.as_deref(), Some("Person"));
+        assert_eq!(owner.foreign_key.as_deref(), Some("ownerId"));
+    }
+
+    #[test]
+    fn it_should_read_static_fields_and_hooks() {
+        let source = b"class Person extends objection.Model {\n  static tableName = 'persons';\n  static relationMappings = () => ({\n    pets: { relation: Model.HasManyRelation, modelClass: Animal, join: { from: 'persons.id', to: 'animals.ownerId' } },\n    movies: { relation: Model.ManyToManyRelation, modelClass: __dirname + '/Movie', join: { from: 'persons.id', through: { from: 'persons_movies.personId', to: 'persons_movies.movieId' }, to: 'movies.id' } },\n  });\n  $beforeInsert() {}\n}\n";
+        let models = models(tree_sitter_typescript::language_typescript(), source);
+
+        assert_eq!(models[0].table.as_deref(), Some("persons"));
+        let relations = &models[0].relations;
+        assert_eq!(relations[0].kind, RelationKind::HasMany);
+        assert_eq!(relations[0].foreign_key.as_deref(), Some("ownerId"));
+        assert_eq!(relations[1].kind, RelationKind::ManyToMany);
+        assert_eq!(relations[1].target.as_deref(), Some("Movie"));
+        assert_eq!(relations[1].foreign_key.as_deref(), Some("personId"));
+        assert_eq!(relations[1].through.as_deref(), Some("persons_movies"));
+        assert_eq!(models[0].hooks[0].event, "beforeInsert");
+        assert_eq!(models[0].hooks[0].callback.as_deref(), Some("$beforeInsert"));
+    }
+}
@@ -0,0 +1,372 @@
+use indexmap::IndexMap;
+use super::{Model, Orm, Validation, Value};
+
+// Validation rules of fields normalized across ORMs, like the ones client-side validators check.
+// Rules which can't be expressed this way, like custom validators or conditions, are left out.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    Required,
+    MinLength(u32),
+    MaxLength(u32),
+    Minimum(f64),
+    Maximum(f64),
+    // Regular expression in JavaScript syntax, like `^[\w+\-.]+@[a-z\d\-.]+\.[a-z]+$` with the flag `i`.
+    Pattern { pattern: String, flags: String },
+    // Format of strings named like JSON Schema does, like `email`, `uri`, `uuid` or `date-time`.
+    Format(String),
+    OneOf(Vec<Value>),
+    // Uniqueness among the rows of the table, which only servers can check.
+    Unique,
+}
+
+// Rules of the fields of model, in the order of their first validations.
+pub fn rules(model: &Model) -> IndexMap<String, Vec<Rule>> {
+    let mut rules: IndexMap<String, Vec<Rule>> = IndexMap::new();
+    let mut push = |field: &str, rule: Rule| {
+        let field_rules = rules.entry(String::from(field)).or_default();
+        if !field_rules.contains(&rule) {
+            field_rules.push(rule);
+        }
+    };
+
+    if model.orm == Orm::Django {
+        // Django fields are required by forms unless they are declared with `blank=True`.
+        for field in model.fields.iter().filter(|field| !field.primary_key) {
+            let blank = model.validations.iter().any(|validation| validation.field == field.name && validation.rule == "blank" && validation.value.is_true());
+            if !blank {
+                push(&field.name, Rule::Required);
+            }
+            let format = match field.field_type.as_deref() {
+                Some("EmailField") => "email",
+                Some("URLField") => "uri",
+                Some("UUIDField") => "uuid",
+                _ => continue,
+            };
+            push(&field.name, Rule::Format(String::from(format)));
+        }
+    }
+
+    for validation in &model.validations {
+        let validation_rules = match model.orm {
+            Orm::ActiveRecord => activerecord(validation),
+            Orm::Django => django(validation),
+            Orm::Eloquent => eloquent(validation, model),
+            Orm::Objection => objection(validation),
+            Orm::TypeOrm => typeorm(validation),
+        };
+        for rule in validation_rules {
+            push(&validation.field, rule);
+        }
+    }
+    rules
+}
+
+// `validates :email, presence: true, length: { maximum: 255 }, format: { with: VALID_EMAIL_REGEX }`
+fn activerecord(validation: &Validation) -> Vec<Rule> {
+    let value = &validation.value;
+    if *value == Value::Boolean(false) {
+        return vec![];
+    }
+    let option = |key: &str| value.get(key);
+    // Options are given to all the rules of `validates`, or to one of them, like `length: { minimum: 6, allow_nil: true }`.
+    let validation_option = |key: &str| validation.options.get(key).or_else(|| option(key));
+    if ["if", "unless", "on", "except_on"].into_iter().any(|key| validation_option(key).is_some()) {
+        return vec![];
+    }
+    let allows_empty = ["allow_nil", "allow_blank"].into_iter().any(|key| validation_option(key).is_some_and(Value::is_true));
+    match validation.rule.as_str() {
+        "presence" if allows_empty => vec![],
+        "presence" => vec![Rule::Required],
+        "uniqueness" => vec![Rule::Unique],
+        "length" => {
+            let mut rules = vec![];
+            let (minimum, maximum) = match option("in").or_else(|| option("within")).and_then(range) {
+                // Exclusive ranges, like `6...20`, leave their end out.
+                Some((minimum, maximum, exclusive)) => (Some(minimum), Some(if exclusive { maximum - 1.0 } else { maximum })),
+                None => (option("minimum").or_else(|| option("is")).and_then(Value::as_number), option("maximum").or_else(|| option("is")).and_then(Value::as_number)),
+            };
+            rules.extend(minimum.and_then(length).map(Rule::MinLength));
+            rules.extend(maximum.and_then(length).map(Rule::MaxLength));
+            rules
+        }
+        "numericality" => {
+            let mut rules = vec![];
+            rules.extend(option("greater_than_or_equal_to").and_then(Value::as_number).map(Rule::Minimum));
+            rules.extend(option("less_than_or_equal_to").and_then(Value::as_number).map(Rule::Maximum));
+            if let Some((minimum, maximum, exclusive)) = option("in").and_then(range) {
+                rules.push(Rule::Minimum(minimum));
+                // Exclusive ends of numbers can't be expressed by maximums.
+                if !exclusive {
+                    rules.push(Rule::Maximum(maximum));
+                }
+            }
+            rules
+        }
+        "format" => option("with").and_then(pattern).into_iter().collect(),
+        "inclusion" => match option("in") {
+            Some(Value::Array(items)) => vec![Rule::OneOf(items.clone())],
+            _ => vec![],
+        },
+        _ => vec![],
+    }
+}
+
+// `max_length=200`, `choices=STATUSES` and `validators=[MinLengthValidator(8)]`
+fn django(validation: &Validation) -> Vec<Rule> {
+    let value = &validation.value;
+    let number = || value.as_number();
+    match validation.rule.as_str() {
+        "max_length" | "MaxLengthValidator" => number().and_then(length).map(Rule::MaxLength).into_iter().collect(),
+        "MinLengthValidator" => number().and_then(length).map(Rule::MinLength).into_iter().collect(),
+        "MinValueValidator" => number().map(Rule::Minimum).into_iter().collect(),
+        "MaxValueValidator" => number().map(Rule::Maximum).into_iter().collect(),
+        "unique" if value.is_true() => vec![Rule::Unique],
+        "RegexValidator" => pattern(value).into_iter().collect(),
+        "validate_email" | "EmailValidator" => vec![Rule::Format(String::from("email"))],
+        "URLValidator" => vec![Rule::Format(String::from("uri"))],
+        // Choices are pairs of values and labels, like `[('d', 'Draft'), ('p', 'Published')]`.
+        "choices" => match value {
+            Value::Array(choices) => {
+                let values = choices.iter().map(|choice| match choice {
+                    Value::Array(pair) => pair.first().cloned().unwrap_or(Value::Null),
+                    _ => choice.clone(),
+                });
+                vec![Rule::OneOf(values.collect())]
+            }
+            _ => vec![],
+        },
+        _ => vec![],
+    }
+}
+
+// `'email' => 'required|email|max:255'`, where sizes are lengths unless the field is numeric.
+fn eloquent(validation: &Validation, model: &Model) -> Vec<Rule> {
+    let numeric = model.validations.iter().any(|other| other.field == validation.field && matches!(other.rule.as_str(), "numeric" | "integer"));
+    let parameters: Vec<&str> = validation.value.as_str().map(|parameters| parameters.split(',').collect()).unwrap_or_default();
+    let number = || validation.value.as_number();
+    let bound = |number: f64, maximum: bool| match (numeric, maximum) {
+        (true, false) => Some(Rule::Minimum(number)),
+        (true, true) => Some(Rule::Maximum(number)),
+        (false, false) => length(number).map(Rule::MinLength),
+        (false, true) => length(number).map(Rule::MaxLength),
+    };
+    match validation.rule.as_str() {
+        "required" => vec![Rule::Required],
+        "unique" => vec![Rule::Unique],
+        "email" => vec![Rule::Format(String::from("email"))],
+        "url" => vec![Rule::Format(String::from("uri"))],
+        "uuid" => vec![Rule::Format(String::from("uuid"))],
+        "min" => number().and_then(|number| bound(number, false)).into_iter().collect(),
+        "max" => number().and_then(|number| bound(number, true)).into_iter().collect(),
+        "size" => number().into_iter().flat_map(|number| [bound(number, false), bound(number, true)]).flatten().collect(),
+        "between" => match parameters.as_slice() {
+            [minimum, maximum] => {
+                let bounds = [(minimum, false), (maximum, true)];
+                bounds.into_iter().filter_map(|(number, maximum)| bound(number.trim().parse().ok()?, maximum)).collect()
+            }
+            _ => vec![],
+        },
+        "in" => vec![Rule::OneOf(parameters.into_iter().map(|item| Value::String(String::from(item))).collect())],
+        // Patterns are delimited like PHP ones, like `regex:/^[a-z]+$/i`.
+        "regex" => match validation.value.as_str() {
+            Some(regex) => match (regex.strip_prefix('/'), regex.rfind('/')) {
+                (Some(_), Some(end)) if end > 0 => {
+                    vec![Rule::Pattern { pattern: String::from(&regex[1..end]), flags: String::from(&regex[end + 1..]) }]
+                }
+                _ => vec![],
+            },
+            None => vec![],
+        },
+        _ => vec![],
+    }
+}
+
+// JSON Schema keywords, like `required: ['name']` and `{ type: 'string', minLength: 1, maxLength: 255 }`.
+fn objection(validation: &Validation) -> Vec<Rule> {
+    let value = &validation.value;
+    let rule = match validation.rule.as_str() {
+        "required" => Some(Rule::Required),
+        "minLength" => value.as_number().and_then(length).map(Rule::MinLength),
+        "maxLength" => value.as_number().and_then(length).map(Rule::MaxLength),
+        "minimum" => value.as_number().map(Rule::Minimum),
+        "maximum" => value.as_number().map(Rule::Maximum),
+        "pattern" => pattern(value),
+        "format" => value.as_str().map(|format| Rule::Format(String::from(format))),
+        "enum" => match value {
+            Value::Array(items) => Some(Rule::OneOf(items.clone())),
+            _ => None,
+        },
+        _ => None,
+    };
+    rule.into_iter().collect()
+}
+
+// Decorators of class-validator, like `@IsNotEmpty()`, `@Length(6, 20)` or `@Matches(/^[a-z]+$/)`.
+fn typeorm(validation: &Validation) -> Vec<Rule> {
+    let value = &validation.value;
+    let arguments = match value {
+        Value::Array(arguments) if validation.rule != "IsIn" => arguments.clone(),
+        _ => vec![value.clone()],
+    };
+    let number = |index: usize| arguments.get(index).and_then(Value::as_number);
+    match validation.rule.as_str() {
+        "IsNotEmpty" | "IsDefined" => vec![Rule::Required],
+        "MinLength" => number(0).and_then(length).map(Rule::MinLength).into_iter().collect(),
+        "MaxLength" => number(0).and_then(length).map(Rule::MaxLength).into_iter().collect(),
+        "Length" => {
+            let mut rules: Vec<Rule> = number(0).and_then(length).map(Rule::MinLength).into_iter().collect();
+            rules.extend(number(1).and_then(length).map(Rule::MaxLength));
+            rules
+        }
+        "Min" => number(0).map(Rule::Minimum).into_iter().collect(),
+        "Max" => number(0).map(Rule::Maximum).into_iter().collect(),
+        // Patterns given by strings may be followed by their flags, like `@Matches('^[a-z]+$', 'i')`.
+        "Matches" => match (arguments.first(), arguments.get(1).and_then(Value::as_str)) {
+            (Some(Value::String(pattern)), Some(flags)) => vec![Rule::Pattern { pattern: pattern.clone(), flags: String::from(flags) }],
+            (Some(regex), _) => self::pattern(regex).into_iter().collect(),
+            _ => vec![],
+        },
+        "IsEmail" => vec![Rule::Format(String::from("email"))],
+        "IsUrl" => vec![Rule::Format(String::from("uri"))],
+        "IsUUID" => vec![Rule::Format(String::from("uuid"))],
+        "IsDateString" | "IsISO8601" => vec![Rule::Format(String::from("date-time"))],
+        "IsIn" => match value {
+            Value::Array(items) => vec![Rule::OneOf(items.clone())],
+            _ => vec![],
+        },
+        _ => vec![],
+    }
+}
+
+// Pattern of regular expression or string, translating the anchors of Ruby and Python, like `\A` and `\z`, to JavaScript ones.
+fn pattern(value: &Value) -> Option<Rule> {
+    let (pattern, flags) = match value {
+        Value::Regex { pattern, flags } => (pattern, flags.as_str()),
+        Value::String(pattern) => (pattern, ""),
+        _ => return None,
+    };
+    let mut translated = String::new();
+    let mut chars = pattern.chars();
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            translated.push(char);
+            continue;
+        }
+        match chars.next() {
+            Some('A') => translated.push('^'),
+            Some('z' | 'Z') => translated.push('$'),
+            Some('h') => translated.push_str("[0-9a-fA-F]"),
+            Some(escaped) => {
+                translated.push('\\');
+                translated.push(escaped);
+            }
+            None => translated.push('\\'),
+        }
+    }
+    // Ruby `m` lets dots match newlines like JavaScript `s` does, and extended patterns can't be translated.
+    let flags = flags.chars()
+        .filter_map(|flag| match flag {
+            'm' => Some('s'),
+            'i' | 'u' | 's' | 'g' | 'y' => Some(flag),
+            _ => None,
+        })
+        .collect();
+    Some(Rule::Pattern { pattern: translated, flags })
+}
+
+fn length(number: f64) -> Option<u32> {
+    Value::Number(number).as_length()
+}
+
+// Bounds of Ruby range, like `6..20`, and whether it excludes its end, like `6...20`.
+fn range(value: &Value) -> Option<(f64, f64, bool)> {
+    let text = value.as_text()?;
+    let ((minimum, maximum), exclusive) = match text.split_once("...") {
+        Some(bounds) => (bounds, true),
+        None => (text.split_once("..")?, false),
+    };
+    Some((minimum.trim().parse().ok()?, maximum.trim().parse().ok()?, exclusive))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_fixture;
+    use crate::models::models;
+
+    fn rules_of(file_name: &str, source: &[u8]) -> Vec<IndexMap<String, Vec<Rule>>> {
+        models(file_name, source).unwrap().iter().map(rules).collect()
+    }
+
+    fn pattern(pattern: &str, flags: &str) -> Rule {
+        Rule::Pattern { pattern: String::from(pattern), flags: String::from(flags) }
+    }
+
+    #[test]
+    fn it_should_normalize_activerecord_rules() {
+        let source = read_fixture("user.rb");
+        let rules = &rules_of("user.rb", &source)[0];
+
+        assert_eq!(rules["name"], vec![Rule::Required, Rule::MaxLength(50)]);
+        assert_eq!(rules["email"], vec![
+            Rule::Required,
+            Rule::MaxLength(255),
+            pattern("^[\\w+\\-.]+@[a-z\\d\\-.]+\\.[a-z]+$", "i"),
+            Rule::Unique,
+        ]);
+        // `allow_nil: true` lets users keep their passwords when they update their profiles.
+        assert_eq!(rules["password"], vec![Rule::MinLength(6)]);
+
+        let source = b"class Order < ApplicationRecord\n  KINDS = ['retail', 'wholesale']\n  validates :code, length: { in: 6..20 }, presence: false\n  validates :kind, inclusion: { in: KINDS }\n  validates_numericality_of :total, greater_than_or_equal_to: 0\n  validates :token, length: { in: 6...20 }\n  validates :nick, presence: true, if: :admin?\nend\n";
+        let rules = &rules_of("order.rb", source)[0];
+        assert_eq!(rules["code"], vec![Rule::MinLength(6), Rule::MaxLength(20)]);
+        assert_eq!(rules["kind"], vec![Rule::OneOf(vec![Value::String(String::from("retail")), Value::String(String::from("wholesale"))])]);
+        assert_eq!(rules["total"], vec![Rule::Minimum(0.0)]);
+        assert_eq!(rules["token"], vec![Rule::MinLength(6), Rule::MaxLength(19)]);
+        assert!(!rules.contains_key("nick"));
+    }
+
+    #[test]
+    fn it_should_normalize_objection_rules() {
+        let source = read_fixture("Animal.js");
+        let rules = &rules_of("Animal.js", &source)[0];
+
+        assert_eq!(rules["name"], vec![Rule::Required, Rule::MinLength(1), Rule::MaxLength(255)]);
+        assert_eq!(rules["species"], vec![Rule::MinLength(1), Rule::MaxLength(255)]);
+    }
+
+    #[test]
+    fn it_should_normalize_django_rules() {
+        let source = read_fixture("models.py");
+        let rules = &rules_of("polls/models.py", &source);
+        assert_eq!(rules[0]["question_text"], vec![Rule::Required, Rule::MaxLength(200)]);
+        assert_eq!(rules[1]["votes"], vec![Rule::Required]);
+
+        let source = b"SLUG = r'\\A[-a-z0-9]+\\Z'\nSTATUSES = [('d', 'Draft'), ('p', 'Published')]\n\nclass Post(models.Model):\n    slug = models.CharField(max_length=50, unique=True, validators=[RegexValidator(SLUG), MinLengthValidator(3)])\n    status = models.CharField(max_length=1, choices=STATUSES)\n    contact = models.EmailField(blank=True)\n";
+        let rules = &rules_of("blog/models.py", source)[0];
+        assert_eq!(rules["slug"], vec![Rule::Required, Rule::MaxLength(50), Rule::Unique, pattern("^[-a-z0-9]+$", ""), Rule::MinLength(3)]);
+        assert_eq!(rules["status"], vec![Rule::Required, Rule::MaxLength(1), Rule::OneOf(vec![Value::String(String::from("d")), Value::String(String::from("p"))])]);
+        assert_eq!(rules["contact"], vec![Rule::Format(String::from("email"))]);
+    }
+
+    #[test]
+    fn it_should_normalize_typeorm_rules() {
+        let source = b"const USERNAME = /^[a-z]+$/i;\n@Entity()\nexport class Account {\n  @Column()\n  @IsNotEmpty()\n  @Length(3, 20)\n  @Matches(USERNAME)\n  username: string\n  @Column()\n  @IsEmail()\n  @IsIn(['a@b.c'])\n  email: string\n  @Column()\n  @Min(0)\n  @Max(120)\n  age: number\n}\n";
+        let rules = &rules_of("Account.ts", source)[0];
+
+        assert_eq!(rules["username"], vec![Rule::Required, Rule::MinLength(3), Rule::MaxLength(20), pattern("^[a-z]+$", "i")]);
+        assert_eq!(rules["email"], vec![Rule::Format(String::from("email")), Rule::OneOf(vec![Value::String(String::from("a@b.c"))])]);
+        assert_eq!(rules["age"], vec![Rule::Minimum(0.0), Rule::Maximum(120.0)]);
+    }
+
+    #[test]
+    fn it_should_normalize_eloquent_rules() {
+        let source = b"<?php\nclass User extends Model {\n    public static $rules = [\n        'email' => 'required|email|max:255|unique:users,email',\n        'age' => ['integer', 'between:18,120'],\n        'role' => 'in:admin,editor',\n    ];\n}\n";
+        let rules = &rules_of("User.php", source)[0];
+
+        assert_eq!(rules["email"], vec![Rule::Required, Rule::Format(String::from("email")), Rule::MaxLength(255), Rule::Unique]);
+        assert_eq!(rules["age"], vec![Rule::Minimum(18.0), Rule::Maximum(120.0)]);
+        assert_eq!(rules["role"], vec![Rule::OneOf(vec![Value::String(String::from("admin")), Value::String(String::from("editor"))])]);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::hierarchy::simple_name;
+use super::{Model, Scope};
+
+// Resolution of references to scopes, like `User.active` to `scope :active, -> { ... }`.
+
+// Scope referred like `User.active`, `User::active()`, `Question.objects.published()` or `Post.published.recent`,
+// whose last name is resolved on the model, or on the manager or repository providing it, like `PostRepository.findPublished()`.
+pub fn resolve<'a>(models: &'a [Model], reference: &str) -> Option<(&'a Model, &'a Scope)> {
+    let names = names(reference);
+    let (first, last) = (*names.first()?, *names.last()?);
+    if names.len() < 2 {
+        return None;
+    }
+    let receivers = &names[1..names.len() - 1];
+    models.iter()
+        .filter(|model| simple_name(&model.name) == simple_name(first))
+        .find_map(|model| {
+            let scope = model.scopes.iter().find(|scope| {
+                scope.name == last && scope.receiver.as_ref().is_none_or(|receiver| receivers.contains(&receiver.as_str()))
+            })?;
+            Some((model, scope))
+        })
+        .or_else(|| {
+            models.iter().find_map(|model| {
+                let scope = model.scopes.iter().find(|scope| scope.name == last && scope.receiver.as_deref() == Some(first))?;
+                Some((model, scope))
+            })
+        })
+}
+
+// Names of method chain without arguments, like `["User", "where", "active"]` for `User::where('a.b', 1)->active()`.
+fn names(reference: &str) -> Vec<&str> {
+    let mut names = vec![];
+    let (mut start, mut depth) = (0, 0);
+    let bytes = reference.as_bytes();
+    for (index, byte) in bytes.iter().enumerate() {
+        match byte {
+            b'(' | b'[' | b'{' => {
+                if depth == 0 {
+                    names.push(&reference[start..index]);
+                }
+                depth += 1;
+            }
+            b')' | b']' | b'}' => {
+                depth -= 1;
+                start = index + 1;
+            }
+            b'.' | b':' | b'>' if depth == 0 => {
+                names.push(&reference[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    names.push(&reference[start..]);
+    names.into_iter().map(|name| name.trim().trim_end_matches('-')).filter(|name| !name.is_empty()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::models;
+
+    #[test]
+    fn it_should_resolve_scopes() {
+        let source = b"class Post < ApplicationRecord\n  scope :published, -> { where(published: true) }\n  def self.recent\n    order(created_at: :desc)\n  end\nend\n";
+        let models = models("app/models/post.rb", source).unwrap();
+
+        let definition = |reference: &str| resolve(&models, reference).map(|(model, scope)| (model.name.as_str(), scope.range.start));
+        assert_eq!(definition("Post.published"), Some(("Post", 33)));
+        assert_eq!(definition("Post.where(\"a.b\" => 1).recent"), Some(("Post", 83)));
+        assert_eq!(definition("Post.drafts"), None);
+        assert_eq!(definition("Post"), None);
+    }
+
+    #[test]
+    fn it_should_split_method_chains() {
+        assert_eq!(names("User::where('a.b', 1)->active()"), vec!["User", "where", "active"]);
+        assert_eq!(names("Question.objects.published()"), vec!["Question", "objects", "published"]);
+    }
+}
@@ -0,0 +1,121 @@
+use std::path::Path;
+use crate::hierarchy::simple_name;
+use crate::inflector::{pluralize, underscore};
+use super::{Model, Orm};
+
+// Effective table names of models, declared by them or inferred by the conventions of their ORMs.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableSource {
+    // Declared by `self.table_name`, `$table`, `Meta.db_table`, `@Entity("posts")` or `tableName`.
+    Explicit,
+    // Derived from the class name, like `users` for `User`.
+    Inferred,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableName {
+    pub name: String,
+    pub source: TableSource,
+}
+
+// Table name of the model, which is missing for Objection models not declaring it, as Objection requires `tableName`.
+pub fn table_name(model: &Model) -> Option<TableName> {
+    if let Some(table) = &model.table {
+        return Some(TableName { name: table.clone(), source: TableSource::Explicit });
+    }
+    let class_name = underscore(simple_name(&model.name));
+    let name = match model.orm {
+        // Rails and Laravel pluralize the last word of the snake case name, like `user_profiles`.
+        Orm::ActiveRecord | Orm::Eloquent => pluralize(&class_name),
+        // Django prefixes the lower case name by the label of the app, like `polls_question`.
+        Orm::Django => {
+            let model_name = simple_name(&model.name).to_lowercase();
+            match app_label(model) {
+                Some(app_label) => format!("{}_{}", app_label, model_name),
+                None => model_name,
+            }
+        }
+        // TypeORM names tables by the snake case name without pluralizing it.
+        Orm::TypeOrm => class_name,
+        Orm::Objection => return None,
+    };
+    Some(TableName { name, source: TableSource::Inferred })
+}
+
+// Label of Django app, declared by `Meta.app_label` or named after the package containing `models.py` or `models/`.
+fn app_label(model: &Model) -> Option<String> {
+    if let Some(app_label) = &model.app_label {
+        return Some(app_label.clone());
+    }
+    let mut directory = Path::new(&model.file).parent()?;
+    if directory.file_name()? == "models" {
+        directory = directory.parent()?;
+    }
+    directory.file_name()?.to_str().map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_fixture;
+    use crate::models::models;
+
+    fn table_names(file_name: &str, source: &[u8]) -> Vec<Option<TableName>> {
+        models(file_name, source).unwrap().iter().map(table_name).collect()
+    }
+
+    fn explicit(name: &str) -> Option<TableName> {
+        Some(TableName { name: String::from(name), source: TableSource::Explicit })
+    }
+
+    fn inferred(name: &str) -> Option<TableName> {
+        Some(TableName { name: String::from(name), source: TableSource::Inferred })
+    }
+
+    #[test]
+    fn it_should_resolve_table_names_of_fixtures() {
+        let fixtures = [
+            ("Animal.js", vec![explicit("animals")]),
+            ("Post.ts", vec![explicit("sample10_post")]),
+            ("user.rb", vec![inferred("users")]),
+            ("models.py", vec![inferred("polls_question"), inferred("polls_choice")]),
+        ];
+        for (fixture_name, expected) in fixtures {
+            let source = read_fixture(fixture_name);
+            // Django apps are named after their directories, like `polls/models.py`.
+            let file_name = match fixture_name {
+                "models.py" => String::from("polls/models.py"),
+                _ => format!("fixtures/{}", fixture_name),
+            };
+            assert_eq!(table_names(&file_name, &source), expected);
+        }
+    }
+
+    #[test]
+    fn it_should_resolve_eloquent_table_names() {
+        let source = read_fixture("User.php");
+        assert_eq!(table_names("app/Models/User.php", &source), vec![inferred("users")]);
+        assert_eq!(table_names("app/Models/Tag.php", b"<?php\nclass Tag extends Model {\n    protected $table = 'labels';\n}\n"), vec![explicit("labels")]);
+    }
+
+    #[test]
+    fn it_should_infer_table_names_by_conventions() {
+        assert_eq!(table_names("app/models/person.rb", b"class Person < ApplicationRecord\nend\nclass LineItem < ApplicationRecord\nend\n"), vec![
+            inferred("people"),
+            inferred("line_items"),
+        ]);
+        // Uncountable words are whole words, unlike `rice` of `Price` or `fish` of `Selfish`.
+        assert_eq!(table_names("app/models/price.rb", b"class Price < ApplicationRecord\nend\nclass Selfish < ApplicationRecord\nend\nclass TropicalFish < ApplicationRecord\nend\n"), vec![
+            inferred("prices"),
+            inferred("selfishes"),
+            inferred("tropical_fish"),
+        ]);
+        assert_eq!(table_names("shop/models/order.py", b"class OrderLine(models.Model):\n    pass\n\nclass Tag(models.Model):\n    class Meta:\n        app_label = 'catalog'\n"), vec![
+            inferred("shop_orderline"),
+            inferred("catalog_tag"),
+        ]);
+        assert_eq!(table_names("src/entity/PostDetails.ts", b"@Entity()\nexport class PostDetails {}\n"), vec![inferred("post_details")]);
+        assert_eq!(table_names("models/Person.js", b"class Person extends Model {}\n"), vec![None]);
+    }
+}
@@ -0,0 +1,349 @@
+use tree_sitter::{Language, Node};
+use crate::hierarchy::simple_name;
+use crate::syntax::{children, parse, text, visit};
+use super::{constants, unquote, values, Field, Hook, Model, Orm, Relation, RelationKind, Scope, Validation, Value};
+
+// TypeORM entities, which are classes decorated with `@Entity`, and their members described by decorators.
+
+const COLUMNS: [&str; 8] = [
+    "Column", "PrimaryColumn", "PrimaryGeneratedColumn", "CreateDateColumn", "UpdateDateColumn", "DeleteDateColumn", "VersionColumn", "ObjectIdColumn",
+];
+
+const RELATIONS: [&str; 4] = ["OneToOne", "OneToMany", "ManyToOne", "ManyToMany"];
+
+const HOOKS: [&str; 8] = [
+    "AfterLoad", "BeforeInsert", "AfterInsert", "BeforeUpdate", "AfterUpdate", "BeforeRemove", "AfterRemove", "BeforeSoftRemove",
+];
+
+// Decorators of class-validator, besides the ones starting with `Is`.
+const VALIDATIONS: [&str; 10] = [
+    "Length", "MinLength", "MaxLength", "Min", "Max", "Matches", "Contains", "NotContains", "Equals", "NotEquals",
+];
+
+pub fn models(language: Language, source: &[u8]) -> Vec<Model> {
+    let tree = parse(language, source);
+    let constants = constants::javascript(tree.root_node(), source);
+
+    let mut models = vec![];
+    visit(tree.root_node(), &mut |node| {
+        if !matches!(node.kind(), "class_declaration" | "abstract_class_declaration") {
+            return;
+        }
+        let entity = class_decorators(node).into_iter().find(|decorator| decorator_name(*decorator, source) == "Entity");
+        let (name, body, entity) = match (node.child_by_field_name("name"), node.child_by_field_name("body"), entity) {
+            (Some(name), Some(body), Some(entity)) => (name, body, entity),
+            _ => return,
+        };
+
+        let mut model = Model::new(text(name, source), Orm::TypeOrm, node.byte_range());
+        // The table is named by the first argument, like `@Entity("posts")` or `@Entity({ name: "posts" })`.
+        model.table = arguments(entity).first().and_then(|argument| match argument.kind() {
+            "object" => option(*argument, "name", source).and_then(|name| unquote(text(name, source))),
+            _ => unquote(text(*argument, source)),
+        });
+
+        // Decorators of members precede them in the class body.
+        let mut decorators = vec![];
+        for member in children(body) {
+            if member.kind() == "decorator" {
+                decorators.push(member);
+                continue;
+            }
+            if let Some(name) = member.child_by_field_name("name") {
+                read_member(&mut model, member, text(name, source), &decorators, source);
+            }
+            decorators.clear();
+        }
+        model.resolve(&constants);
+        models.push(model);
+    });
+    read_repositories(&mut models, tree.root_node(), source);
+    models
+}
+
+// Methods of custom repositories are scopes of their entities, called on the repositories like `PostRepository.findPublished()`.
+// Repositories are declared like `@EntityRepository(Post) class PostRepository` or `class PostRepository extends Repository<Post>`,
+// or like `const PostRepository = dataSource.getRepository(Post).extend({ ... })`.
+fn read_repositories(models: &mut [Model], root: Node, source: &[u8]) {
+    visit(root, &mut |node| {
+        let (repository, entity, methods) = match node.kind() {
+            "class_declaration" => {
+                let decorated = class_decorators(node).into_iter()
+                    .find(|decorator| decorator_name(*decorator, source) == "EntityRepository")
+                    .and_then(|decorator| arguments(decorator).first().copied());
+                let extended = children(node).into_iter()
+                    .filter(|child| child.kind() == "class_heritage")
+                    .flat_map(children)
+                    .filter(|clause| clause.kind() == "extends_clause")
+                    .flat_map(children)
+                    .find(|base| base.kind() == "generic_type" && base.named_child(0).is_some_and(|base| text(base, source) == "Repository"))
+                    .and_then(|base| base.named_child(1))
+                    .and_then(|arguments| arguments.named_child(0));
+                let methods = node.child_by_field_name("body").map(children).unwrap_or_default()
+                    .into_iter()
+                    .filter(|member| member.kind() == "method_definition")
+                    .filter(|method| !children(*method).iter().any(|child| child.kind() == "accessibility_modifier" && text(*child, source) != "public"))
+                    .filter_map(|method| Some((text(method.child_by_field_name("name")?, source), method)))
+                    .filter(|(name, _)| *name != "constructor" && !name.starts_with('#'))
+                    .collect::<Vec<_>>();
+                (node.child_by_field_name("name"), decorated.or(extended), methods)
+            }
+            "variable_declarator" => {
+                let call = node.child_by_field_name("value").filter(|value| value.kind() == "call_expression");
+                let function = call.and_then(|call| call.child_by_field_name("function")).filter(|function| function.kind() == "member_expression");
+                let extended = function
+                    .filter(|function| function.child_by_field_name("property").is_some_and(|property| text(property, source) == "extend"))
+                    .and_then(|function| function.child_by_field_name("object"))
+                    .filter(|object| object.kind() == "call_expression")
+                    .filter(|object| object.child_by_field_name("function").is_some_and(|function| simple_name(text(function, source)) == "getRepository"));
+                let entity = extended
+                    .and_then(|object| object.child_by_field_name("arguments"))
+                    .and_then(|arguments| arguments.named_child(0));
+                let methods = call.and_then(|call| call.child_by_field_name("arguments"))
+                    .and_then(|arguments| arguments.named_child(0))
+                    .filter(|object| object.kind() == "object")
+                    .map(children)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|member| match member.kind() {
+                        "method_definition" => true,
+                        "pair" => member.child_by_field_name("value").is_some_and(|value| matches!(value.kind(), "arrow_function" | "function" | "function_expression")),
+                        _ => false,
+                    })
+                    .filter_map(|member| Some((text(member.child_by_field_name("name").or(member.child_by_field_name("key"))?, source), member)))
+                    .collect();
+                (node.child_by_field_name("name"), entity, methods)
+            }
+            _ => return,
+        };
+        let (repository, entity) = match (repository, entity) {
+            (Some(repository), Some(entity)) => (text(repository, source), simple_name(text(entity, source))),
+            _ => return,
+        };
+        if let Some(model) = models.iter_mut().find(|model| model.name == entity) {
+            for (name, method) in methods {
+                model.scopes.push(Scope::new(name, Some(String::from(repository)), method.byte_range()));
+            }
+        }
+    });
+}
+
+fn read_member(model: &mut Model, member: Node, name: &str, decorators: &[Node], source: &[u8]) {
+    for decorator in decorators {
+        let decorator_name = decorator_name(*decorator, source);
+        if COLUMNS.contains(&decorator_name) {
+            let options = arguments(*decorator).into_iter()
+                .find(|argument| argument.kind() == "object")
+                .map_or(Value::Null, |options| values::javascript(options, source));
+            // Columns are required unless they are declared with `nullable: true`.
+            let mut field = Field::new(name, Orm::TypeOrm, column_type(*decorator, member, source), member.byte_range());
+            field.length = options.get("length").and_then(|length| length.as_length());
+            field.nullable = options.get("nullable").is_some_and(|nullable| nullable.is_true());
+            field.default = options.get("default").cloned();
+            field.primary_key = decorator_name.starts_with("Primary") || options.get("primary").is_some_and(|primary| primary.is_true());
+            // Columns declared with `select: false` are not loaded unless they are asked for.
+            if options.get("select") == Some(&Value::Boolean(false)) {
+                model.hidden.push(String::from(name));
+            }
+            model.fields.push(field);
+        } else if RELATIONS.contains(&decorator_name) {
+            let relation = relation_of(name, decorator_name, *decorator, decorators, source);
+            model.relations.push(Relation { range: member.byte_range(), ..relation });
+        } else if decorator_name == "Exclude" {
+            // Members excluded by class-transformer, which serializes entities by `instanceToPlain`.
+            model.hidden.push(String::from(name));
+        } else if HOOKS.contains(&decorator_name) {
+            let mut hook = Hook::new(decorator_name, Some(String::from(name)), decorator.byte_range());
+            hook.definition = Some(member.byte_range());
+            model.hooks.push(hook);
+        } else if VALIDATIONS.contains(&decorator_name) || is_validation(decorator_name) {
+            // Arguments of validations, like `@Length(6, 20)`, are kept as an array when there are several.
+            let value = match arguments(*decorator).as_slice() {
+                [] => Value::Boolean(true),
+                [argument] => values::javascript(*argument, source),
+                arguments => Value::Array(arguments.iter().map(|argument| values::javascript(*argument, source)).collect()),
+            };
+            model.validations.push(Validation::new(name, decorator_name, value, decorator.byte_range()));
+        }
+    }
+}
+
+// Relation declared like `@ManyToOne((type) => Cover, (cover) => cover.posts, { cascade: true })`.
+// The owning side of relations is marked by `@JoinColumn` and `@JoinTable`, whose arguments are kept among the options.
+fn relation_of(name: &str, decorator_name: &str, decorator: Node, decorators: &[Node], source: &[u8]) -> Relation {
+    let join = |join_name: &str| decorators.iter().find(|decorator| self::decorator_name(**decorator, source) == join_name).copied();
+    let join_column = join("JoinColumn");
+    let join_table = join("JoinTable");
+    let kind = match decorator_name {
+        "ManyToOne" => RelationKind::BelongsTo,
+        "OneToOne" if join_column.is_some() => RelationKind::BelongsTo,
+        "OneToOne" => RelationKind::HasOne,
+        "OneToMany" => RelationKind::HasMany,
+        _ => RelationKind::ManyToMany,
+    };
+    let mut relation = Relation::new(name, kind, decorator.byte_range());
+
+    let arguments = arguments(decorator);
+    let mut positional = arguments.iter().filter(|argument| argument.kind() != "object");
+    // Types and inverse sides are given by functions like `(type) => Cover` and `(cover) => cover.posts`, or by their names.
+    relation.target = positional.next().and_then(|target| returned_name(*target, source));
+    relation.inverse = positional.next().and_then(|inverse| returned_name(*inverse, source));
+    if let Some(options) = arguments.iter().find(|argument| argument.kind() == "object") {
+        if let Value::Object(options) = values::javascript(*options, source) {
+            relation.options = options;
+        }
+    }
+
+    for (option, join) in [("joinColumn", join_column), ("joinTable", join_table)] {
+        if let Some(join) = join {
+            let value = self::arguments(join).first().map_or(Value::Boolean(true), |options| values::javascript(*options, source));
+            relation.options.insert(String::from(option), value);
+        }
+    }
+    let join_name = |option: &str| relation.options.get(option).and_then(|join| join.get("name")).and_then(|name| name.as_str()).map(String::from);
+    relation.foreign_key = match kind {
+        RelationKind::BelongsTo => join_name("joinColumn").or_else(|| Some(format!("{}Id", name))),
+        _ => None,
+    };
+    relation.through = join_name("joinTable");
+    relation
+}
+
+// Name returned by function like `(cover) => cover.posts`, or given as string.
+fn returned_name(node: Node, source: &[u8]) -> Option<String> {
+    match node.kind() {
+        "arrow_function" | "function" => returned_name(node.child_by_field_name("body")?, source),
+        "identifier" | "member_expression" => Some(String::from(simple_name(text(node, source)))),
+        "parenthesized_expression" => returned_name(node.named_child(0)?, source),
+        _ => unquote(text(node, source)),
+    }
+}
+
+// Validations like `IsEmail` or `IsNotEmpty`, but not the `Is` decorator itself.
+fn is_validation(name: &str) -> bool {
+    name.strip_prefix("Is").is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()))
+}
+
+// Type given by `@Column("int")`, `@Column({ type: "int" })` or the type annotation of the member.
+fn column_type(decorator: Node, member: Node, source: &[u8]) -> Option<String> {
+    for argument in arguments(decorator) {
+        let column_type = match argument.kind() {
+            "object" => option(argument, "type", source).and_then(|column_type| unquote(text(column_type, source))),
+            _ => unquote(text(argument, source)),
+        };
+        if column_type.is_some() {
+            return column_type;
+        }
+    }
+    member.child_by_field_name("type")
+        .and_then(|annotation| annotation.named_child(0))
+        .map(|annotation| String::from(text(annotation, source)))
+}
+
+// Decorators of class, which are attached to the export statement for exported classes.
+fn class_decorators(class: Node) -> Vec<Node> {
+    let mut decorators: Vec<Node> = children(class).into_iter().filter(|child| child.kind() == "decorator").collect();
+    if let Some(parent) = class.parent().filter(|parent| parent.kind() == "export_statement") {
+        decorators.extend(children(parent).into_iter().filter(|child| child.kind() == "decorator"));
+    }
+    decorators
+}
+
+// Name of decorator, like `Column` for `@Column({ nullable: false })`.
+fn decorator_name<'a>(decorator: Node, source: &'a [u8]) -> &'a str {
+    let expression = match decorator.named_child(0) {
+        Some(expression) if expression.kind() == "call_expression" => expression.child_by_field_name("function"),
+        expression => expression,
+    };
+    expression.map_or("", |expression| text(expression, source))
+}
+
+fn arguments(decorator: Node) -> Vec<Node> {
+    decorator.named_child(0)
+        .filter(|expression| expression.kind() == "call_expression")
+        .and_then(|call| call.child_by_field_name("arguments"))
+        .map(children)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|argument| argument.is_named())
+        .collect()
+}
+
+// Value of option in object literal.
+fn option<'a>(object: Node<'a>, name: &str, source: &[u8]) -> Option<Node<'a>> {
+    children(object).into_iter()
+        .filter(|pair| pair.kind() == "pair")
+        .find(|pair| pair.child_by_field_name("key").is_some_and(|key| text(key, source).trim_matches(['"', '\'']) == name))
+        .and_then(|pair| pair.child_by_field_name("value"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_fixture;
+    use crate::models::DataType;
+
+    #[test]
+    fn it_should_read_typeorm_entities() {
+        let source = read_fixture("Post.ts");
+        let models = models(tree_sitter_typescript::language_typescript(), &source);
+
+        assert_eq!(models.len(), 1);
+        let post = &models[0];
+        assert_eq!(post.name, "Post");
+        assert_eq!(post.table.as_deref(), Some("sample10_post"));
+
+        let fields: Vec<(&str, Option<&str>)> = post.fields.iter().map(|field| (field.name.as_str(), field.field_type.as_deref())).collect();
+        assert_eq!(fields, vec![("id", Some("number")), ("title", Some("string")), ("text", Some("string")), ("coverId", Some("int"))]);
+        let flags: Vec<_> = post.fields.iter().map(|field| (field.data_type, field.nullable, field.primary_key)).collect();
+        assert_eq!(flags, vec![
+            (DataType::Integer, false, true),
+            (DataType::String, false, false),
+            (DataType::String, false, false),
+            (DataType::Integer, true, false),
+        ]);
+
+        let relations: Vec<_> = post.relations.iter()
+            .map(|relation| (relation.name.as_str(), relation.kind, relation.target.as_deref(), relation.inverse.as_deref(), relation.foreign_key.as_deref()))
+            .collect();
+        assert_eq!(relations, vec![
+            ("details", RelationKind::BelongsTo, Some("PostDetails"), Some("post"), Some("detailsId")),
+            ("images", RelationKind::HasMany, Some("Image"), Some("post"), None),
+            ("secondaryImages", RelationKind::HasMany, Some("Image"), Some("secondaryPost"), None),
+            ("cover", RelationKind::BelongsTo, Some("Cover"), Some("posts"), Some("coverId")),
+            ("categories", RelationKind::ManyToMany, Some("Category"), Some("posts"), None),
+        ]);
+        assert_eq!(post.relations[0].options.get("cascade"), Some(&Value::Boolean(true)));
+        assert_eq!(post.relations[4].options.get("joinTable"), Some(&Value::Boolean(true)));
+    }
+
+    #[test]
+    fn it_should_read_validations_and_hooks() {
+        let source = b"@Entity({ name: \"accounts\" })\nclass Account {\n  @Column({ type: \"varchar\", length: 100, default: \"\" })\n  @IsEmail()\n  @MaxLength(255)\n  email: string\n  @Column({ select: false })\n  password: string\n  @Exclude()\n  @Column()\n  token: string\n  @BeforeInsert()\n  normalize() {}\n  @OneToOne(\"Profile\", \"account\")\n  profile: Profile\n  @ManyToMany(() => Role)\n  @JoinTable({ name: \"account_roles\" })\n  roles: Role[]\n}\nclass Plain {}\n";
+        let models = models(tree_sitter_typescript::language_typescript(), source);
+
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].table.as_deref(), Some("accounts"));
+        assert_eq!(models[0].fields[0].field_type.as_deref(), Some("varchar"));
+        assert_eq!(models[0].fields[0].length, Some(100));
+        assert_eq!(models[0].fields[0].default, Some(Value::String(String::new())));
+        let validations: Vec<&str> = models[0].validations.iter().map(|validation| validation.rule.as_str()).collect();
+        assert_eq!(validations, vec!["IsEmail", "MaxLength"]);
+        assert_eq!(models[0].hooks[0].event, "BeforeInsert");
+        assert_eq!(models[0].hooks[0].callback.as_deref(), Some("normalize"));
+        assert_eq!(models[0].hidden, vec!["password", "token"]);
+
+        let relations = &models[0].relations;
+        assert_eq!((relations[0].kind, relations[0].target.as_deref(), relations[0].inverse.as_deref()), (RelationKind::HasOne, Some("Profile"), Some("account")));
+        assert_eq!((relations[1].target.as_deref(), relations[1].through.as_deref()), (Some("Role"), Some("account_roles")));
+    }
+
+    #[test]
+    fn it_should_read_repositories() {
+        let source = b"@Entity()\nexport class Post {}\n@EntityRepository(Post)\nexport class PostRepository extends Repository<Post> {\n  private visible() {}\n  findPublished() { return this.find() }\n}\nexport const Posts = dataSource.getRepository(Post).extend({\n  findByAuthor(id: number) { return this.findBy({ id }) },\n  recent: async () => this.find(),\n})\n";
+        let models = models(tree_sitter_typescript::language_typescript(), source);
+
+        let scopes: Vec<_> = models[0].scopes.iter().map(|scope| (scope.name.as_str(), scope.receiver.as_deref(), scope.range.start)).collect();
+        assert_eq!(scopes, vec![("findPublished", Some("PostRepository"), 135), ("findByAuthor", Some("Posts"), 239), ("recent", Some("Posts"), 298)]);
+    }
+}
@@ -0,0 +1,125 @@
+use std::fmt;
+use super::Orm;
+
+// Portable types of fields, mapped from the types ORMs declare them with.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataType {
+    String,
+    Text,
+    Integer,
+    BigInteger,
+    Float,
+    Decimal,
+    Boolean,
+    Date,
+    DateTime,
+    Time,
+    Json,
+    Binary,
+    Uuid,
+    Unknown,
+}
+
+impl fmt::Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let data_type = match self {
+            DataType::String => "string",
+            DataType::Text => "text",
+            DataType::Integer => "integer",
+            DataType::BigInteger => "big_integer",
+            DataType::Float => "float",
+            DataType::Decimal => "decimal",
+            DataType::Boolean => "boolean",
+            DataType::Date => "date",
+            DataType::DateTime => "datetime",
+            DataType::Time => "time",
+            DataType::Json => "json",
+            DataType::Binary => "binary",
+            DataType::Uuid => "uuid",
+            DataType::Unknown => "unknown",
+        };
+        write!(f, "{}", data_type)
+    }
+}
+
+// Portable type of the type declared for ORM, like `CharField`, `datetime`, `varchar` or `number`.
+pub fn data_type(orm: Orm, declared: &str) -> DataType {
+    // Parameters are dropped, like the ones of `decimal:2` casts or `varchar(255)` columns.
+    let declared = declared.split([':', '(']).next().unwrap_or(declared).trim();
+    match (orm, declared.to_lowercase().as_str()) {
+        // JSON Schema numbers may have fractions, while TypeScript numbers are stored as integers by TypeORM.
+        (Orm::Objection, "number") => DataType::Float,
+        (Orm::TypeOrm, "number") => DataType::Integer,
+        // TypeScript `Date` is stored with time, unlike `date` columns.
+        (Orm::TypeOrm, "date") if declared == "Date" => DataType::DateTime,
+        (_, declared) => portable_type(declared),
+    }
+}
+
+// Portable type of the column type databases report, like `character varying(255)`, `int unsigned` or `bigserial`.
+pub fn sql_data_type(declared: &str) -> DataType {
+    let declared = declared.trim().to_lowercase();
+    // MySQL stores booleans as `tinyint(1)`.
+    if declared.starts_with("tinyint(1)") {
+        return DataType::Boolean;
+    }
+    let declared = declared.split('(').next().unwrap_or(&declared).trim_end_matches(" unsigned").trim();
+    match declared.strip_suffix(" without time zone").unwrap_or(declared) {
+        "serial" | "serial4" | "smallserial" => DataType::Integer,
+        "bigserial" | "serial8" => DataType::BigInteger,
+        "longblob" | "mediumblob" | "tinyblob" => DataType::Binary,
+        "clob" | "character large object" => DataType::Text,
+        declared => portable_type(declared),
+    }
+}
+
+fn portable_type(declared: &str) -> DataType {
+    match declared {
+        "string" | "varchar" | "char" | "character varying" | "nvarchar" | "character" | "enum" | "simple-array" | "encrypted"
+            | "charfield" | "slugfield" | "emailfield" | "urlfield" | "filefield" | "imagefield" | "filepathfield"
+            | "ipaddressfield" | "genericipaddressfield" => DataType::String,
+        "text" | "longtext" | "mediumtext" | "tinytext" | "textfield" => DataType::Text,
+        "integer" | "int" | "int2" | "int4" | "smallint" | "tinyint" | "mediumint" | "increments"
+            | "integerfield" | "smallintegerfield" | "positiveintegerfield" | "positivesmallintegerfield" | "autofield" | "smallautofield" => DataType::Integer,
+        "bigint" | "int8" | "big_integer" | "bigincrements" | "bigintegerfield" | "positivebigintegerfield" | "bigautofield" => DataType::BigInteger,
+        "float" | "double" | "real" | "float4" | "float8" | "double precision" | "floatfield" => DataType::Float,
+        "decimal" | "numeric" | "decimalfield" => DataType::Decimal,
+        "boolean" | "bool" | "booleanfield" | "nullbooleanfield" => DataType::Boolean,
+        "date" | "immutable_date" | "datefield" => DataType::Date,
+        "datetime" | "immutable_datetime" | "timestamp" | "timestamptz" | "timestamp with time zone" | "date-time" | "datetimefield" => DataType::DateTime,
+        "time" | "timefield" | "durationfield" => DataType::Time,
+        "json" | "jsonb" | "simple-json" | "array" | "object" | "collection" | "jsonfield" => DataType::Json,
+        "binary" | "blob" | "bytea" | "varbinary" | "binaryfield" => DataType::Binary,
+        "uuid" | "uuidfield" => DataType::Uuid,
+        _ => DataType::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_map_declared_types() {
+        assert_eq!(data_type(Orm::Django, "CharField"), DataType::String);
+        assert_eq!(data_type(Orm::Eloquent, "decimal:2"), DataType::Decimal);
+        assert_eq!(data_type(Orm::TypeOrm, "varchar(255)"), DataType::String);
+        assert_eq!(data_type(Orm::TypeOrm, "number"), DataType::Integer);
+        assert_eq!(data_type(Orm::TypeOrm, "Date"), DataType::DateTime);
+        assert_eq!(data_type(Orm::TypeOrm, "date"), DataType::Date);
+        assert_eq!(data_type(Orm::Objection, "number"), DataType::Float);
+        assert_eq!(data_type(Orm::ActiveRecord, "datetime"), DataType::DateTime);
+        assert_eq!(data_type(Orm::Eloquent, "App\\Casts\\Money"), DataType::Unknown);
+    }
+
+    #[test]
+    fn it_should_map_sql_types() {
+        assert_eq!(sql_data_type("CHARACTER VARYING(255)"), DataType::String);
+        assert_eq!(sql_data_type("timestamp(6) without time zone"), DataType::DateTime);
+        assert_eq!(sql_data_type("BIGSERIAL"), DataType::BigInteger);
+        assert_eq!(sql_data_type("int unsigned"), DataType::Integer);
+        assert_eq!(sql_data_type("tinyint(1)"), DataType::Boolean);
+        assert_eq!(sql_data_type("tinyint(4)"), DataType::Integer);
+    }
+}
@@ -0,0 +1,261 @@
+use std::fmt;
+use indexmap::IndexMap;
+use tree_sitter::Node;
+use crate::syntax::{children, text};
+use super::{unquote, unquote_php};
+
+// Literal values of arguments passed to ORM macros, decorators and calls, like `{ cascade: true }` or `on_delete=models.CASCADE`.
+// Anything else than literals is kept as the source text of the expression.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Number(f64),
+    Boolean(bool),
+    Null,
+    Array(Vec<Value>),
+    Object(IndexMap<String, Value>),
+    // Regular expression literal, like `/\A[\w+\-.]+@[a-z\d\-.]+\.[a-z]+\z/i`.
+    Regex { pattern: String, flags: String },
+    // Source text of expression which is not literal, like `Person`, `models.CASCADE` or `(type) => Cover`.
+    Expression(String),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Value::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    // Length or count given by non-negative integer, like `max_length=200`.
+    pub fn as_length(&self) -> Option<u32> {
+        self.as_number().filter(|number| *number >= 0.0 && number.fract() == 0.0).map(|number| number as u32)
+    }
+
+    pub fn is_true(&self) -> bool {
+        *self == Value::Boolean(true)
+    }
+
+    // Text of string or expression, like the class referred by `Person` or `'app.Person'`.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Value::String(text) | Value::Expression(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    // Value of the key in object.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(object) => object.get(key),
+            _ => None,
+        }
+    }
+
+    // Value with references to the constants replaced by their values, like `VALID_EMAIL_REGEX` in `{ with: VALID_EMAIL_REGEX }`.
+    pub fn resolve(&self, constants: &IndexMap<String, Value>) -> Value {
+        match self {
+            Value::Expression(expression) => constants.get(expression).cloned().unwrap_or_else(|| self.clone()),
+            Value::Array(array) => Value::Array(array.iter().map(|item| item.resolve(constants)).collect()),
+            Value::Object(object) => Value::Object(object.iter().map(|(key, value)| (key.clone(), value.resolve(constants))).collect()),
+            _ => self.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::String(string) => write!(f, "{:?}", string),
+            Value::Number(number) => write!(f, "{}", number),
+            Value::Boolean(boolean) => write!(f, "{}", boolean),
+            Value::Null => write!(f, "null"),
+            Value::Array(array) => {
+                let items: Vec<String> = array.iter().map(|item| item.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+            Value::Object(object) => {
+                let pairs: Vec<String> = object.iter().map(|(key, value)| format!("{}: {}", key, value)).collect();
+                write!(f, "{{{}}}", pairs.join(", "))
+            }
+            Value::Regex { pattern, flags } => write!(f, "/{}/{}", pattern, flags),
+            Value::Expression(expression) => write!(f, "{}", expression),
+        }
+    }
+}
+
+pub fn javascript(node: Node, source: &[u8]) -> Value {
+    match node.kind() {
+        "string" | "template_string" => string(node, source),
+        "number" => number(node, source),
+        "regex" => Value::Regex {
+            pattern: node.child_by_field_name("pattern").map_or_else(String::new, |pattern| String::from(text(pattern, source))),
+            flags: node.child_by_field_name("flags").map_or_else(String::new, |flags| String::from(text(flags, source))),
+        },
+        "true" | "false" => Value::Boolean(node.kind() == "true"),
+        "null" | "undefined" => Value::Null,
+        "array" => Value::Array(named(node).into_iter().map(|item| javascript(item, source)).collect()),
+        "object" => Value::Object(
+            named(node).into_iter()
+                .filter(|pair| pair.kind() == "pair")
+                .filter_map(|pair| Some((key(pair.child_by_field_name("key")?, source), javascript(pair.child_by_field_name("value")?, source))))
+                .collect(),
+        ),
+        _ => expression(node, source),
+    }
+}
+
+pub fn ruby(node: Node, source: &[u8]) -> Value {
+    match node.kind() {
+        "string" => string(node, source),
+        "simple_symbol" => Value::String(String::from(text(node, source).trim_start_matches(':'))),
+        "integer" | "float" => number(node, source),
+        "regex" => regex(node, source),
+        "true" | "false" => Value::Boolean(node.kind() == "true"),
+        "nil" => Value::Null,
+        "array" => Value::Array(named(node).into_iter().map(|item| ruby(item, source)).collect()),
+        "hash" => Value::Object(pairs(named(node), source, ruby)),
+        _ => expression(node, source),
+    }
+}
+
+pub fn php(node: Node, source: &[u8]) -> Value {
+    match node.kind() {
+        "string" | "encapsed_string" => unquote_php(text(node, source)).map_or_else(|| expression(node, source), Value::String),
+        "integer" | "float" => number(node, source),
+        "boolean" => Value::Boolean(text(node, source).eq_ignore_ascii_case("true")),
+        "null" => Value::Null,
+        "array_creation_expression" => {
+            let elements: Vec<Vec<Node>> = named(node).into_iter()
+                .filter(|element| element.kind() == "array_element_initializer")
+                .map(named)
+                .collect();
+            if elements.iter().all(|element| element.len() == 1) {
+                Value::Array(elements.into_iter().map(|element| php(element[0], source)).collect())
+            } else {
+                Value::Object(
+                    elements.into_iter()
+                        .filter_map(|element| match element.as_slice() {
+                            [key_node, value] => Some((key(*key_node, source), php(*value, source))),
+                            _ => None,
+                        })
+                        .collect(),
+                )
+            }
+        }
+        _ => expression(node, source),
+    }
+}
+
+pub fn python(node: Node, source: &[u8]) -> Value {
+    match node.kind() {
+        // Raw and byte strings are taken as strings, but not formatted ones.
+        "string" => match text(node, source).trim_start_matches(['r', 'R', 'b', 'B', 'u', 'U']) {
+            literal if !literal.starts_with(['f', 'F']) => unquote(literal).map_or_else(|| expression(node, source), Value::String),
+            _ => expression(node, source),
+        },
+        "integer" | "float" => number(node, source),
+        "true" | "false" => Value::Boolean(node.kind() == "true"),
+        "none" => Value::Null,
+        "list" | "tuple" => Value::Array(named(node).into_iter().map(|item| python(item, source)).collect()),
+        "dictionary" => Value::Object(pairs(named(node), source, python)),
+        _ => expression(node, source),
+    }
+}
+
+fn pairs(nodes: Vec<Node>, source: &[u8], value: fn(Node, &[u8]) -> Value) -> IndexMap<String, Value> {
+    nodes.into_iter()
+        .filter(|pair| pair.kind() == "pair")
+        .filter_map(|pair| Some((key(pair.child_by_field_name("key")?, source), value(pair.child_by_field_name("value")?, source))))
+        .collect()
+}
+
+// Key of object, which is given as string or bare word.
+pub(crate) fn key(node: Node, source: &[u8]) -> String {
+    let key = text(node, source);
+    unquote(key).unwrap_or_else(|| String::from(key.trim_start_matches(':').trim_end_matches(':')))
+}
+
+// Interpolated strings are kept as expressions.
+fn string(node: Node, source: &[u8]) -> Value {
+    match unquote(text(node, source)) {
+        Some(string) => Value::String(string),
+        None => expression(node, source),
+    }
+}
+
+// Ruby regular expression, unless it is interpolated or delimited otherwise than by slashes.
+fn regex(node: Node, source: &[u8]) -> Value {
+    let literal = text(node, source);
+    match (literal.strip_prefix('/'), literal.rfind('/')) {
+        (Some(_), Some(end)) if end > 0 && !literal.contains("#{") => Value::Regex {
+            pattern: String::from(&literal[1..end]),
+            flags: String::from(&literal[end + 1..]),
+        },
+        _ => expression(node, source),
+    }
+}
+
+fn number(node: Node, source: &[u8]) -> Value {
+    match text(node, source).replace('_', "").parse() {
+        Ok(number) => Value::Number(number),
+        Err(_) => expression(node, source),
+    }
+}
+
+fn expression(node: Node, source: &[u8]) -> Value {
+    Value::Expression(String::from(text(node, source)))
+}
+
+fn named(node: Node) -> Vec<Node> {
+    children(node).into_iter().filter(|child| child.is_named() && child.kind() != "comment").collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::parse;
+
+    #[test]
+    fn it_should_read_literal_values() {
+        let source = b"f({ cascade: ['insert'], 'nullable': true, length: 255, type: () => Cover, name: `a${b}` })";
+        let tree = parse(tree_sitter_javascript::language(), source);
+        let object = tree.root_node().named_child(0).and_then(|statement| statement.named_child(0))
+            .and_then(|call| call.child_by_field_name("arguments"))
+            .and_then(|arguments| arguments.named_child(0))
+            .unwrap();
+
+        let value = javascript(object, source);
+        assert_eq!(value.get("cascade"), Some(&Value::Array(vec![Value::String(String::from("insert"))])));
+        assert_eq!(value.get("nullable"), Some(&Value::Boolean(true)));
+        assert_eq!(value.get("length"), Some(&Value::Number(255.0)));
+        assert_eq!(value.get("type"), Some(&Value::Expression(String::from("() => Cover"))));
+        assert_eq!(value.get("name"), Some(&Value::Expression(String::from("`a${b}`"))));
+        assert_eq!(value.to_string(), "{cascade: [\"insert\"], nullable: true, length: 255, type: () => Cover, name: `a${b}`}");
+    }
+
+    #[test]
+    fn it_should_resolve_constants() {
+        let source = b"validates :email, format: { with: /\\A\\S+@\\S+\\z/i, without: PATTERN }";
+        let tree = parse(tree_sitter_ruby::language(), source);
+        let hash = tree.root_node().named_child(0)
+            .and_then(|call| call.child_by_field_name("arguments"))
+            .map(|arguments| ruby(arguments.named_child(1).unwrap().child_by_field_name("value").unwrap(), source))
+            .unwrap();
+        assert_eq!(hash.get("with"), Some(&Value::Regex { pattern: String::from("\\A\\S+@\\S+\\z"), flags: String::from("i") }));
+
+        let constants = IndexMap::from([(String::from("PATTERN"), Value::Regex { pattern: String::from("^$"), flags: String::new() })]);
+        let resolved = hash.resolve(&constants);
+        assert_eq!(resolved.get("without"), constants.get("PATTERN"));
+        assert_eq!(resolved.to_string(), "{with: /\\A\\S+@\\S+\\z/i, without: /^$/}");
+    }
+}
@@ -0,0 +1,272 @@
+use indexmap::IndexMap;
+use tree_sitter::{Language, Node};
+use crate::python::is_method;
+use crate::syntax::{children, parse, text, visit};
+
+// Modifiers of definitions, like `static` or `private`, keyed by the start byte of their names.
+// Members of classes and interfaces always have their visibility, while other definitions only have the modifiers they are declared with.
+
+// Canonical order of modifiers, visibility comes first.
+const ORDER: [&str; 11] = [
+    "public", "protected", "private", "static", "abstract", "final", "readonly", "async", "generator", "getter", "setter",
+];
+
+// Sort modifiers in the canonical order, dropping duplicates.
+pub fn sort(modifiers: &mut Vec<String>) {
+    modifiers.sort_by_key(|modifier| ORDER.iter().position(|order| order == modifier).unwrap_or(ORDER.len()));
+    modifiers.dedup();
+}
+
+// Modifiers of JavaScript and TypeScript functions, classes and class members.
+pub fn javascript(language: Language, source: &[u8]) -> IndexMap<usize, Vec<String>> {
+    let tree = parse(language, source);
+
+    let mut modifiers = IndexMap::new();
+    visit(tree.root_node(), &mut |node| {
+        let (name, mut found) = match node.kind() {
+            "method_definition" | "method_signature" | "abstract_method_signature" | "property_signature" | "public_field_definition" | "field_definition" => {
+                let name = match node.child_by_field_name("name").or_else(|| node.child_by_field_name("property")) {
+                    Some(name) => name,
+                    None => return,
+                };
+                let visibility = match children(node).into_iter().find(|child| child.kind() == "accessibility_modifier") {
+                    Some(modifier) => text(modifier, source),
+                    None if name.kind() == "private_property_identifier" => "private",
+                    None => "public",
+                };
+                (name, vec![String::from(visibility)])
+            }
+            "function_declaration" | "generator_function_declaration" | "function_signature" | "class_declaration" | "abstract_class_declaration" => {
+                match node.child_by_field_name("name") {
+                    Some(name) => (name, vec![]),
+                    None => return,
+                }
+            }
+            // Functions assigned to variables, like `const load = async () => {}`.
+            "variable_declarator" => match (node.child_by_field_name("name"), node.child_by_field_name("value")) {
+                (Some(name), Some(value)) if matches!(value.kind(), "arrow_function" | "function" | "generator_function") => {
+                    (name, keywords(value))
+                }
+                _ => return,
+            },
+            _ => return,
+        };
+        found.extend(keywords(node));
+        if !found.is_empty() {
+            sort(&mut found);
+            modifiers.insert(name.start_byte(), found);
+        }
+    });
+    modifiers
+}
+
+// Modifiers given by keywords of JavaScript function or class member.
+fn keywords(node: Node) -> Vec<String> {
+    let mut keywords = vec![];
+    if matches!(node.kind(), "generator_function_declaration" | "generator_function" | "abstract_class_declaration") {
+        keywords.push(if node.kind() == "abstract_class_declaration" { "abstract" } else { "generator" });
+    }
+    for child in children(node) {
+        match child.kind() {
+            "static" | "async" | "abstract" | "readonly" => keywords.push(child.kind()),
+            "*" => keywords.push("generator"),
+            "get" => keywords.push("getter"),
+            "set" => keywords.push("setter"),
+            _ => {}
+        }
+    }
+    keywords.into_iter().map(String::from).collect()
+}
+
+// Modifiers of Ruby methods, whose visibility is given by the sections or arguments of `private`, `protected` and `public`.
+pub fn ruby(source: &[u8]) -> IndexMap<usize, Vec<String>> {
+    let tree = parse(tree_sitter_ruby::language(), source);
+
+    let mut modifiers = IndexMap::new();
+    visit(tree.root_node(), &mut |node| match node.kind() {
+        "class" | "module" | "singleton_class" => ruby_body(node, source, &mut modifiers),
+        // Singleton methods are public regardless of the section.
+        "singleton_method" => {
+            if let Some(name) = node.child_by_field_name("name") {
+                modifiers.insert(name.start_byte(), vec![String::from("public"), String::from("static")]);
+            }
+        }
+        _ => {}
+    });
+    modifiers
+}
+
+fn ruby_body(body: Node, source: &[u8], modifiers: &mut IndexMap<usize, Vec<String>>) {
+    let mut visibility = "public";
+    // (name node, visibility) of the methods defined in this body.
+    let mut methods: Vec<(Node, &str)> = vec![];
+    // Visibility given to methods by their names, like `private :helper`.
+    let mut named: IndexMap<&str, &str> = IndexMap::new();
+
+    for child in children(body) {
+        match child.kind() {
+            "identifier" if is_visibility(text(child, source)) => visibility = text(child, source),
+            "method" => {
+                if let Some(name) = child.child_by_field_name("name") {
+                    methods.push((name, visibility));
+                }
+            }
+            "call" if child.child_by_field_name("receiver").is_none() => {
+                let method = child.child_by_field_name("method").map_or("", |method| text(method, source));
+                if !is_visibility(method) {
+                    continue;
+                }
+                let arguments = child.child_by_field_name("arguments").map(children).unwrap_or_default();
+                for argument in arguments {
+                    match argument.kind() {
+                        "method" => {
+                            if let Some(name) = argument.child_by_field_name("name") {
+                                methods.push((name, method));
+                            }
+                        }
+                        "simple_symbol" => {
+                            named.insert(text(argument, source).trim_start_matches(':'), method);
+                        }
+                        "string" => {
+                            named.insert(text(argument, source).trim_matches(['"', '\'']), method);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (name, visibility) in methods {
+        let method_name = text(name, source);
+        let visibility = match named.get(method_name) {
+            Some(visibility) => visibility,
+            // Ruby always makes constructors private.
+            None if method_name == "initialize" => "private",
+            None => visibility,
+        };
+        let mut found = vec![String::from(visibility)];
+        if body.kind() == "singleton_class" {
+            found.push(String::from("static"));
+        }
+        modifiers.insert(name.start_byte(), found);
+    }
+}
+
+fn is_visibility(name: &str) -> bool {
+    matches!(name, "private" | "protected" | "public")
+}
+
+// Modifiers of Python functions, given by `async`, `yield`, decorators and naming conventions of methods.
+pub fn python(source: &[u8]) -> IndexMap<usize, Vec<String>> {
+    let tree = parse(tree_sitter_python::language(), source);
+    let decorators = crate::python::decorators(source);
+
+    let mut modifiers = IndexMap::new();
+    visit(tree.root_node(), &mut |node| {
+        if node.kind() != "function_definition" {
+            return;
+        }
+        let name = match node.child_by_field_name("name") {
+            Some(name) => name,
+            None => return,
+        };
+
+        let mut found = vec![];
+        if is_method(node) {
+            let method_name = text(name, source);
+            found.push(if method_name.starts_with("__") && !method_name.ends_with("__") {
+                "private"
+            } else if method_name.starts_with('_') && !method_name.starts_with("__") {
+                "protected"
+            } else {
+                "public"
+            });
+        }
+        if children(node).iter().any(|child| child.kind() == "async") {
+            found.push("async");
+        }
+        if node.child_by_field_name("body").is_some_and(yields) {
+            found.push("generator");
+        }
+        for decorator in decorators.get(&name.start_byte()).into_iter().flatten() {
+            match decorator.rsplit('.').next().unwrap_or("") {
+                "staticmethod" | "classmethod" => found.push("static"),
+                "abstractmethod" => found.push("abstract"),
+                "property" | "cached_property" | "getter" => found.push("getter"),
+                "setter" => found.push("setter"),
+                _ => {}
+            }
+        }
+
+        if !found.is_empty() {
+            let mut found = found.into_iter().map(String::from).collect();
+            sort(&mut found);
+            modifiers.insert(name.start_byte(), found);
+        }
+    });
+    modifiers
+}
+
+// Whether the function body yields, not counting nested functions and classes.
+fn yields(node: Node) -> bool {
+    children(node).into_iter().any(|child| match child.kind() {
+        "yield" => true,
+        "function_definition" | "lambda" | "class_definition" => false,
+        _ => yields(child),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modifiers_of(modifiers: &IndexMap<usize, Vec<String>>, source: &[u8], name: &str) -> String {
+        let start = String::from_utf8_lossy(source).find(name).unwrap();
+        modifiers.get(&start).map(|modifiers| modifiers.join(" ")).unwrap_or_default()
+    }
+
+    #[test]
+    fn it_should_read_js_modifiers() {
+        let source = b"abstract class Shape {\n  private static readonly origin = 0;\n  #id = 1;\n  static get unit() {}\n  async *points() {}\n  protected abstract area(): number;\n}\nasync function draw() {}\nconst load = async () => {};\n";
+        let modifiers = javascript(tree_sitter_typescript::language_typescript(), source);
+
+        assert_eq!(modifiers_of(&modifiers, source, "Shape"), "abstract");
+        assert_eq!(modifiers_of(&modifiers, source, "origin"), "private static readonly");
+        assert_eq!(modifiers_of(&modifiers, source, "#id"), "private");
+        assert_eq!(modifiers_of(&modifiers, source, "unit"), "public static getter");
+        assert_eq!(modifiers_of(&modifiers, source, "points"), "public async generator");
+        assert_eq!(modifiers_of(&modifiers, source, "area"), "protected abstract");
+        assert_eq!(modifiers_of(&modifiers, source, "draw"), "async");
+        assert_eq!(modifiers_of(&modifiers, source, "load"), "async");
+    }
+
+    #[test]
+    fn it_should_read_ruby_modifiers() {
+        let source = b"class Account\n  def self.build; end\n  def initialize; end\n  def balance; end\n  private def audit; end\n  protected\n  def compare; end\n  def reset; end\n  public :reset\n  class << self\n    def load; end\n  end\nend\n";
+        let modifiers = ruby(source);
+
+        assert_eq!(modifiers_of(&modifiers, source, "build"), "public static");
+        assert_eq!(modifiers_of(&modifiers, source, "initialize"), "private");
+        assert_eq!(modifiers_of(&modifiers, source, "balance"), "public");
+        assert_eq!(modifiers_of(&modifiers, source, "audit"), "private");
+        assert_eq!(modifiers_of(&modifiers, source, "compare"), "protected");
+        assert_eq!(modifiers_of(&modifiers, source, "reset"), "public");
+        assert_eq!(modifiers_of(&modifiers, source, "load"), "public static");
+    }
+
+    #[test]
+    fn it_should_read_python_modifiers() {
+        let source = b"class Feed:\n    @property\n    def title(self):\n        pass\n    @title.setter\n    def title_(self, value):\n        pass\n    @staticmethod\n    def parse(text):\n        pass\n    async def __fetch(self):\n        yield 1\n    def _cache(self):\n        def inner():\n            yield 1\n\ndef entries():\n    pass\n";
+        let modifiers = python(source);
+
+        assert_eq!(modifiers_of(&modifiers, source, "title("), "public getter");
+        assert_eq!(modifiers_of(&modifiers, source, "title_"), "public setter");
+        assert_eq!(modifiers_of(&modifiers, source, "parse"), "public static");
+        assert_eq!(modifiers_of(&modifiers, source, "__fetch"), "private async generator");
+        assert_eq!(modifiers_of(&modifiers, source, "_cache"), "protected");
+        assert_eq!(modifiers_of(&modifiers, source, "inner"), "generator");
+        assert_eq!(modifiers_of(&modifiers, source, "entries"), "");
+    }
+}
@@ -0,0 +1,169 @@
+use indexmap::IndexMap;
+use tree_sitter::{Node, Query, QueryCursor};
+use crate::hierarchy::simple_name;
+use crate::inflector::underscore;
+use crate::macros::Synthetic;
+use crate::models::eloquent;
+use crate::syntax::{children, parse, text, visit};
+
+// Details of PHP source, which can't be expressed by tagging queries.
+
+// Modifiers of all classes and class members, keyed by the start byte of their names.
+// Members without visibility are public as PHP defaults, while classes have no visibility.
+pub fn modifiers(source: &[u8]) -> IndexMap<usize, Vec<String>> {
+    let tree = parse(tree_sitter_php::language(), source);
+
+    let query = Query::new(
+        tree_sitter_php::language(),
+        "[(class_declaration) (method_declaration) (property_declaration) (const_declaration)] @declaration",
+    ).unwrap();
+    let mut cursor = QueryCursor::new();
+
+    let mut modifiers = IndexMap::new();
+    for matched in cursor.matches(&query, tree.root_node(), source) {
+        let declaration = matched.captures[0].node;
+        let is_class = declaration.kind() == "class_declaration";
+        // Constants may be declared outside classes.
+        if !is_class && !declaration.parent().is_some_and(|parent| matches!(parent.kind(), "declaration_list" | "enum_declaration_list")) {
+            continue;
+        }
+
+        let mut member_modifiers = vec![];
+        let mut walker = declaration.walk();
+        for child in declaration.named_children(&mut walker) {
+            match child.kind() {
+                // `var` is an old synonym of `public`.
+                "var_modifier" => {}
+                kind if kind.ends_with("_modifier") => member_modifiers.push(text(child, source).to_lowercase()),
+                _ => {}
+            }
+        }
+        if !is_class && !member_modifiers.iter().any(|modifier| matches!(modifier.as_str(), "public" | "protected" | "private")) {
+            member_modifiers.push(String::from("public"));
+        }
+        if member_modifiers.is_empty() {
+            continue;
+        }
+        crate::modifiers::sort(&mut member_modifiers);
+
+        for name in names(declaration) {
+            modifiers.insert(name.start_byte(), member_modifiers.clone());
+        }
+    }
+    modifiers
+}
+
+// Members of Eloquent models which are resolved by naming conventions at runtime, defined at the names of the methods providing them:
+// attributes of accessors and mutators, like `password` of `setPasswordAttribute` or `first_name` of `firstName(): Attribute`,
+// scopes called without their prefix, like `active` of `scopeActive`, and relations read as properties, like `posts` of `posts()`.
+pub fn magic_members(source: &[u8]) -> Vec<Synthetic> {
+    let models = eloquent::models(source);
+    let tree = parse(tree_sitter_php::language(), source);
+
+    let mut synthetics = vec![];
+    visit(tree.root_node(), &mut |node| {
+        let model = match models.iter().find(|model| node.kind() == "class_declaration" && model.range == node.byte_range()) {
+            Some(model) => model,
+            None => return,
+        };
+        let methods = node.child_by_field_name("body").map(children).unwrap_or_default()
+            .into_iter()
+            .filter(|declaration| declaration.kind() == "method_declaration");
+        for method in methods {
+            let name = match method.child_by_field_name("name") {
+                Some(name) => name,
+                None => continue,
+            };
+            let method_name = text(name, source);
+            let define = |member: String, kind: &'static str, convention: &str| Synthetic {
+                name: member,
+                kind,
+                macro_name: String::from(convention),
+                range: name.byte_range(),
+            };
+            let attribute = |prefix: &str| method_name.strip_prefix(prefix)
+                .and_then(|name| name.strip_suffix("Attribute"))
+                .filter(|name| name.starts_with(|char: char| char.is_uppercase()))
+                .map(underscore);
+
+            if let Some(attribute) = attribute("get") {
+                synthetics.push(define(attribute, "property", "accessor"));
+            } else if let Some(attribute) = attribute("set") {
+                synthetics.push(define(attribute, "property", "mutator"));
+            } else if let Some(scope) = model.scopes.iter().find(|scope| scope.range == method.byte_range()) {
+                synthetics.push(define(scope.name.clone(), "function", "scope"));
+            } else if let Some(relation) = model.relations.iter().find(|relation| relation.range == method.byte_range()) {
+                synthetics.push(define(relation.name.clone(), "property", "relation"));
+            } else if is_attribute_method(method, source) {
+                synthetics.push(define(underscore(method_name), "property", "attribute"));
+            }
+        }
+    });
+    synthetics
+}
+
+// Methods declaring attributes since Laravel 9, like `protected function firstName(): Attribute { return Attribute::make(get: ...); }`.
+fn is_attribute_method(method: Node, source: &[u8]) -> bool {
+    let returns_attribute = method.child_by_field_name("return_type")
+        .is_some_and(|return_type| simple_name(text(return_type, source).trim_start_matches('?')) == "Attribute");
+    let mut makes_attribute = false;
+    if let Some(body) = method.child_by_field_name("body") {
+        visit(body, &mut |node| {
+            let scope = node.child_by_field_name("scope").map(|scope| simple_name(text(scope, source)));
+            let name = node.child_by_field_name("name").map(|name| text(name, source));
+            makes_attribute |= node.kind() == "scoped_call_expression" && scope == Some("Attribute") && name == Some("make");
+        });
+    }
+    returns_attribute || makes_attribute
+}
+
+// Name nodes of the members declared at once, like `public $a, $b;`.
+fn names(declaration: Node) -> Vec<Node> {
+    if matches!(declaration.kind(), "class_declaration" | "method_declaration") {
+        return declaration.child_by_field_name("name").into_iter().collect();
+    }
+    let mut walker = declaration.walk();
+    let elements: Vec<Node> = declaration.named_children(&mut walker)
+        .filter(|child| matches!(child.kind(), "property_element" | "const_element"))
+        .collect();
+    elements.into_iter()
+        .filter_map(|element| match element.kind() {
+            "property_element" => element.named_child(0).and_then(|variable| variable.named_child(0)),
+            _ => element.named_child(0),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_read_member_modifiers() {
+        let source = b"<?php\nconst VERSION = 1;\nabstract class Base {\n    var $legacy;\n    protected static $cache, $hits;\n    abstract public function run();\n}\n";
+        let modifiers = modifiers(source);
+
+        assert_eq!(modifiers.into_iter().collect::<Vec<_>>(), vec![
+            (40, vec![String::from("abstract")]),
+            (56, vec![String::from("public")]),
+            (86, vec![String::from("protected"), String::from("static")]),
+            (94, vec![String::from("protected"), String::from("static")]),
+            (129, vec![String::from("public"), String::from("abstract")]),
+        ]);
+    }
+
+    #[test]
+    fn it_should_synthesize_magic_members() {
+        let source = b"<?php\nclass User extends Model {\n    public function getFullNameAttribute() {\n        return \"{$this->first_name} {$this->last_name}\";\n    }\n    public function setPasswordAttribute($password) {\n        $this->attributes['password'] = bcrypt($password);\n    }\n    protected function isAdmin(): Attribute {\n        return Attribute::make(get: fn () => $this->role === 'admin');\n    }\n    public function scopeActive($query) {\n        return $query->where('active', true);\n    }\n    public function posts() {\n        return $this->hasMany(Post::class);\n    }\n    public function helper() {\n        return 1;\n    }\n}\n";
+        let synthetics = magic_members(source);
+
+        let members: Vec<_> = synthetics.iter().map(|synthetic| (synthetic.name.as_str(), synthetic.kind, synthetic.macro_name.as_str(), &source[synthetic.range.clone()])).collect();
+        assert_eq!(members, vec![
+            ("full_name", "property", "accessor", &b"getFullNameAttribute"[..]),
+            ("password", "property", "mutator", &b"setPasswordAttribute"[..]),
+            ("is_admin", "property", "attribute", &b"isAdmin"[..]),
+            ("active", "function", "scope", &b"scopeActive"[..]),
+            ("posts", "property", "relation", &b"posts"[..]),
+        ]);
+    }
+}
@@ -0,0 +1,118 @@
+use std::ops::Range;
+use indexmap::IndexMap;
+use tree_sitter::{Node, Query, QueryCursor};
+use crate::syntax::{parse, text};
+
+// Details of Python source, which can't be expressed by tagging queries.
+
+// Name listed in `__all__` of a module.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Export {
+    pub name: String,
+    // Byte range of the string literal.
+    pub range: Range<usize>,
+}
+
+// Decorators of all decorated functions and classes, keyed by the start byte of their names.
+// Decorators are listed in source order, without their arguments, like `admin.display` for `@admin.display(boolean=True)`.
+pub fn decorators(source: &[u8]) -> IndexMap<usize, Vec<String>> {
+    let tree = parse(tree_sitter_python::language(), source);
+    let query = Query::new(tree_sitter_python::language(), "(decorated_definition) @definition").unwrap();
+    let mut cursor = QueryCursor::new();
+
+    let mut decorators = IndexMap::new();
+    for matched in cursor.matches(&query, tree.root_node(), source) {
+        let definition = matched.captures[0].node;
+        let name = match definition.child_by_field_name("definition").and_then(|node| node.child_by_field_name("name")) {
+            Some(name) => name,
+            None => continue,
+        };
+
+        let mut walker = definition.walk();
+        let names = definition.named_children(&mut walker)
+            .filter(|child| child.kind() == "decorator")
+            .filter_map(|decorator| decorator.named_child(0))
+            .map(|expression| match expression.kind() {
+                "call" => expression.child_by_field_name("function").map_or("", |function| text(function, source)),
+                _ => text(expression, source),
+            })
+            .map(String::from)
+            .collect();
+        decorators.insert(name.start_byte(), names);
+    }
+    decorators
+}
+
+// Names exported by module-level `__all__` list or tuple.
+pub fn exports(source: &[u8]) -> Vec<Export> {
+    let tree = parse(tree_sitter_python::language(), source);
+    let query = Query::new(
+        tree_sitter_python::language(),
+        r#"
+        ((module
+          (expression_statement
+            (assignment
+              left: (identifier) @target
+              right: [(list (string) @export) (tuple (string) @export)])))
+         (#eq? @target "__all__"))
+        "#,
+    ).unwrap();
+    let export_index = query.capture_index_for_name("export").unwrap();
+    let mut cursor = QueryCursor::new();
+
+    let mut exports = vec![];
+    for matched in cursor.matches(&query, tree.root_node(), source) {
+        for capture in matched.captures.iter().filter(|capture| capture.index == export_index) {
+            exports.push(Export {
+                name: String::from(unquote(text(capture.node, source))),
+                range: capture.node.byte_range(),
+            });
+        }
+    }
+    exports
+}
+
+// Whether the function is defined right in the body of a class, with or without decorators.
+pub fn is_method(function: Node) -> bool {
+    let mut node = function;
+    if let Some(parent) = node.parent().filter(|parent| parent.kind() == "decorated_definition") {
+        node = parent;
+    }
+    node.parent()
+        .filter(|block| block.kind() == "block")
+        .and_then(|block| block.parent())
+        .is_some_and(|parent| parent.kind() == "class_definition")
+}
+
+// Content of string literal, without its prefix (like `r` or `b`) and quotes.
+fn unquote(literal: &str) -> &str {
+    let literal = literal.trim_start_matches(|character: char| character.is_ascii_alphabetic());
+    let quote = if literal.starts_with("\"\"\"") || literal.starts_with("'''") { 3 } else { 1 };
+    literal.get(quote..literal.len().saturating_sub(quote)).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_read_decorators() {
+        let source = b"@admin.display(boolean=True)\n@property\ndef published(self):\n    pass\n\n@dataclass\nclass Point:\n    pass\n";
+        let decorators = decorators(source);
+
+        assert_eq!(decorators.len(), 2);
+        assert_eq!(decorators[&43], vec!["admin.display", "property"]);
+        assert_eq!(decorators[&87], vec!["dataclass"]);
+    }
+
+    #[test]
+    fn it_should_read_exports() {
+        let source = b"__all__ = [\"Question\", 'Choice']\nnames = ['ignored']\n";
+        let exports = exports(source);
+
+        assert_eq!(exports, vec![
+            Export { name: String::from("Question"), range: 11..21 },
+            Export { name: String::from("Choice"), range: 23..31 },
+        ]);
+    }
+}
@@ -0,0 +1,597 @@
+use std::ops::Range;
+use sqlparser::ast::{Expr, FunctionArg, FunctionArgExpr, JoinConstraint, JoinOperator, ObjectName, Query, SelectItem, SetExpr, Statement, TableFactor, TableWithJoins};
+use sqlparser::dialect::{Dialect, GenericDialect, MySqlDialect};
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::Token;
+use tree_sitter::{Language, Node};
+use crate::syntax::{children, parse, text, visit};
+
+// Raw SQL given as strings to query methods of ORMs, like `Micropost.where("user_id = :user_id", user_id: id)`,
+// with the tables and columns it refers and the values interpolated into it, which may inject SQL.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Clause {
+    // Whole statements, like the ones of `find_by_sql`.
+    Statement,
+    // Conditions, like the ones of `where`.
+    Condition,
+    // Selected expressions, like the ones of `DB::raw` or `selectRaw`.
+    Expression,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fragment {
+    // Method receiving the SQL, like `where`, `find_by_sql` or `DB::raw`.
+    pub method: String,
+    // SQL of the string, whose interpolated values are replaced by `?` placeholders.
+    pub sql: String,
+    // Whether the SQL grammar parses the fragment, as the tables and columns are read from its syntax.
+    pub parsed: bool,
+    // Tables referred by the SQL, like `relationships` of `SELECT followed_id FROM relationships`.
+    pub tables: Vec<String>,
+    // Columns referred by the SQL as written, like `user_id` or `post.id`.
+    pub columns: Vec<String>,
+    // Byte ranges of the values interpolated into the SQL, like `following_ids` of `"user_id IN (#{following_ids})"`.
+    pub interpolations: Vec<Range<usize>>,
+    // Byte range of the argument holding the SQL.
+    pub range: Range<usize>,
+}
+
+// Method, clause and the argument holding the SQL of a call which is an entry point of raw SQL,
+// and whether values are formatted into placeholders of Python, like `%s`, of the SQL.
+type EntryPoint<'tree> = Option<(String, Clause, Node<'tree>, bool)>;
+
+// Kinds of the nodes holding the content of string literals, rather than interpolated values.
+const CONTENTS: [&str; 6] = ["string_start", "string_content", "string_end", "string_fragment", "string_value", "escape_sequence"];
+
+// `where("user_id = ?", id)`, `having(...)` and `find_by_sql("SELECT ...")` of ActiveRecord.
+pub fn ruby(source: &[u8]) -> Vec<Fragment> {
+    fragments(tree_sitter_ruby::language(), source, &|call| {
+        if call.kind() != "call" {
+            return None;
+        }
+        let method = text(call.child_by_field_name("method")?, source);
+        let clause = match method {
+            "where" | "having" => Clause::Condition,
+            "find_by_sql" => Clause::Statement,
+            _ => return None,
+        };
+        // Conditions may be given as arrays, like `where(["user_id = ?", id])`.
+        let argument = arguments(call).into_iter().next()?;
+        let argument = match argument.kind() {
+            "array" => argument.named_child(0)?,
+            _ => argument,
+        };
+        Some((String::from(method), clause, argument, false))
+    })
+}
+
+// `DB::raw(...)`, `DB::select(...)` and the raw methods of query builders, like `whereRaw(...)`, of Laravel.
+pub fn php(source: &[u8]) -> Vec<Fragment> {
+    fragments(tree_sitter_php::language(), source, &|call| {
+        let (method, clause) = match call.kind() {
+            "scoped_call_expression" => {
+                let scope = text(call.child_by_field_name("scope")?, source);
+                let method = text(call.child_by_field_name("name")?, source);
+                let clause = match method {
+                    _ if scope.rsplit('\\').next() != Some("DB") => return None,
+                    "raw" => Clause::Expression,
+                    "select" | "insert" | "update" | "delete" | "statement" => Clause::Statement,
+                    _ => return None,
+                };
+                (format!("{}::{}", scope, method), clause)
+            }
+            "member_call_expression" => {
+                let method = text(call.child_by_field_name("name")?, source);
+                let clause = match method {
+                    "whereRaw" | "orWhereRaw" | "havingRaw" | "orHavingRaw" => Clause::Condition,
+                    "selectRaw" => Clause::Expression,
+                    _ => return None,
+                };
+                (String::from(method), clause)
+            }
+            _ => return None,
+        };
+        let argument = arguments(call).into_iter().next()?;
+        Some((method, clause, argument, false))
+    })
+}
+
+// `Model.objects.raw("SELECT ...")` and `RawSQL("...")` of Django.
+pub fn python(source: &[u8]) -> Vec<Fragment> {
+    fragments(tree_sitter_python::language(), source, &|call| {
+        if call.kind() != "call" {
+            return None;
+        }
+        let function = call.child_by_field_name("function")?;
+        let (method, clause) = match function.kind() {
+            "attribute" if function.child_by_field_name("attribute").is_some_and(|method| text(method, source) == "raw") => ("raw", Clause::Statement),
+            "identifier" | "attribute" if text(function, source).rsplit('.').next() == Some("RawSQL") => ("RawSQL", Clause::Expression),
+            _ => return None,
+        };
+        let arguments = arguments(call);
+        let argument = arguments.first().copied().filter(|argument| argument.kind() != "keyword_argument")?;
+        // Values are formatted by `%` operator, or by the database with the parameters given next, like `raw("... = %s", [code])`.
+        let operator = argument.child_by_field_name("operator").map(|operator| text(operator, source));
+        let formatted = arguments.len() > 1 || (argument.kind() == "binary_operator" && operator == Some("%"));
+        Some((String::from(method), clause, argument, formatted))
+    })
+}
+
+// `query("SELECT ...")` of data sources, managers and repositories of TypeORM, and conditions of its query builders,
+// like `createQueryBuilder("post").where("post.id = :id", { id })`.
+pub fn javascript(language: Language, source: &[u8]) -> Vec<Fragment> {
+    fragments(language, source, &|call| {
+        if call.kind() != "call_expression" {
+            return None;
+        }
+        let function = call.child_by_field_name("function").filter(|function| function.kind() == "member_expression")?;
+        let method = text(function.child_by_field_name("property")?, source);
+        let clause = match method {
+            "query" => Clause::Statement,
+            "where" | "andWhere" | "orWhere" | "having" | "andHaving" | "orHaving" if is_query_builder(function.child_by_field_name("object")?, source) => Clause::Condition,
+            _ => return None,
+        };
+        let argument = arguments(call).into_iter().next()?;
+        Some((String::from(method), clause, argument, false))
+    })
+}
+
+// Whether the chain of calls starts with `createQueryBuilder()`.
+fn is_query_builder(mut node: Node, source: &[u8]) -> bool {
+    while node.kind() == "call_expression" {
+        let function = match node.child_by_field_name("function") {
+            Some(function) => function,
+            None => return false,
+        };
+        match function.kind() {
+            "member_expression" => {
+                if function.child_by_field_name("property").is_some_and(|property| text(property, source) == "createQueryBuilder") {
+                    return true;
+                }
+                node = match function.child_by_field_name("object") {
+                    Some(object) => object,
+                    None => return false,
+                };
+            }
+            _ => return text(function, source) == "createQueryBuilder",
+        }
+    }
+    false
+}
+
+// Fragments passed to the calls which are recognized as entry points of raw SQL by given function.
+fn fragments(language: Language, source: &[u8], entry_point: &dyn for<'tree> Fn(Node<'tree>) -> EntryPoint<'tree>) -> Vec<Fragment> {
+    let tree = parse(language, source);
+    let mut fragments = vec![];
+    visit(tree.root_node(), &mut |node| {
+        let (method, clause, argument, formatted) = match entry_point(node) {
+            Some(entry_point) => entry_point,
+            None => return,
+        };
+        let mut sql = String::new();
+        let mut interpolations = vec![];
+        // SQL held by variables or returned by functions is not known.
+        if !write_sql(argument, source, &mut sql, &mut interpolations) {
+            return;
+        }
+        let sql = if formatted { placeholders(&sql) } else { sql };
+        let references = references(&sql, clause);
+        let parsed = references.is_some();
+        let (tables, columns) = references.unwrap_or_default();
+        fragments.push(Fragment { method, sql, parsed, tables, columns, interpolations, range: argument.byte_range() });
+    });
+    // Calls chained on calls are visited before them.
+    fragments.sort_by_key(|fragment| fragment.range.start);
+    fragments
+}
+
+// Write SQL of string literals, or of concatenations and formats of them, like `"user_id = " + id` or `"id = %s" % id`.
+// Values other than literals are written as placeholders, and their ranges are pushed to interpolations.
+// Returns whether any literal is written.
+fn write_sql(node: Node, source: &[u8], sql: &mut String, interpolations: &mut Vec<Range<usize>>) -> bool {
+    match node.kind() {
+        "string" | "template_string" | "encapsed_string" => {
+            write_literal(node, source, sql, interpolations);
+            true
+        }
+        "parenthesized_expression" => node.named_child(0).is_some_and(|inner| write_sql(inner, source, sql, interpolations)),
+        "binary" | "binary_operator" | "binary_expression" => {
+            let operator = node.child_by_field_name("operator").map_or("", |operator| text(operator, source));
+            let (left, right) = match (node.child_by_field_name("left"), node.child_by_field_name("right")) {
+                (Some(left), Some(right)) if matches!(operator, "+" | "." | "%") => (left, right),
+                _ => return false,
+            };
+            let written = write_sql(left, source, sql, interpolations);
+            // Values are formatted into the placeholders of the left, like `%s`.
+            if operator == "%" {
+                interpolations.push(right.byte_range());
+                return written;
+            }
+            write_sql(right, source, sql, interpolations) || written
+        }
+        _ => {
+            sql.push('?');
+            interpolations.push(node.byte_range());
+            false
+        }
+    }
+}
+
+fn write_literal(literal: Node, source: &[u8], sql: &mut String, interpolations: &mut Vec<Range<usize>>) {
+    let children = children(literal);
+    let is_delimiter = |child: &&Node| !child.is_named() || matches!(child.kind(), "string_start" | "string_end");
+    let mut start = children.first().filter(is_delimiter).map_or(literal.start_byte(), |quote| quote.end_byte());
+    let mut end = children.last().filter(is_delimiter).map_or(literal.end_byte(), |quote| quote.start_byte());
+    // Literals without children, like `'...'` of PHP.
+    if children.is_empty() && end - start >= 2 && matches!(source[start], b'\'' | b'"' | b'`') {
+        start += 1;
+        end -= 1;
+    }
+
+    let mut position = start;
+    for child in children.iter().filter(|child| child.is_named() && child.start_byte() >= start && child.end_byte() <= end) {
+        sql.push_str(&String::from_utf8_lossy(&source[position..child.start_byte()]));
+        position = child.end_byte();
+        match child.kind() {
+            "escape_sequence" => sql.push_str(unescape(text(*child, source))),
+            kind if CONTENTS.contains(&kind) => sql.push_str(text(*child, source)),
+            // Interpolations like `#{id}` or `${id}`, or variables of PHP strings like `{$id}`.
+            "interpolation" | "template_substitution" => {
+                sql.push('?');
+                interpolations.push(child.named_child(0).unwrap_or(*child).byte_range());
+            }
+            _ => {
+                sql.push('?');
+                interpolations.push(child.byte_range());
+            }
+        }
+    }
+    sql.push_str(&String::from_utf8_lossy(&source[position.min(end)..end]));
+}
+
+fn unescape(escape_sequence: &str) -> &str {
+    match escape_sequence {
+        "\\n" | "\\r" | "\\t" => " ",
+        "\\'" => "'",
+        "\\\"" => "\"",
+        "\\\\" => "\\",
+        "\\`" => "`",
+        escape_sequence => escape_sequence,
+    }
+}
+
+// SQL whose placeholders of Python, like `%s` or `%(id)s`, are replaced by `?` which the SQL grammar reads.
+// Quoted literals of SQL, like `'%smith%'`, are kept as they are.
+fn placeholders(sql: &str) -> String {
+    let mut replaced = String::new();
+    let mut quoted = false;
+    let mut rest = sql;
+    while let Some(index) = rest.find(['%', '\'']) {
+        replaced.push_str(&rest[..index]);
+        let after = &rest[index + 1..];
+        if rest[index..].starts_with('\'') {
+            quoted = !quoted;
+            replaced.push('\'');
+            rest = after;
+            continue;
+        }
+        // Named placeholders, like `%(id)s`.
+        let length = after.strip_prefix('(').and_then(|named| named.find(')')).map_or(0, |close| close + 2);
+        match after[length..].chars().next() {
+            Some('s' | 'd') if !quoted => {
+                replaced.push('?');
+                rest = &after[length + 1..];
+            }
+            _ => {
+                replaced.push('%');
+                rest = after;
+            }
+        }
+    }
+    replaced.push_str(rest);
+    replaced
+}
+
+// Tables and columns referred by SQL, or nothing if the SQL grammar can't parse it.
+fn references(sql: &str, clause: Clause) -> Option<(Vec<String>, Vec<String>)> {
+    // MySQL quotes identifiers with backticks, which the generic dialect doesn't support.
+    let dialect: Box<dyn Dialect> = match sql.contains('`') {
+        true => Box::new(MySqlDialect {}),
+        false => Box::new(GenericDialect {}),
+    };
+    let mut parser = Parser::new(dialect.as_ref()).try_with_sql(sql).ok()?;
+    let mut references = References::default();
+    match clause {
+        Clause::Statement => {
+            for statement in parser.parse_statements().ok()? {
+                references.statement(&statement);
+            }
+        }
+        Clause::Condition => references.expr(&parser.parse_expr().ok()?),
+        Clause::Expression => {
+            for item in parser.parse_comma_separated(Parser::parse_select_item).ok()? {
+                references.select_item(&item);
+            }
+        }
+    }
+    if parser.peek_token().token != Token::EOF {
+        return None;
+    }
+    Some((references.tables, references.columns))
+}
+
+#[derive(Default)]
+struct References {
+    tables: Vec<String>,
+    columns: Vec<String>,
+}
+
+impl References {
+    fn table(&mut self, name: &ObjectName) {
+        let name = name.0.last().map(|ident| ident.value.clone()).unwrap_or_default();
+        if !self.tables.contains(&name) {
+            self.tables.push(name);
+        }
+    }
+
+    fn column(&mut self, name: String) {
+        if !self.columns.contains(&name) {
+            self.columns.push(name);
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Query(query) => self.query(query),
+            Statement::Insert { table_name, columns, source, .. } => {
+                self.table(table_name);
+                for column in columns {
+                    self.column(column.value.clone());
+                }
+                self.query(source);
+            }
+            Statement::Update { table, assignments, from, selection, .. } => {
+                self.table_with_joins(table);
+                for assignment in assignments {
+                    self.column(assignment.id.iter().map(|ident| ident.value.as_str()).collect::<Vec<_>>().join("."));
+                    self.expr(&assignment.value);
+                }
+                if let Some(from) = from {
+                    self.table_with_joins(from);
+                }
+                if let Some(selection) = selection {
+                    self.expr(selection);
+                }
+            }
+            Statement::Delete { from, selection, .. } => {
+                for table in from {
+                    self.table_with_joins(table);
+                }
+                if let Some(selection) = selection {
+                    self.expr(selection);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn query(&mut self, query: &Query) {
+        self.set_expr(&query.body);
+        for order_by in &query.order_by {
+            self.expr(&order_by.expr);
+        }
+    }
+
+    fn set_expr(&mut self, set_expr: &SetExpr) {
+        match set_expr {
+            SetExpr::Select(select) => {
+                for table in &select.from {
+                    self.table_with_joins(table);
+                }
+                for item in &select.projection {
+                    self.select_item(item);
+                }
+                for expr in select.selection.iter().chain(&select.group_by).chain(&select.having) {
+                    self.expr(expr);
+                }
+            }
+            SetExpr::Query(query) => self.query(query),
+            SetExpr::SetOperation { left, right, .. } => {
+                self.set_expr(left);
+                self.set_expr(right);
+            }
+            SetExpr::Values(values) => {
+                for expr in values.rows.iter().flatten() {
+                    self.expr(expr);
+                }
+            }
+            SetExpr::Insert(statement) | SetExpr::Update(statement) => self.statement(statement),
+            SetExpr::Table(_) => {}
+        }
+    }
+
+    fn table_with_joins(&mut self, table: &TableWithJoins) {
+        self.table_factor(&table.relation);
+        for join in &table.joins {
+            self.table_factor(&join.relation);
+            match &join.join_operator {
+                JoinOperator::Inner(constraint)
+                | JoinOperator::LeftOuter(constraint)
+                | JoinOperator::RightOuter(constraint)
+                | JoinOperator::FullOuter(constraint)
+                | JoinOperator::LeftSemi(constraint)
+                | JoinOperator::RightSemi(constraint)
+                | JoinOperator::LeftAnti(constraint)
+                | JoinOperator::RightAnti(constraint) => match constraint {
+                    JoinConstraint::On(expr) => self.expr(expr),
+                    JoinConstraint::Using(columns) => columns.iter().for_each(|column| self.column(column.value.clone())),
+                    JoinConstraint::Natural | JoinConstraint::None => {}
+                },
+                JoinOperator::CrossJoin | JoinOperator::CrossApply | JoinOperator::OuterApply => {}
+            }
+        }
+    }
+
+    fn table_factor(&mut self, factor: &TableFactor) {
+        match factor {
+            TableFactor::Table { name, .. } => self.table(name),
+            TableFactor::Derived { subquery, .. } => self.query(subquery),
+            TableFactor::NestedJoin { table_with_joins, .. } => self.table_with_joins(table_with_joins),
+            _ => {}
+        }
+    }
+
+    fn select_item(&mut self, item: &SelectItem) {
+        match item {
+            SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => self.expr(expr),
+            SelectItem::QualifiedWildcard(..) | SelectItem::Wildcard(_) => {}
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Identifier(ident) => self.column(ident.value.clone()),
+            Expr::CompoundIdentifier(idents) => self.column(idents.iter().map(|ident| ident.value.as_str()).collect::<Vec<_>>().join(".")),
+            Expr::IsFalse(expr)
+            | Expr::IsNotFalse(expr)
+            | Expr::IsTrue(expr)
+            | Expr::IsNotTrue(expr)
+            | Expr::IsNull(expr)
+            | Expr::IsNotNull(expr)
+            | Expr::IsUnknown(expr)
+            | Expr::IsNotUnknown(expr)
+            | Expr::AnyOp(expr)
+            | Expr::AllOp(expr)
+            | Expr::UnaryOp { expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::TryCast { expr, .. }
+            | Expr::Nested(expr) => self.expr(expr),
+            Expr::IsDistinctFrom(left, right) | Expr::IsNotDistinctFrom(left, right) | Expr::BinaryOp { left, right, .. } => {
+                self.expr(left);
+                self.expr(right);
+            }
+            Expr::Like { expr, pattern, .. } | Expr::ILike { expr, pattern, .. } | Expr::SimilarTo { expr, pattern, .. } => {
+                self.expr(expr);
+                self.expr(pattern);
+            }
+            Expr::InList { expr, list, .. } => {
+                self.expr(expr);
+                list.iter().for_each(|item| self.expr(item));
+            }
+            Expr::InSubquery { expr, subquery, .. } => {
+                self.expr(expr);
+                self.query(subquery);
+            }
+            Expr::Between { expr, low, high, .. } => {
+                self.expr(expr);
+                self.expr(low);
+                self.expr(high);
+            }
+            Expr::Function(function) => {
+                for argument in &function.args {
+                    if let FunctionArg::Named { arg: FunctionArgExpr::Expr(expr), .. } | FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) = argument {
+                        self.expr(expr);
+                    }
+                }
+            }
+            Expr::Case { operand, conditions, results, else_result } => {
+                operand.iter().chain(else_result).for_each(|expr| self.expr(expr));
+                conditions.iter().chain(results).for_each(|expr| self.expr(expr));
+            }
+            Expr::Exists { subquery, .. } | Expr::Subquery(subquery) => self.query(subquery),
+            Expr::Tuple(exprs) => exprs.iter().for_each(|expr| self.expr(expr)),
+            _ => {}
+        }
+    }
+}
+
+// Arguments of call, unwrapping the `argument` nodes of PHP.
+fn arguments(call: Node) -> Vec<Node> {
+    call.child_by_field_name("arguments")
+        .map(children)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|argument| argument.is_named() && argument.kind() != "comment")
+        .filter_map(|argument| match argument.kind() {
+            "argument" => argument.named_child(0),
+            _ => Some(argument),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_fixture;
+
+    #[test]
+    fn it_should_read_ruby_conditions() {
+        let source = read_fixture("user.rb");
+        let fragments = ruby(&source);
+
+        assert_eq!(fragments.len(), 1);
+        let feed = &fragments[0];
+        assert_eq!(feed.method, "where");
+        assert_eq!(feed.sql, "user_id IN (?)\n                     OR user_id = :user_id");
+        assert!(feed.parsed);
+        assert_eq!((feed.tables.clone(), feed.columns.clone()), (vec![], vec![String::from("user_id")]));
+        assert_eq!(feed.interpolations.iter().map(|range| &source[range.clone()]).collect::<Vec<_>>(), vec![&b"following_ids"[..]]);
+    }
+
+    #[test]
+    fn it_should_read_laravel_raw_expressions() {
+        let source = b"<?php\n$users = DB::table('users')->select(DB::raw('count(*) as user_count, status'))->whereRaw(\"votes > $min\")->get();\n$orders = DB::table('orders')->selectRaw('price * ? as price_with_tax', [1.0825])->get();\n";
+        let fragments = php(source);
+
+        let references: Vec<_> = fragments.iter()
+            .map(|fragment| (fragment.method.as_str(), fragment.sql.as_str(), fragment.columns.clone(), fragment.interpolations.iter().map(|range| &source[range.clone()]).collect::<Vec<_>>()))
+            .collect();
+        assert_eq!(references, vec![
+            ("DB::raw", "count(*) as user_count, status", vec![String::from("status")], vec![]),
+            ("whereRaw", "votes > ?", vec![String::from("votes")], vec![&b"$min"[..]]),
+            ("selectRaw", "price * ? as price_with_tax", vec![String::from("price")], vec![]),
+        ]);
+    }
+
+    #[test]
+    fn it_should_read_statements() {
+        let source = b"Person.objects.raw(\"SELECT p.id, name FROM people p JOIN teams t ON t.id = p.team_id WHERE t.code = %s\", [code])\nPerson.objects.raw(f\"SELECT * FROM people WHERE name = '{name}'\")\nPerson.objects.raw(\"SELECT FROM WHERE\")\n";
+        let fragments = python(source);
+
+        let references: Vec<_> = fragments.iter().map(|fragment| (fragment.parsed, fragment.tables.clone(), fragment.columns.clone(), fragment.interpolations.len())).collect();
+        let strings = |strings: &[&str]| strings.iter().map(|string| String::from(*string)).collect::<Vec<_>>();
+        assert_eq!(references, vec![
+            (true, strings(&["people", "teams"]), strings(&["t.id", "p.team_id", "p.id", "name", "t.code"]), 0),
+            (true, strings(&["people"]), strings(&["name"]), 1),
+            (false, vec![], vec![], 0),
+        ]);
+    }
+
+    #[test]
+    fn it_should_keep_percent_signs_of_literals() {
+        let source = b"User.where(\"name LIKE '%smith%'\")\n";
+        assert_eq!(ruby(source)[0].sql, "name LIKE '%smith%'");
+
+        let source = b"Person.objects.raw(\"SELECT * FROM people WHERE name LIKE '%s%%' AND team = %s\", [team])\nPerson.objects.raw(\"SELECT * FROM people WHERE code = '%d'\")\n";
+        let fragments = python(source);
+        assert_eq!(fragments[0].sql, "SELECT * FROM people WHERE name LIKE '%s%%' AND team = ?");
+        assert_eq!(fragments[1].sql, "SELECT * FROM people WHERE code = '%d'");
+    }
+
+    #[test]
+    fn it_should_read_query_builders() {
+        let source = b"const posts = await dataSource.getRepository(Post).createQueryBuilder(\"post\").where(\"post.authorId = :id\", { id }).orWhere(\"post.title LIKE '\" + title + \"'\").getMany()\nawait dataSource.query(`UPDATE posts SET views = views + 1 WHERE id = ${id}`)\nfilters.where(\"not sql\")\n";
+        let fragments = javascript(tree_sitter_typescript::language_typescript(), source);
+
+        let references: Vec<_> = fragments.iter()
+            .map(|fragment| (fragment.method.as_str(), fragment.sql.as_str(), fragment.columns.clone(), fragment.interpolations.iter().map(|range| &source[range.clone()]).collect::<Vec<_>>()))
+            .collect();
+        assert_eq!(references, vec![
+            ("where", "post.authorId = :id", vec![String::from("post.authorId")], vec![]),
+            ("orWhere", "post.title LIKE '?'", vec![String::from("post.title")], vec![&b"title"[..]]),
+            ("query", "UPDATE posts SET views = views + 1 WHERE id = ?", vec![String::from("views"), String::from("id")], vec![&b"id"[..]]),
+        ]);
+        assert_eq!(fragments[2].tables, vec!["posts"]);
+    }
+}
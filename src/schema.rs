@@ -0,0 +1,445 @@
+use indexmap::IndexMap;
+use rusqlite::Connection;
+use sqlparser::ast::{AlterTableOperation, ColumnDef, ColumnOption, Expr, ObjectName, ObjectType, Statement, TableConstraint, UnaryOperator};
+use sqlparser::dialect::{Dialect, GenericDialect, MySqlDialect};
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Tokenizer, TokenizerError};
+use crate::ddl::{Column, ForeignKey, Table};
+use crate::migrations::activerecord;
+use crate::models::types::sql_data_type;
+use crate::models::{DataType, Value};
+use crate::syntax::parse;
+
+// Actual schemas of databases, read from SQLite databases, Rails `db/schema.rb` files or SQL dumps,
+// to compare them with the ones which models describe. See `migrations` for the schemas which migrations make.
+
+// Change of schema, made by a migration or a statement of SQL dump.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    CreateTable(Table),
+    DropTable(String),
+    RenameTable { from: String, to: String },
+    AddColumn { table: String, column: Column },
+    // Column replacing the one of the same name, like `change_column :users, :age, :string`.
+    ChangeColumn { table: String, column: Column },
+    RemoveColumn { table: String, column: String },
+    RenameColumn { table: String, from: String, to: String },
+    AddPrimaryKey { table: String, columns: Vec<String> },
+    AddForeignKey { table: String, foreign_key: ForeignKey },
+    RemoveForeignKey { table: String, column: String },
+}
+
+// Tables resulting from changes made in order.
+pub fn replay(changes: impl IntoIterator<Item = Change>) -> Vec<Table> {
+    let mut tables = vec![];
+    for change in changes {
+        apply(&mut tables, change);
+    }
+    resolve_references(&mut tables);
+    tables
+}
+
+// Applies change to tables, ignoring the changes of missing tables and columns.
+pub fn apply(tables: &mut Vec<Table>, change: Change) {
+    let find = |tables: &mut Vec<Table>, name: &str| tables.iter_mut().position(|table| table.name == name);
+    match change {
+        Change::CreateTable(table) => {
+            tables.retain(|other| other.name != table.name);
+            tables.push(table);
+        }
+        Change::DropTable(name) => tables.retain(|table| table.name != name),
+        Change::RenameTable { from, to } => {
+            for table in tables.iter_mut() {
+                if table.name == from {
+                    table.name = to.clone();
+                }
+                for foreign_key in table.foreign_keys.iter_mut().filter(|foreign_key| foreign_key.table == from) {
+                    foreign_key.table = to.clone();
+                }
+            }
+        }
+        Change::AddColumn { table, column } | Change::ChangeColumn { table, column } => {
+            if let Some(index) = find(tables, &table) {
+                let columns = &mut tables[index].columns;
+                match columns.iter_mut().find(|other| other.name == column.name) {
+                    Some(other) => *other = column,
+                    None => columns.push(column),
+                }
+            }
+        }
+        Change::RemoveColumn { table, column } => {
+            if let Some(index) = find(tables, &table) {
+                let table = &mut tables[index];
+                table.columns.retain(|other| other.name != column);
+                table.primary_key.retain(|key| *key != column);
+                table.foreign_keys.retain(|foreign_key| foreign_key.column != column);
+            }
+        }
+        Change::RenameColumn { table: name, from, to } => {
+            let index = match find(tables, &name) {
+                Some(index) if tables[index].column(&from).is_some() => index,
+                _ => return,
+            };
+            let table = &mut tables[index];
+            for column in table.columns.iter_mut().filter(|column| column.name == from) {
+                column.name = to.clone();
+            }
+            for key in table.primary_key.iter_mut().filter(|key| **key == from) {
+                *key = to.clone();
+            }
+            for foreign_key in table.foreign_keys.iter_mut().filter(|foreign_key| foreign_key.column == from) {
+                foreign_key.column = to.clone();
+            }
+            // Keys referring to the column.
+            for foreign_key in tables.iter_mut().flat_map(|table| table.foreign_keys.iter_mut()) {
+                if foreign_key.table == name && foreign_key.references == from {
+                    foreign_key.references = to.clone();
+                }
+            }
+        }
+        Change::AddPrimaryKey { table, columns } => {
+            if let Some(index) = find(tables, &table) {
+                let table = &mut tables[index];
+                for column in table.columns.iter_mut().filter(|column| columns.contains(&column.name)) {
+                    column.nullable = false;
+                }
+                table.primary_key = columns;
+            }
+        }
+        Change::AddForeignKey { table, foreign_key } => {
+            if let Some(index) = find(tables, &table) {
+                if !tables[index].foreign_keys.contains(&foreign_key) {
+                    tables[index].foreign_keys.push(foreign_key);
+                }
+            }
+        }
+        Change::RemoveForeignKey { table, column } => {
+            if let Some(index) = find(tables, &table) {
+                tables[index].foreign_keys.retain(|foreign_key| foreign_key.column != column);
+            }
+        }
+    }
+}
+
+// Tables of SQLite database, like `db/development.sqlite3`.
+pub fn sqlite(connection: &Connection) -> rusqlite::Result<Vec<Table>> {
+    let mut statement = connection.prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY rowid")?;
+    let names = statement.query_map([], |row| row.get::<_, String>(0))?.collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut tables = vec![];
+    for name in names {
+        let mut table = Table::new(&name);
+        let mut columns = connection.prepare("SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?1) ORDER BY cid")?;
+        let mut primary_key = vec![];
+        for row in columns.query_map([&name], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, bool>(2)?, row.get::<_, Option<String>>(3)?, row.get::<_, usize>(4)?))
+        })? {
+            let (name, declared, not_null, default, position) = row?;
+            let mut column = sql_column(&name, &declared, !not_null && position == 0);
+            column.default = default.map(|default| expression(&default));
+            if position > 0 {
+                primary_key.push((position, name));
+            }
+            table.columns.push(column);
+        }
+        primary_key.sort();
+        table.primary_key = primary_key.into_iter().map(|(_, name)| name).collect();
+
+        let mut foreign_keys = connection.prepare("SELECT \"from\", \"table\", \"to\" FROM pragma_foreign_key_list(?1) ORDER BY id, seq")?;
+        for row in foreign_keys.query_map([&name], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, Option<String>>(2)?)))? {
+            let (column, table_name, references) = row?;
+            table.foreign_keys.push(ForeignKey { column, table: table_name, references: references.unwrap_or_default() });
+        }
+        tables.push(table);
+    }
+    resolve_references(&mut tables);
+    Ok(tables)
+}
+
+// Tables of Rails schema, like `create_table "users", force: :cascade do |t| ... end` followed by `add_foreign_key "microposts", "users"`,
+// which are written in the language of migrations.
+pub fn ruby(source: &[u8]) -> Vec<Table> {
+    let tree = parse(tree_sitter_ruby::language(), source);
+    replay(activerecord::changes(tree.root_node(), source))
+}
+
+// Tables of SQL dump, like the ones of `pg_dump --schema-only`, `mysqldump --no-data` or `sqlite3 db.sqlite3 .schema`.
+pub fn sql(source: &str) -> Result<Vec<Table>, TokenizerError> {
+    Ok(replay(sql_changes(source)?))
+}
+
+// Changes made by SQL statements, skipping the ones which are not supported by the SQL grammar, like the ones defining functions.
+pub(crate) fn sql_changes(source: &str) -> Result<Vec<Change>, TokenizerError> {
+    // MySQL quotes identifiers with backticks, which the generic dialect doesn't support.
+    let dialect: Box<dyn Dialect> = match source.contains('`') {
+        true => Box::new(MySqlDialect {}),
+        false => Box::new(GenericDialect {}),
+    };
+    let tokens = Tokenizer::new(dialect.as_ref(), source).tokenize()?;
+
+    let mut changes = vec![];
+    for statement in tokens.split(|token| *token == Token::SemiColon) {
+        let mut tokens: Vec<Token> = statement.iter()
+            .filter(|token| !matches!(token, Token::Whitespace(_)))
+            .cloned()
+            .collect();
+        // `ALTER TABLE ONLY` of PostgreSQL dumps.
+        if tokens.len() > 2 && is_keyword(&tokens[0], "ALTER") && is_keyword(&tokens[1], "TABLE") && is_keyword(&tokens[2], "ONLY") {
+            tokens.remove(2);
+        }
+        if tokens.is_empty() {
+            continue;
+        }
+        if let Ok(statement) = Parser::new(dialect.as_ref()).with_tokens(tokens).parse_statement() {
+            changes.extend(statement_changes(statement));
+        }
+    }
+    Ok(changes)
+}
+
+fn is_keyword(token: &Token, keyword: &str) -> bool {
+    matches!(token, Token::Word(word) if word.value.eq_ignore_ascii_case(keyword))
+}
+
+fn statement_changes(statement: Statement) -> Vec<Change> {
+    match statement {
+        Statement::CreateTable { name, columns, constraints, .. } => {
+            let mut table = Table::new(&object_name(&name));
+            for column in columns {
+                add_column(&mut table, column);
+            }
+            for constraint in constraints {
+                add_constraint(&mut table, constraint);
+            }
+            vec![Change::CreateTable(table)]
+        }
+        Statement::AlterTable { name, operation } => {
+            // Columns and constraints are added to a table holding them only, to take them as changes.
+            let mut table = Table::new(&object_name(&name));
+            let name = table.name.clone();
+            match operation {
+                AlterTableOperation::AddConstraint(constraint) => add_constraint(&mut table, constraint),
+                AlterTableOperation::AddColumn { column_def, .. } => add_column(&mut table, column_def),
+                AlterTableOperation::DropColumn { column_name, .. } => return vec![Change::RemoveColumn { table: name, column: column_name.value }],
+                AlterTableOperation::RenameColumn { old_column_name, new_column_name } => {
+                    return vec![Change::RenameColumn { table: name, from: old_column_name.value, to: new_column_name.value }];
+                }
+                AlterTableOperation::RenameTable { table_name } => return vec![Change::RenameTable { from: name, to: object_name(&table_name) }],
+                _ => return vec![],
+            }
+            let mut changes: Vec<Change> = table.columns.into_iter().map(|column| Change::AddColumn { table: name.clone(), column }).collect();
+            if !table.primary_key.is_empty() {
+                changes.push(Change::AddPrimaryKey { table: name.clone(), columns: table.primary_key });
+            }
+            changes.extend(table.foreign_keys.into_iter().map(|foreign_key| Change::AddForeignKey { table: name.clone(), foreign_key }));
+            changes
+        }
+        Statement::Drop { object_type: ObjectType::Table, names, .. } => names.iter().map(|name| Change::DropTable(object_name(name))).collect(),
+        _ => vec![],
+    }
+}
+
+fn add_column(table: &mut Table, definition: ColumnDef) {
+    let mut column = sql_column(&definition.name.value, &definition.data_type.to_string(), true);
+    for option in definition.options {
+        match option.option {
+            ColumnOption::NotNull => column.nullable = false,
+            ColumnOption::Default(expr) => column.default = Some(value(&expr)),
+            ColumnOption::Unique { is_primary: true } => {
+                column.nullable = false;
+                table.primary_key = vec![column.name.clone()];
+            }
+            ColumnOption::ForeignKey { foreign_table, referred_columns, .. } => table.foreign_keys.push(ForeignKey {
+                column: column.name.clone(),
+                table: object_name(&foreign_table),
+                references: referred_columns.first().map(|column| column.value.clone()).unwrap_or_default(),
+            }),
+            _ => {}
+        }
+    }
+    table.columns.push(column);
+}
+
+fn add_constraint(table: &mut Table, constraint: TableConstraint) {
+    match constraint {
+        TableConstraint::Unique { columns, is_primary: true, .. } => {
+            table.primary_key = columns.into_iter().map(|column| column.value).collect();
+            for column in table.columns.iter_mut().filter(|column| table.primary_key.contains(&column.name)) {
+                column.nullable = false;
+            }
+        }
+        // Composite foreign keys are kept as the foreign keys of their columns.
+        TableConstraint::ForeignKey { columns, foreign_table, referred_columns, .. } => {
+            for (index, column) in columns.into_iter().enumerate() {
+                table.foreign_keys.push(ForeignKey {
+                    column: column.value,
+                    table: object_name(&foreign_table),
+                    references: referred_columns.get(index).map(|column| column.value.clone()).unwrap_or_default(),
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+// Name of table without schema, like `users` for `public.users`.
+fn object_name(name: &ObjectName) -> String {
+    name.0.last().map(|ident| ident.value.clone()).unwrap_or_default()
+}
+
+// Column of declared SQL type, with the length of strings like 255 for `varchar(255)`.
+pub(crate) fn sql_column(name: &str, declared: &str, nullable: bool) -> Column {
+    let mut column = Column::new(name, sql_data_type(declared), nullable);
+    if column.data_type == DataType::String {
+        column.length = declared.split_once('(').and_then(|(_, parameters)| parameters.split([')', ',']).next()?.trim().parse().ok());
+    }
+    column
+}
+
+// Value of default expression, like the one reported by SQLite as `'draft'`.
+pub(crate) fn expression(source: &str) -> Value {
+    match Parser::new(&GenericDialect {}).try_with_sql(source).and_then(|mut parser| parser.parse_expr()) {
+        Ok(expr) => value(&expr),
+        Err(_) => Value::Expression(String::from(source)),
+    }
+}
+
+fn value(expr: &Expr) -> Value {
+    use sqlparser::ast::Value as Literal;
+    match expr {
+        Expr::Value(Literal::Number(number, _)) => number.parse().map_or_else(|_| Value::Expression(expr.to_string()), Value::Number),
+        Expr::Value(Literal::SingleQuotedString(string)) => Value::String(string.clone()),
+        Expr::Value(Literal::Boolean(boolean)) => Value::Boolean(*boolean),
+        Expr::Value(Literal::Null) => Value::Null,
+        Expr::UnaryOp { op: UnaryOperator::Minus, expr: operand } => match value(operand) {
+            Value::Number(number) => Value::Number(-number),
+            _ => Value::Expression(expr.to_string()),
+        },
+        // Casts like `'draft'::character varying` of PostgreSQL dumps.
+        Expr::Cast { expr, .. } | Expr::Nested(expr) => value(expr),
+        _ => Value::Expression(expr.to_string()),
+    }
+}
+
+// Foreign keys which don't name the referred columns refer to the primary keys of tables.
+fn resolve_references(tables: &mut [Table]) {
+    let primary_keys: IndexMap<String, String> = tables.iter()
+        .filter_map(|table| match table.primary_key.as_slice() {
+            [key] => Some((table.name.clone(), key.clone())),
+            _ => None,
+        })
+        .collect();
+    for foreign_key in tables.iter_mut().flat_map(|table| table.foreign_keys.iter_mut()).filter(|foreign_key| foreign_key.references.is_empty()) {
+        foreign_key.references = primary_keys.get(&foreign_key.table).cloned().unwrap_or_else(|| String::from("id"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns(table: &Table) -> Vec<(&str, DataType, Option<u32>, bool)> {
+        table.columns.iter().map(|column| (column.name.as_str(), column.data_type, column.length, column.nullable)).collect()
+    }
+
+    #[test]
+    fn it_should_read_sqlite_databases() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection.execute_batch("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, email VARCHAR(100) NOT NULL, admin BOOLEAN DEFAULT 0);
+            CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users, body TEXT);").unwrap();
+        let tables = sqlite(&connection).unwrap();
+
+        assert_eq!(tables.iter().map(|table| table.name.as_str()).collect::<Vec<_>>(), vec!["users", "posts"]);
+        assert_eq!(columns(&tables[0]), vec![
+            ("id", DataType::Integer, None, false),
+            ("email", DataType::String, Some(100), false),
+            ("admin", DataType::Boolean, None, true),
+        ]);
+        assert_eq!(tables[0].columns[2].default, Some(Value::Number(0.0)));
+        assert_eq!(tables[1].primary_key, vec!["id"]);
+        assert_eq!(tables[1].foreign_keys, vec![ForeignKey { column: String::from("user_id"), table: String::from("users"), references: String::from("id") }]);
+    }
+
+    #[test]
+    fn it_should_read_rails_schemas() {
+        let source = br#"ActiveRecord::Schema[7.0].define(version: 2023_05_01_120000) do
+  create_table "users", force: :cascade do |t|
+    t.string "email", limit: 100, null: false
+    t.boolean "admin", default: false
+    t.timestamps
+    t.index ["email"], name: "index_users_on_email", unique: true
+  end
+
+  create_table "microposts", id: :uuid, force: :cascade do |t|
+    t.text "content"
+    t.references "user", null: false, foreign_key: true
+    t.references "attachable", polymorphic: true
+  end
+
+  create_table "relationships", id: false do |t|
+    t.column "follower_id", :integer
+  end
+
+  add_foreign_key "relationships", "users", column: "follower_id"
+end
+"#;
+        let tables = ruby(source);
+
+        assert_eq!(columns(&tables[0]), vec![
+            ("id", DataType::BigInteger, None, false),
+            ("email", DataType::String, Some(100), false),
+            ("admin", DataType::Boolean, None, true),
+            ("created_at", DataType::DateTime, None, false),
+            ("updated_at", DataType::DateTime, None, false),
+        ]);
+        assert_eq!(tables[0].columns[2].default, Some(Value::Boolean(false)));
+        assert_eq!(columns(&tables[1]), vec![
+            ("id", DataType::Uuid, None, false),
+            ("content", DataType::Text, None, true),
+            ("user_id", DataType::BigInteger, None, false),
+            ("attachable_id", DataType::BigInteger, None, true),
+            ("attachable_type", DataType::String, None, true),
+        ]);
+        assert_eq!(tables[1].foreign_keys[0].table, "users");
+        assert_eq!(columns(&tables[2]), vec![("follower_id", DataType::Integer, None, true)]);
+        assert!(tables[2].primary_key.is_empty());
+        assert_eq!(tables[2].foreign_keys, vec![ForeignKey { column: String::from("follower_id"), table: String::from("users"), references: String::from("id") }]);
+    }
+
+    #[test]
+    fn it_should_read_sql_dumps() {
+        let source = "SET statement_timeout = 0;
+CREATE TABLE public.polls_question (
+    id bigint NOT NULL,
+    question_text character varying(200) NOT NULL,
+    status character varying(20) DEFAULT 'draft'::character varying,
+    pub_date timestamp with time zone NOT NULL
+);
+CREATE TABLE public.polls_choice (
+    id bigserial PRIMARY KEY,
+    question_id bigint NOT NULL,
+    votes integer DEFAULT -1 NOT NULL
+);
+CREATE FUNCTION public.touch() RETURNS trigger AS $$ BEGIN RETURN NEW; END; $$ LANGUAGE plpgsql;
+ALTER TABLE ONLY public.polls_question ADD CONSTRAINT polls_question_pkey PRIMARY KEY (id);
+ALTER TABLE ONLY public.polls_choice
+    ADD CONSTRAINT polls_choice_question_id_fk FOREIGN KEY (question_id) REFERENCES public.polls_question(id);
+";
+        let tables = sql(source).unwrap();
+
+        assert_eq!(columns(&tables[0]), vec![
+            ("id", DataType::BigInteger, None, false),
+            ("question_text", DataType::String, Some(200), false),
+            ("status", DataType::String, Some(20), true),
+            ("pub_date", DataType::DateTime, None, false),
+        ]);
+        assert_eq!(tables[0].primary_key, vec!["id"]);
+        assert_eq!(tables[0].columns[2].default, Some(Value::String(String::from("draft"))));
+        assert_eq!(tables[1].primary_key, vec!["id"]);
+        assert_eq!(tables[1].columns[2].default, Some(Value::Number(-1.0)));
+        assert_eq!(tables[1].foreign_keys, vec![ForeignKey { column: String::from("question_id"), table: String::from("polls_question"), references: String::from("id") }]);
+
+        let tables = sql("CREATE TABLE `users` (\n  `id` int unsigned NOT NULL AUTO_INCREMENT,\n  `admin` tinyint(1) NOT NULL DEFAULT '0',\n  PRIMARY KEY (`id`),\n  KEY `index_admin` (`admin`)\n) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;").unwrap();
+        assert_eq!(columns(&tables[0]), vec![("id", DataType::Integer, None, false), ("admin", DataType::Boolean, None, false)]);
+    }
+}
@@ -0,0 +1,397 @@
+use std::fmt;
+use indexmap::IndexMap;
+use tree_sitter::{Language, Node};
+use crate::python::is_method;
+use crate::syntax::{children, parse, text, visit};
+
+// Signatures of functions and methods, keyed by the start byte of their names.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterKind {
+    Required,
+    // Positional parameter having default value.
+    Optional,
+    // Collects the rest of positional arguments, like `*args` or `...rest`.
+    Rest,
+    // Passed by name, like `key:` in Ruby or the ones after `*` in Python.
+    Keyword,
+    // Collects the rest of keyword arguments, like `**options`.
+    KeywordRest,
+    // Ruby block, like `&block`.
+    Block,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    pub name: String,
+    pub kind: ParameterKind,
+    pub default: Option<String>,
+    pub type_annotation: Option<String>,
+}
+
+impl Parameter {
+    fn new(name: &str, kind: ParameterKind) -> Self {
+        Parameter {
+            name: String::from(name),
+            kind,
+            default: None,
+            type_annotation: None,
+        }
+    }
+
+    // Whether the parameter must be given by the caller.
+    pub fn is_required(&self) -> bool {
+        matches!(self.kind, ParameterKind::Required | ParameterKind::Keyword) && self.default.is_none()
+    }
+}
+
+impl fmt::Display for Parameter {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let prefix = match self.kind {
+            ParameterKind::Rest => "*",
+            ParameterKind::KeywordRest => "**",
+            ParameterKind::Block => "&",
+            _ => "",
+        };
+        write!(formatter, "{}{}", prefix, self.name)?;
+        // Optional parameters of TypeScript, like `limit?: number`, are the only ones without defaults.
+        if self.kind == ParameterKind::Optional && self.default.is_none() {
+            formatter.write_str("?")?;
+        }
+        if let Some(type_annotation) = &self.type_annotation {
+            write!(formatter, ": {}", type_annotation)?;
+        }
+        if let Some(default) = &self.default {
+            write!(formatter, " = {}", default)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Signature {
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<String>,
+}
+
+impl Signature {
+    // Minimum and maximum number of positional arguments, without maximum when rest ones are collected.
+    pub fn arity(&self) -> (usize, Option<usize>) {
+        let mut minimum = 0;
+        let mut maximum = Some(0);
+        for parameter in &self.parameters {
+            match parameter.kind {
+                ParameterKind::Required => {
+                    minimum += 1;
+                    maximum = maximum.map(|maximum| maximum + 1);
+                }
+                ParameterKind::Optional => maximum = maximum.map(|maximum| maximum + 1),
+                ParameterKind::Rest => maximum = None,
+                _ => {}
+            }
+        }
+        (minimum, maximum)
+    }
+
+    // Whether a call passing given number of positional arguments matches the arity.
+    pub fn accepts(&self, arguments: usize) -> bool {
+        let (minimum, maximum) = self.arity();
+        match maximum {
+            Some(maximum) => (minimum..=maximum).contains(&arguments),
+            None => minimum <= arguments,
+        }
+    }
+}
+
+// Keyword-only parameters are separated by `*` as Python does, unless rest ones precede them.
+impl fmt::Display for Signature {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let mut parameters = vec![];
+        let mut positional = true;
+        for parameter in &self.parameters {
+            match parameter.kind {
+                ParameterKind::Rest => positional = false,
+                ParameterKind::Keyword if positional => {
+                    parameters.push(String::from("*"));
+                    positional = false;
+                }
+                _ => {}
+            }
+            parameters.push(parameter.to_string());
+        }
+        write!(formatter, "({})", parameters.join(", "))?;
+        if let Some(return_type) = &self.return_type {
+            write!(formatter, ": {}", return_type)?;
+        }
+        Ok(())
+    }
+}
+
+// Signatures of JavaScript and TypeScript functions and methods.
+pub fn javascript(language: Language, source: &[u8]) -> IndexMap<usize, Signature> {
+    let tree = parse(language, source);
+
+    let mut signatures = IndexMap::new();
+    visit(tree.root_node(), &mut |node| {
+        let (name, function) = match node.kind() {
+            "function_declaration" | "generator_function_declaration" | "function_signature" | "method_definition"
+            | "method_signature" | "abstract_method_signature" => match node.child_by_field_name("name") {
+                Some(name) => (name, node),
+                None => return,
+            },
+            // Functions assigned to variables, like `const load = async (id) => {}`.
+            "variable_declarator" => match (node.child_by_field_name("name"), node.child_by_field_name("value")) {
+                (Some(name), Some(value)) if matches!(value.kind(), "arrow_function" | "function" | "generator_function") => {
+                    (name, value)
+                }
+                _ => return,
+            },
+            _ => return,
+        };
+
+        let parameters = match (function.child_by_field_name("parameters"), function.child_by_field_name("parameter")) {
+            (Some(parameters), _) => children(parameters).into_iter().filter_map(|parameter| javascript_parameter(parameter, source)).collect(),
+            // Arrow function having single parameter without parentheses.
+            (None, Some(parameter)) => vec![Parameter::new(text(parameter, source), ParameterKind::Required)],
+            (None, None) => vec![],
+        };
+        let return_type = function.child_by_field_name("return_type").map(|return_type| type_annotation(return_type, source));
+        signatures.insert(name.start_byte(), Signature { parameters, return_type });
+    });
+    signatures
+}
+
+fn javascript_parameter(node: Node, source: &[u8]) -> Option<Parameter> {
+    match node.kind() {
+        "identifier" | "object_pattern" | "array_pattern" => Some(Parameter::new(text(node, source), ParameterKind::Required)),
+        "assignment_pattern" => {
+            let mut parameter = Parameter::new(text(node.child_by_field_name("left")?, source), ParameterKind::Optional);
+            parameter.default = node.child_by_field_name("right").map(|right| String::from(text(right, source)));
+            Some(parameter)
+        }
+        "rest_pattern" => Some(Parameter::new(text(node.named_child(0)?, source), ParameterKind::Rest)),
+        // TypeScript parameters, which may have modifiers, decorators and type annotation.
+        "required_parameter" | "optional_parameter" => {
+            let children = children(node);
+            let pattern = children.iter()
+                .find(|child| matches!(child.kind(), "identifier" | "this" | "object_pattern" | "array_pattern" | "rest_pattern"))?;
+            let mut parameter = match pattern.kind() {
+                "rest_pattern" => Parameter::new(text(pattern.named_child(0)?, source), ParameterKind::Rest),
+                _ if node.kind() == "optional_parameter" || node.child_by_field_name("value").is_some() => {
+                    Parameter::new(text(*pattern, source), ParameterKind::Optional)
+                }
+                _ => Parameter::new(text(*pattern, source), ParameterKind::Required),
+            };
+            parameter.default = node.child_by_field_name("value").map(|value| String::from(text(value, source)));
+            parameter.type_annotation = children.iter()
+                .find(|child| child.kind() == "type_annotation")
+                .map(|annotation| type_annotation(*annotation, source));
+            Some(parameter)
+        }
+        _ => None,
+    }
+}
+
+// Type of TypeScript annotation, without leading colon.
+fn type_annotation(node: Node, source: &[u8]) -> String {
+    String::from(text(node, source).trim_start_matches(':').trim())
+}
+
+// Signatures of Ruby methods.
+pub fn ruby(source: &[u8]) -> IndexMap<usize, Signature> {
+    let tree = parse(tree_sitter_ruby::language(), source);
+
+    let mut signatures = IndexMap::new();
+    visit(tree.root_node(), &mut |node| {
+        if !matches!(node.kind(), "method" | "singleton_method") {
+            return;
+        }
+        let name = match node.child_by_field_name("name") {
+            Some(name) => name,
+            None => return,
+        };
+        let parameters = node.child_by_field_name("parameters")
+            .map(|parameters| children(parameters).into_iter().filter_map(|parameter| ruby_parameter(parameter, source)).collect())
+            .unwrap_or_default();
+        signatures.insert(name.start_byte(), Signature { parameters, return_type: None });
+    });
+    signatures
+}
+
+fn ruby_parameter(node: Node, source: &[u8]) -> Option<Parameter> {
+    let name = node.child_by_field_name("name").map_or("", |name| text(name, source));
+    let kind = match node.kind() {
+        "identifier" | "destructured_parameter" => return Some(Parameter::new(text(node, source), ParameterKind::Required)),
+        // `...` forwards all arguments.
+        "forward_parameter" => return Some(Parameter::new("...", ParameterKind::Rest)),
+        "optional_parameter" => ParameterKind::Optional,
+        "splat_parameter" => ParameterKind::Rest,
+        "keyword_parameter" => ParameterKind::Keyword,
+        "hash_splat_parameter" => ParameterKind::KeywordRest,
+        "block_parameter" => ParameterKind::Block,
+        _ => return None,
+    };
+    let mut parameter = Parameter::new(name, kind);
+    parameter.default = node.child_by_field_name("value").map(|value| String::from(text(value, source)));
+    Some(parameter)
+}
+
+// Signatures of PHP functions and methods.
+pub fn php(source: &[u8]) -> IndexMap<usize, Signature> {
+    let tree = parse(tree_sitter_php::language(), source);
+
+    let mut signatures = IndexMap::new();
+    visit(tree.root_node(), &mut |node| {
+        if !matches!(node.kind(), "function_definition" | "method_declaration") {
+            return;
+        }
+        let name = match node.child_by_field_name("name") {
+            Some(name) => name,
+            None => return,
+        };
+        let parameters = node.child_by_field_name("parameters")
+            .map(|parameters| children(parameters).into_iter().filter_map(|parameter| php_parameter(parameter, source)).collect())
+            .unwrap_or_default();
+        let return_type = node.child_by_field_name("return_type").map(|return_type| String::from(text(return_type, source)));
+        signatures.insert(name.start_byte(), Signature { parameters, return_type });
+    });
+    signatures
+}
+
+fn php_parameter(node: Node, source: &[u8]) -> Option<Parameter> {
+    let kind = match node.kind() {
+        "simple_parameter" | "property_promotion_parameter" if node.child_by_field_name("default_value").is_some() => ParameterKind::Optional,
+        "simple_parameter" | "property_promotion_parameter" => ParameterKind::Required,
+        "variadic_parameter" => ParameterKind::Rest,
+        _ => return None,
+    };
+    let mut parameter = Parameter::new(text(node.child_by_field_name("name")?, source), kind);
+    parameter.default = node.child_by_field_name("default_value").map(|value| String::from(text(value, source)));
+    parameter.type_annotation = node.child_by_field_name("type").map(|type_node| String::from(text(type_node, source)));
+    Some(parameter)
+}
+
+// Signatures of Python functions, whose methods don't include the leading `self` or `cls`.
+pub fn python(source: &[u8]) -> IndexMap<usize, Signature> {
+    let tree = parse(tree_sitter_python::language(), source);
+
+    let mut signatures = IndexMap::new();
+    visit(tree.root_node(), &mut |node| {
+        if node.kind() != "function_definition" {
+            return;
+        }
+        let name = match node.child_by_field_name("name") {
+            Some(name) => name,
+            None => return,
+        };
+
+        let mut parameters = vec![];
+        // Parameters after `*` or `*args` can only be passed by name.
+        let mut keyword_only = false;
+        for child in node.child_by_field_name("parameters").map(children).unwrap_or_default() {
+            let (pattern, type_node) = match child.kind() {
+                "typed_parameter" => (child.named_child(0), child.child_by_field_name("type")),
+                "keyword_separator" | "*" => {
+                    keyword_only = true;
+                    continue;
+                }
+                _ => (Some(child), child.child_by_field_name("type")),
+            };
+            let pattern = match pattern {
+                Some(pattern) => pattern,
+                None => continue,
+            };
+            let (name, kind) = match pattern.kind() {
+                "identifier" if keyword_only => (pattern, ParameterKind::Keyword),
+                "identifier" => (pattern, ParameterKind::Required),
+                "default_parameter" | "typed_default_parameter" => match pattern.child_by_field_name("name") {
+                    Some(name) if keyword_only => (name, ParameterKind::Keyword),
+                    Some(name) => (name, ParameterKind::Optional),
+                    None => continue,
+                },
+                "list_splat_pattern" => {
+                    keyword_only = true;
+                    match pattern.named_child(0) {
+                        Some(name) => (name, ParameterKind::Rest),
+                        None => continue,
+                    }
+                }
+                "dictionary_splat_pattern" => match pattern.named_child(0) {
+                    Some(name) => (name, ParameterKind::KeywordRest),
+                    None => continue,
+                },
+                _ => continue,
+            };
+            let mut parameter = Parameter::new(text(name, source), kind);
+            parameter.default = pattern.child_by_field_name("value").map(|value| String::from(text(value, source)));
+            parameter.type_annotation = type_node.map(|type_node| String::from(text(type_node, source)));
+            parameters.push(parameter);
+        }
+        if is_method(node) && parameters.first().is_some_and(|parameter| matches!(parameter.name.as_str(), "self" | "cls")) {
+            parameters.remove(0);
+        }
+
+        let return_type = node.child_by_field_name("return_type").map(|return_type| String::from(text(return_type, source)));
+        signatures.insert(name.start_byte(), Signature { parameters, return_type });
+    });
+    signatures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signature_of(signatures: &IndexMap<usize, Signature>, source: &[u8], name: &str) -> String {
+        let start = String::from_utf8_lossy(source).find(name).unwrap();
+        signatures[&start].to_string()
+    }
+
+    #[test]
+    fn it_should_read_js_signatures() {
+        let source = b"function find(id: number, options = {}, ...fields: string[]): Promise<User> {}\nclass Repo {\n  constructor(private readonly db: Db, { cache }: Options, limit?: number) {}\n}\nconst load = id => id;\n";
+        let signatures = javascript(tree_sitter_typescript::language_typescript(), source);
+
+        assert_eq!(signature_of(&signatures, source, "find"), "(id: number, options = {}, *fields: string[]): Promise<User>");
+        assert_eq!(signature_of(&signatures, source, "constructor"), "(db: Db, { cache }: Options, limit?: number)");
+        assert_eq!(signature_of(&signatures, source, "load"), "(id)");
+        assert_eq!(signatures[&9].arity(), (1, None));
+    }
+
+    #[test]
+    fn it_should_read_ruby_signatures() {
+        let source = b"def authenticated?(attribute, token); end\ndef fetch(key, default = nil, *rest, strict:, scope: :all, **options, &block); end\ndef self.digest string\nend\n";
+        let signatures = ruby(source);
+
+        assert_eq!(signature_of(&signatures, source, "authenticated?"), "(attribute, token)");
+        assert_eq!(signature_of(&signatures, source, "fetch"), "(key, default = nil, *rest, strict, scope = :all, **options, &block)");
+        assert_eq!(signature_of(&signatures, source, "digest"), "(string)");
+
+        let fetch = &signatures[&46];
+        assert_eq!(fetch.arity(), (1, None));
+        assert!(fetch.parameters[3].is_required());
+        assert!(!fetch.parameters[4].is_required());
+    }
+
+    #[test]
+    fn it_should_read_php_signatures() {
+        let source = b"<?php\nfunction retry(callable $callback, int $attempts = 3, ...$arguments): mixed {}\nclass User {\n    public function setPasswordAttribute($password) {}\n}\n";
+        let signatures = php(source);
+
+        assert_eq!(signature_of(&signatures, source, "retry"), "($callback: callable, $attempts: int = 3, *$arguments): mixed");
+        assert_eq!(signature_of(&signatures, source, "setPasswordAttribute"), "($password)");
+    }
+
+    #[test]
+    fn it_should_read_python_signatures() {
+        let source = b"class Question:\n    def was_published_recently(self, now: datetime = None) -> bool:\n        pass\n\ndef paginate(items, /, size=10, *, page: int, **filters):\n    pass\n";
+        let signatures = python(source);
+
+        assert_eq!(signature_of(&signatures, source, "was_published_recently"), "(now: datetime = None): bool");
+        assert_eq!(signature_of(&signatures, source, "paginate"), "(items, size = 10, *, page: int, **filters)");
+
+        let paginate = &signatures[&102];
+        assert_eq!(paginate.arity(), (1, Some(2)));
+        assert!(paginate.accepts(2));
+        assert!(!paginate.accepts(3));
+    }
+}
@@ -0,0 +1,28 @@
+use tree_sitter::{Language, Node, Parser, Tree};
+
+// Helpers to walk syntax trees.
+
+pub fn parse(language: Language, source: &[u8]) -> Tree {
+    let mut parser = Parser::new();
+    parser.set_language(language).expect("Can't load grammar");
+    parser.parse(source, None).expect("Can't parse source code")
+}
+
+// Visit all named nodes in depth-first order.
+pub fn visit<'a>(node: Node<'a>, callback: &mut impl FnMut(Node<'a>)) {
+    callback(node);
+    for child in children(node) {
+        if child.is_named() {
+            visit(child, callback);
+        }
+    }
+}
+
+pub fn children(node: Node) -> Vec<Node> {
+    let mut walker = node.walk();
+    node.children(&mut walker).collect()
+}
+
+pub fn text<'a>(node: Node, source: &'a [u8]) -> &'a str {
+    node.utf8_text(source).unwrap_or("")
+}